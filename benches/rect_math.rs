@@ -0,0 +1,81 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Compares the clip helpers with the generic rect math and with alternative formulations over a frame worth of
+// widget rects
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use microui_redux::*;
+use rand::{Rng, SeedableRng};
+
+fn rects(count: usize) -> Vec<Recti> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    (0..count)
+        .map(|_| rect(rng.gen_range(-200..1200), rng.gen_range(-200..1000), rng.gen_range(1..300), rng.gen_range(1..40)))
+        .collect()
+}
+
+fn check_clip_branchy(r: &Recti, cr: &Recti) -> Clip {
+    if r.x > cr.x + cr.width || r.x + r.width < cr.x || r.y > cr.y + cr.height || r.y + r.height < cr.y {
+        return Clip::All;
+    }
+    if r.x >= cr.x && r.x + r.width <= cr.x + cr.width && r.y >= cr.y && r.y + r.height <= cr.y + cr.height {
+        return Clip::None;
+    }
+    Clip::Part
+}
+
+fn bench(c: &mut Criterion) {
+    let rects = rects(10_000);
+    let clip = rect(0, 0, 1024, 768);
+
+    c.bench_function("check_clip/branchy", |b| {
+        b.iter(|| rects.iter().filter(|r| check_clip_branchy(black_box(r), &clip) == Clip::Part).count())
+    });
+    c.bench_function("check_clip/branch_free", |b| {
+        b.iter(|| {
+            rects
+                .iter()
+                .filter(|r| {
+                    let r = black_box(r);
+                    rect_touches(r, &clip) & !rect_contains_rect(&clip, r)
+                })
+                .count()
+        })
+    });
+    c.bench_function("check_clip/rect_clip_state", |b| {
+        b.iter(|| rects.iter().filter(|r| rect_clip_state(black_box(r), &clip) == Clip::Part).count())
+    });
+    c.bench_function("intersect/rs_math3d", |b| {
+        b.iter(|| rects.iter().map(|r| black_box(r).intersect(&clip).unwrap_or_default().width).sum::<i32>())
+    });
+    c.bench_function("intersect/rect_intersect", |b| b.iter(|| rects.iter().map(|r| rect_intersect(black_box(r), &clip).width).sum::<i32>()));
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,156 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Frames of a synthetic stress UI: layout and command recording (`frame/*`) and the canvas batching down to a
+// renderer that only counts what it receives (`render/*`). The frame stats are printed once per scene so
+// the number of commands and vertices can be compared along with the timings.
+use criterion::{criterion_group, criterion_main, Criterion};
+use microui_redux::*;
+
+#[derive(Default)]
+struct CountingRenderer {
+    atlas: Option<AtlasHandle>,
+    vertices: usize,
+    flushes: usize,
+}
+
+impl Renderer for CountingRenderer {
+    fn get_atlas(&self) -> AtlasHandle {
+        self.atlas.clone().unwrap()
+    }
+    fn begin(&mut self, _width: i32, _height: i32, _clr: Color) {
+        self.vertices = 0;
+        self.flushes = 0;
+    }
+    fn push_quad_vertices(&mut self, _v0: &Vertex, _v1: &Vertex, _v2: &Vertex, _v3: &Vertex) {
+        self.vertices += 4;
+    }
+    fn flush(&mut self) {
+        self.flushes += 1;
+    }
+    fn end(&mut self) {}
+}
+
+fn atlas() -> AtlasHandle {
+    let config = builder::Config {
+        texture_height: 256,
+        texture_width: 256,
+        white_icon: String::from("assets/WHITE.png"),
+        close_icon: String::from("assets/CLOSE.png"),
+        expand_icon: String::from("assets/PLUS.png"),
+        collapse_icon: String::from("assets/MINUS.png"),
+        check_icon: String::from("assets/CHECK.png"),
+        default_font: String::from("assets/NORMAL.ttf"),
+        default_font_size: 12,
+        slots: &[],
+        packing: PackingAlgorithm::Skyline,
+    };
+    builder::Builder::from_config(&config).unwrap().to_atlas()
+}
+
+fn context() -> (Context<CountingRenderer>, RendererHandle<CountingRenderer>) {
+    let renderer = RendererHandle::new(CountingRenderer { atlas: Some(atlas()), ..Default::default() });
+    (Context::new(renderer.clone(), Dimensioni::new(1280, 720)), renderer)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Scene {
+    Buttons,  // 10k buttons in a single scrolled window
+    Windows,  // 1k small windows
+    HugeText, // a wrapped text of about 1MB
+}
+
+struct Stress {
+    scene: Scene,
+    windows: Vec<WindowHandle>,
+    text: String,
+}
+
+impl Stress {
+    fn new(ctx: &mut Context<CountingRenderer>, scene: Scene) -> Self {
+        let count = match scene {
+            Scene::Windows => 1000,
+            _ => 1,
+        };
+        let windows = (0..count).map(|i| ctx.new_window(&format!("window {}", i), rect((i % 40) * 30, (i / 40) * 28, 200, 120))).collect();
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(24_000);
+        Self { scene, windows, text }
+    }
+
+    fn frame(&mut self, ctx: &mut Context<CountingRenderer>) {
+        let (scene, text) = (self.scene, &self.text);
+        ctx.frame(|ctx| {
+            for w in &mut self.windows {
+                ctx.window(w, ContainerOption::NONE, |cont| {
+                    match scene {
+                        Scene::Buttons => {
+                            cont.set_row_widths_height(&[100; 10], 0);
+                            for i in 0..10_000 {
+                                cont.button_ex(&format!("button {}", i), None, WidgetOption::NONE);
+                            }
+                        }
+                        Scene::Windows => {
+                            cont.set_row_widths_height(&[-1], 0);
+                            cont.label("label");
+                            cont.button_ex("button", None, WidgetOption::NONE);
+                        }
+                        Scene::HugeText => {
+                            cont.set_row_widths_height(&[-1], 0);
+                            cont.text(text);
+                        }
+                    }
+                    WindowState::Open
+                });
+            }
+        });
+    }
+
+    fn render(&mut self, ctx: &mut Context<CountingRenderer>) {
+        self.frame(ctx);
+        ctx.begin(1280, 720, color(0, 0, 0, 255));
+        ctx.end();
+    }
+}
+
+fn bench(c: &mut Criterion) {
+    for scene in [Scene::Buttons, Scene::Windows, Scene::HugeText] {
+        let (mut ctx, renderer) = context();
+        let mut stress = Stress::new(&mut ctx, scene);
+        stress.render(&mut ctx);
+        let stats = ctx.frame_stats();
+        let (vertices, flushes) = renderer.scope(|r| (r.vertices, r.flushes));
+        eprintln!("{:?}: {:?}, {} vertices, {} flushes", scene, stats, vertices, flushes);
+
+        c.bench_function(&format!("frame/{:?}", scene), |b| b.iter(|| stress.frame(&mut ctx)));
+        c.bench_function(&format!("render/{:?}", scene), |b| b.iter(|| stress.render(&mut ctx)));
+    }
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -57,7 +57,6 @@ use microui_redux as microui;
 use std::sync::Arc;
 
 use sdl2::event::{Event, WindowEvent};
-use sdl2::keyboard::Keycode;
 use sdl2::video::{GLContext, GLProfile, Window};
 use sdl2::{Sdl, VideoSubsystem};
 type MicroUI = microui_redux::Context<glow_renderer::GLRenderer>;
@@ -128,22 +127,44 @@ impl<S> Application<S> {
             }
 
             fn map_keymode(sdl_km: sdl2::keyboard::Mod, sdl_kc: Option<sdl2::keyboard::Keycode>) -> microui::KeyMode {
+                use sdl2::keyboard::Keycode;
                 match (sdl_km, sdl_kc) {
-                    (sdl2::keyboard::Mod::LALTMOD, _) | (sdl2::keyboard::Mod::RALTMOD, _) => microui::KeyMode::ALT,
-                    (sdl2::keyboard::Mod::LCTRLMOD, _) | (sdl2::keyboard::Mod::RCTRLMOD, _) => microui::KeyMode::CTRL,
-                    (sdl2::keyboard::Mod::LSHIFTMOD, _) | (sdl2::keyboard::Mod::RSHIFTMOD, _) => microui::KeyMode::SHIFT,
-                    (_, Some(sdl2::keyboard::Keycode::Backspace)) => microui::KeyMode::BACKSPACE,
-                    (_, Some(sdl2::keyboard::Keycode::Return)) => microui::KeyMode::RETURN,
+                    (_, Some(Keycode::LAlt)) | (_, Some(Keycode::RAlt)) => microui::KeyMode::ALT,
+                    (_, Some(Keycode::LCtrl)) | (_, Some(Keycode::RCtrl)) => microui::KeyMode::CTRL,
+                    (_, Some(Keycode::LShift)) | (_, Some(Keycode::RShift)) => microui::KeyMode::SHIFT,
+                    (_, Some(Keycode::Backspace)) => microui::KeyMode::BACKSPACE,
+                    (_, Some(Keycode::Return)) | (_, Some(Keycode::KpEnter)) => microui::KeyMode::RETURN,
+                    (_, Some(Keycode::Delete)) => microui::KeyMode::DELETE,
+                    (_, Some(Keycode::Home)) => microui::KeyMode::HOME,
+                    (_, Some(Keycode::End)) => microui::KeyMode::END,
+                    (_, Some(Keycode::Left)) => microui::KeyMode::LEFT,
+                    (_, Some(Keycode::Right)) => microui::KeyMode::RIGHT,
+                    (_, Some(Keycode::Up)) => microui::KeyMode::UP,
+                    (_, Some(Keycode::Down)) => microui::KeyMode::DOWN,
+                    (_, Some(Keycode::Tab)) => microui::KeyMode::TAB,
+                    (_, Some(Keycode::Space)) => microui::KeyMode::SPACE,
+                    (_, Some(Keycode::Escape)) => microui::KeyMode::ESCAPE,
+                    // keypad navigation keys when num lock is off
+                    (km, Some(kc)) if !km.contains(sdl2::keyboard::Mod::NUMMOD) => match kc {
+                        Keycode::Kp7 => microui::KeyMode::HOME,
+                        Keycode::Kp1 => microui::KeyMode::END,
+                        Keycode::Kp4 => microui::KeyMode::LEFT,
+                        Keycode::Kp6 => microui::KeyMode::RIGHT,
+                        Keycode::Kp8 => microui::KeyMode::UP,
+                        Keycode::Kp2 => microui::KeyMode::DOWN,
+                        Keycode::KpPeriod => microui::KeyMode::DELETE,
+                        _ => microui::KeyMode::NONE,
+                    },
                     _ => microui::KeyMode::NONE,
                 }
             }
 
             for event in event_pump.poll_iter() {
                 match event {
-                    Event::Quit { .. } | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => break 'running,
+                    Event::Quit { .. } => break 'running,
                     Event::Window { win_event: WindowEvent::Close, .. } => break 'running,
                     Event::MouseMotion { x, y, .. } => self.ctx.input.borrow_mut().mousemove(x, y),
-                    Event::MouseWheel { y, .. } => self.ctx.input.borrow_mut().scroll(0, y * -30),
+                    Event::MouseWheel { x, y, .. } => self.ctx.input.borrow_mut().scroll(x as _, y as _, microui::ScrollUnit::Lines),
                     Event::MouseButtonDown { x, y, mouse_btn, .. } => {
                         let mb = map_mouse_button(mouse_btn);
                         self.ctx.input.borrow_mut().mousedown(x, y, mb);
@@ -154,12 +175,33 @@ impl<S> Application<S> {
                     }
                     Event::KeyDown { keymod, keycode, .. } => {
                         let km = map_keymode(keymod, keycode);
-                        self.ctx.input.borrow_mut().keydown(km);
+                        let mut input = self.ctx.input.borrow_mut();
+                        input.keydown(km);
+                        // Alt+letter or digit presses the button with that mnemonic
+                        if keymod.intersects(sdl2::keyboard::Mod::LALTMOD | sdl2::keyboard::Mod::RALTMOD) {
+                            let name = keycode.map(|k| k.name()).unwrap_or_default();
+                            let mut chars = name.chars();
+                            if let (Some(c), None) = (chars.next(), chars.next()) {
+                                if c.is_alphanumeric() {
+                                    input.mnemonic(c);
+                                }
+                            }
+                        }
                     }
                     Event::KeyUp { keymod, keycode, .. } => {
                         let km = map_keymode(keymod, keycode);
                         self.ctx.input.borrow_mut().keyup(km);
                     }
+                    // finger coordinates are normalized to the window
+                    Event::FingerDown { finger_id, x, y, .. } => {
+                        let (px, py) = ((x * width as f32) as i32, (y * height as f32) as i32);
+                        self.ctx.input.borrow_mut().touch_down(finger_id as u64, px, py);
+                    }
+                    Event::FingerMotion { finger_id, x, y, .. } => {
+                        let (px, py) = ((x * width as f32) as i32, (y * height as f32) as i32);
+                        self.ctx.input.borrow_mut().touch_move(finger_id as u64, px, py);
+                    }
+                    Event::FingerUp { finger_id, .. } => self.ctx.input.borrow_mut().touch_up(finger_id as u64),
                     Event::TextInput { text, .. } => {
                         self.ctx.input.borrow_mut().text(text.as_str());
                     }
@@ -189,5 +231,6 @@ pub fn atlas_config(slots: &Vec<Dimensioni>) -> builder::Config {
         default_font: String::from("assets/NORMAL.ttf"),
         default_font_size: 12,
         slots,
+        packing: PackingAlgorithm::Skyline,
     }
 }
@@ -56,6 +56,7 @@ use std::{sync::Arc, usize};
 
 use super::*;
 use glow::*;
+use microui_redux::Sampler;
 
 const VERTEX_SHADER: &str = "#version 100
 uniform highp mat4 uTransform;
@@ -64,10 +65,12 @@ attribute highp vec2 vertexTexCoord;
 attribute lowp vec4 vertexColor;
 varying highp vec2 vTexCoord;
 varying lowp vec4 vVertexColor;
+varying highp vec2 vPosition;
 void main()
 {
     vVertexColor = vertexColor;
     vTexCoord = vertexTexCoord;
+    vPosition = vertexPosition;
     highp vec4 pos = vec4(vertexPosition.x, vertexPosition.y, 0.0, 1.0);
     gl_Position = uTransform * pos;
 }";
@@ -75,11 +78,21 @@ void main()
 const FRAGMENT_SHADER: &str = "#version 100
 varying highp vec2 vTexCoord;
 varying lowp vec4 vVertexColor;
+varying highp vec2 vPosition;
 uniform sampler2D uTexture;
+uniform highp vec4 uMaskRect;
+uniform highp float uMaskRadius;
 void main()
 {
-    lowp vec4 col = texture2D(uTexture, vTexCoord);
-    gl_FragColor = col * vVertexColor;
+    lowp vec4 col = texture2D(uTexture, vTexCoord) * vVertexColor;
+    if (uMaskRadius >= 0.0) {
+        // signed distance to the rounded rect, antialiased over one pixel
+        highp vec2 center = (uMaskRect.xy + uMaskRect.zw) * 0.5;
+        highp vec2 q = abs(vPosition - center) - ((uMaskRect.zw - uMaskRect.xy) * 0.5 - vec2(uMaskRadius));
+        highp float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - uMaskRadius;
+        col.a *= clamp(0.5 - d, 0.0, 1.0);
+    }
+    gl_FragColor = col;
 }";
 
 pub struct GLRenderer {
@@ -98,9 +111,44 @@ pub struct GLRenderer {
 
     atlas: AtlasHandle,
     last_update_id: usize,
+    mask: Option<ClipMask>,
+    sampler: Sampler,
+    scissor: Option<Recti>,
+
+    // offscreen target of the supersampled layer, kept across frames
+    layer_target: Option<(NativeFramebuffer, NativeTexture, i32, i32)>,
+    // UI area and scale of the active layer
+    layer: Option<(Recti, f32)>,
+    // texture drawn instead of the atlas when compositing the layer
+    composite: Option<NativeTexture>,
 }
 
 impl GLRenderer {
+    // UI area covered by the bound target, its scale and height in pixels
+    fn target(&self) -> (Recti, f32, i32) {
+        match self.layer {
+            Some((area, scale)) => (area, scale, (area.height as f32 * scale).round() as i32),
+            None => (Recti::new(0, 0, self.width as i32, self.height as i32), 1.0, self.height as i32),
+        }
+    }
+
+    // GL counts y from the bottom
+    fn to_pixels(&self, r: Recti) -> Recti {
+        let (area, scale, height) = self.target();
+        let x = ((r.x - area.x) as f32 * scale).round() as i32;
+        let y = ((r.y - area.y) as f32 * scale).round() as i32;
+        let w = (r.width as f32 * scale).round() as i32;
+        let h = (r.height as f32 * scale).round() as i32;
+        Recti::new(x, height - y - h, w, h)
+    }
+
+    fn apply_scissor(&self) {
+        let r = self.to_pixels(self.scissor.unwrap_or(self.target().0));
+        unsafe {
+            self.gl.scissor(r.x, r.y, r.width, r.height);
+        }
+    }
+
     fn update_atlas(&mut self) {
         let gl = &self.gl;
         if self.last_update_id != self.atlas.get_last_update_id() {
@@ -112,7 +160,9 @@ impl GLRenderer {
                 debug_assert!(gl.get_error() == 0);
                 gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::NEAREST as i32);
                 debug_assert!(gl.get_error() == 0);
-                gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 0);
+                // mip levels are only built when a slot asks for them, few of them since neighbours bleed in deeper levels
+                let mipmaps = self.atlas.has_mipmaps();
+                gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, if mipmaps { 2 } else { 0 });
                 debug_assert!(gl.get_error() == 0);
 
                 // we are going to pass a pointer, hold the atlas pixels in memory since it returns a copy
@@ -132,6 +182,10 @@ impl GLRenderer {
                     );
                     debug_assert!(gl.get_error() == 0);
                 });
+                if mipmaps {
+                    gl.generate_mipmap(glow::TEXTURE_2D);
+                    debug_assert!(gl.get_error() == 0);
+                }
             }
             self.last_update_id = self.atlas.get_last_update_id()
         }
@@ -185,6 +239,12 @@ impl GLRenderer {
                 height,
                 atlas,
                 last_update_id: usize::MAX,
+                mask: None,
+                sampler: Sampler::default(),
+                scissor: None,
+                layer_target: None,
+                layer: None,
+                composite: None,
             }
         }
     }
@@ -204,11 +264,18 @@ impl Renderer for GLRenderer {
         let gl = &self.gl;
         unsafe {
             // opengl rendering states
-            gl.viewport(0, 0, self.width as i32, self.height as i32);
-            gl.scissor(0, 0, self.width as i32, self.height as i32);
+            let (area, _, _) = self.target();
+            let full = self.to_pixels(area);
+            gl.viewport(0, 0, full.width, full.height);
+            self.apply_scissor();
             gl.enable(glow::BLEND);
             debug_assert!(gl.get_error() == 0);
-            gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
+            // the layer keeps premultiplied colors so that compositing it doesn't darken translucent pixels
+            match (self.layer, self.composite) {
+                (_, Some(_)) => gl.blend_func(glow::ONE, glow::ONE_MINUS_SRC_ALPHA),
+                (Some(_), None) => gl.blend_func_separate(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA, glow::ONE, glow::ONE_MINUS_SRC_ALPHA),
+                (None, None) => gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA),
+            }
             debug_assert!(gl.get_error() == 0);
             gl.disable(glow::CULL_FACE);
             debug_assert!(gl.get_error() == 0);
@@ -222,20 +289,46 @@ impl Renderer for GLRenderer {
             debug_assert!(gl.get_error() == 0);
 
             // set the texture
-            gl.bind_texture(glow::TEXTURE_2D, Some(self.tex_o));
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.composite.unwrap_or(self.tex_o)));
             gl.active_texture(glow::TEXTURE0 + 0);
             let tex_uniform_id = gl.get_uniform_location(self.program, "uTexture").unwrap();
             gl.uniform_1_i32(Some(&tex_uniform_id), 0);
             debug_assert_eq!(gl.get_error(), 0);
 
+            // set the sampling
+            let (min_filter, mag_filter) = match (self.sampler.filter, self.sampler.mipmaps && self.atlas.has_mipmaps()) {
+                _ if self.composite.is_some() => (glow::LINEAR, glow::LINEAR),
+                (TextureFilter::Nearest, false) => (glow::NEAREST, glow::NEAREST),
+                (TextureFilter::Nearest, true) => (glow::NEAREST_MIPMAP_NEAREST, glow::NEAREST),
+                (TextureFilter::Linear, false) => (glow::LINEAR, glow::LINEAR),
+                (TextureFilter::Linear, true) => (glow::LINEAR_MIPMAP_LINEAR, glow::LINEAR),
+            };
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, min_filter as i32);
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, mag_filter as i32);
+            debug_assert_eq!(gl.get_error(), 0);
+
             // set the viewport
             let viewport = gl.get_uniform_location(self.program, "uTransform").unwrap();
-            let tm = ortho4(0.0, self.width as f32, self.height as f32, 0.0, -1.0, 1.0);
+            let (x, y) = (area.x as f32, area.y as f32);
+            let tm = ortho4(x, x + area.width as f32, y + area.height as f32, y, -1.0, 1.0);
             let tm_ptr = tm.col.as_ptr() as *const _ as *const f32;
             let slice = std::slice::from_raw_parts(tm_ptr, 16);
             gl.uniform_matrix_4_f32_slice(Some(&viewport), false, &slice);
             debug_assert_eq!(gl.get_error(), 0);
 
+            // set the mask, a negative radius disables it
+            let mask_rect = gl.get_uniform_location(self.program, "uMaskRect").unwrap();
+            let mask_radius = gl.get_uniform_location(self.program, "uMaskRadius").unwrap();
+            match self.mask {
+                Some(m) => {
+                    let r = m.rect;
+                    gl.uniform_4_f32(Some(&mask_rect), r.x as f32, r.y as f32, (r.x + r.width) as f32, (r.y + r.height) as f32);
+                    gl.uniform_1_f32(Some(&mask_radius), m.radius.clamp(0, r.width.min(r.height) / 2) as f32);
+                }
+                None => gl.uniform_1_f32(Some(&mask_radius), -1.0),
+            }
+            debug_assert_eq!(gl.get_error(), 0);
+
             // set the vertex buffer
             let pos_attrib_id = gl.get_attrib_location(self.program, "vertexPosition").unwrap();
             let tex_attrib_id = gl.get_attrib_location(self.program, "vertexTexCoord").unwrap();
@@ -279,6 +372,97 @@ impl Renderer for GLRenderer {
         }
     }
 
+    fn set_mask(&mut self, mask: Option<ClipMask>) {
+        self.mask = mask;
+    }
+
+    fn set_sampler(&mut self, sampler: Sampler) {
+        self.sampler = sampler;
+    }
+
+    fn set_scissor(&mut self, rect: Option<Recti>) {
+        self.scissor = rect;
+        self.apply_scissor();
+    }
+
+    fn set_viewport(&mut self, r: Recti) {
+        let r = self.to_pixels(r);
+        unsafe {
+            self.gl.viewport(r.x, r.y, r.width, r.height);
+        }
+    }
+
+    fn begin_scaled_layer(&mut self, area: Recti, scale: f32) -> bool {
+        let (w, h) = ((area.width as f32 * scale).round() as i32, (area.height as f32 * scale).round() as i32);
+        if self.layer.is_some() || w <= 0 || h <= 0 {
+            return false;
+        }
+        let gl = &self.gl;
+        unsafe {
+            let fbo = match self.layer_target {
+                Some((fbo, _, tw, th)) if tw == w && th == h => fbo,
+                old => {
+                    if let Some((fbo, tex, _, _)) = old {
+                        gl.delete_framebuffer(fbo);
+                        gl.delete_texture(tex);
+                    }
+                    let tex = gl.create_texture().unwrap();
+                    gl.bind_texture(glow::TEXTURE_2D, Some(tex));
+                    gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA as i32, w, h, 0, glow::RGBA, glow::UNSIGNED_BYTE, PixelUnpackData::Slice(None));
+                    gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
+                    gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
+                    gl.bind_texture(glow::TEXTURE_2D, None);
+                    let fbo = gl.create_framebuffer().unwrap();
+                    gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+                    gl.framebuffer_texture_2d(glow::FRAMEBUFFER, glow::COLOR_ATTACHMENT0, glow::TEXTURE_2D, Some(tex), 0);
+                    let complete = gl.check_framebuffer_status(glow::FRAMEBUFFER) == glow::FRAMEBUFFER_COMPLETE;
+                    gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+                    if !complete {
+                        gl.delete_framebuffer(fbo);
+                        gl.delete_texture(tex);
+                        self.layer_target = None;
+                        return false;
+                    }
+                    self.layer_target = Some((fbo, tex, w, h));
+                    fbo
+                }
+            };
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            gl.disable(glow::SCISSOR_TEST);
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            gl.clear(glow::COLOR_BUFFER_BIT);
+            gl.enable(glow::SCISSOR_TEST);
+            debug_assert!(gl.get_error() == 0);
+        }
+        self.layer = Some((area, scale));
+        self.apply_scissor();
+        true
+    }
+
+    fn end_scaled_layer(&mut self) {
+        let (area, tex) = match (self.layer.take(), self.layer_target) {
+            (Some((area, _)), Some((_, tex, _, _))) => (area, tex),
+            _ => return,
+        };
+        unsafe {
+            self.gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        }
+
+        // one quad over the area, the texture rows run bottom up
+        let (x0, y0) = (area.x as f32, area.y as f32);
+        let (x1, y1) = (x0 + area.width as f32, y0 + area.height as f32);
+        let white = color4b(255, 255, 255, 255);
+        let vertex = |x: f32, y: f32, u: f32, v: f32| Vertex::new(Vec2f::new(x, y), Vec2f::new(u, v), white);
+        let (mask, scissor) = (self.mask.take(), self.scissor.take());
+        self.composite = Some(tex);
+        self.push_quad_vertices(&vertex(x0, y0, 0.0, 1.0), &vertex(x1, y0, 1.0, 1.0), &vertex(x1, y1, 1.0, 0.0), &vertex(x0, y1, 0.0, 0.0));
+        self.flush();
+        self.composite = None;
+        self.mask = mask;
+        self.scissor = scissor;
+        self.apply_scissor();
+    }
+
     fn push_quad_vertices(&mut self, v0: &Vertex, v1: &Vertex, v2: &Vertex, v3: &Vertex) {
         if self.verts.len() + 4 >= 65536 || self.indices.len() + 6 >= 65536 {
             self.flush();
@@ -303,6 +487,25 @@ impl Renderer for GLRenderer {
         }
     }
 
+    fn push_quads(&mut self, vertices: &[Vertex]) {
+        for run in vertices.chunks(4 * 4096) {
+            if self.verts.len() + run.len() >= 65536 || self.indices.len() + run.len() / 4 * 6 >= 65536 {
+                self.flush();
+            }
+
+            let base = self.verts.len() as u16;
+            for q in 0..(run.len() / 4) as u16 {
+                let is = base + q * 4;
+                self.indices.extend_from_slice(&[is, is + 1, is + 2, is + 2, is + 3, is]);
+            }
+            self.verts.extend_from_slice(run);
+        }
+
+        if self.last_update_id != self.atlas.get_last_update_id() {
+            self.flush()
+        }
+    }
+
     fn begin(&mut self, width: i32, height: i32, clr: Color) {
         self.width = width as u32;
         self.height = height as u32;
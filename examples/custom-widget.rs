@@ -0,0 +1,115 @@
+#[path = "./common/mod.rs"]
+mod common;
+
+use application::Application;
+use common::*;
+use microui_redux::widgets::prelude::*;
+use microui_redux::{ContainerOption, WindowHandle, WindowState};
+
+// an on/off switch with a sliding thumb
+struct Switch {
+    label: String,
+    on: bool,
+}
+
+impl Widget for Switch {
+    fn preferred_size(&self, style: &Style, atlas: &AtlasHandle) -> Dimensioni {
+        let text = atlas.get_text_size(style.font, &self.label);
+        Dimensioni::new(text.width + 40 + style.padding * 3, text.height + style.padding * 2)
+    }
+
+    fn handle(&mut self, container: &mut Container, id: Id, r: Recti) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        if container.is_clicked(id) {
+            self.on = !self.on;
+            res |= ResourceState::CHANGE;
+            container.emit_event(id, WidgetEventKind::Changed, EventValue::Bool(self.on));
+        }
+
+        let track = rect(r.x, r.y + (r.height - 16) / 2, 32, 16);
+        let colorid = if self.on { ControlColor::Accent } else { ControlColor::Base };
+        container.draw_frame(track, colorid);
+        let thumb_x = if self.on { track.x + track.width - 14 } else { track.x + 2 };
+        container.draw_rect(rect(thumb_x, track.y + 2, 12, 12), container.style.colors[ControlColor::Text as usize]);
+
+        let text = rect(track.x + track.width + container.style.padding, r.y, r.width - track.width, r.height);
+        container.draw_control_text(&self.label, text, ControlColor::Text, WidgetOption::NONE);
+        res
+    }
+}
+
+// a rotary knob dragged vertically, the value goes from 0 to 1
+struct Knob {
+    value: Real,
+}
+
+impl Widget for Knob {
+    fn preferred_size(&self, _style: &Style, _atlas: &AtlasHandle) -> Dimensioni {
+        Dimensioni::new(48, 48)
+    }
+
+    fn options(&self) -> WidgetOption {
+        WidgetOption::HOLD_FOCUS
+    }
+
+    fn handle(&mut self, container: &mut Container, id: Id, r: Recti) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let dragging = container.focus == Some(id) && container.input.borrow().get_mouse_buttons().is_left();
+        if dragging {
+            let dy = container.input.borrow().mouse_delta().y;
+            let value = (self.value - dy as Real / 100.0).clamp(0.0, 1.0);
+            if value != self.value {
+                self.value = value;
+                res |= ResourceState::CHANGE;
+                container.emit_event(id, WidgetEventKind::Changed, EventValue::Real(value));
+            }
+        }
+
+        let center = Vec2f::new((r.x + r.width / 2) as f32, (r.y + r.height / 2) as f32);
+        let radius = (r.width.min(r.height) / 2 - 4) as f32;
+        let start = std::f32::consts::PI * 0.75;
+        let sweep = std::f32::consts::PI * 1.5;
+        let base = container.style.colors[ControlColor::Base as usize];
+        let accent = container.style.colors[ControlColor::Accent as usize];
+        container.draw_arc(center, radius, 4.0, start, start + sweep, base);
+        container.draw_arc(center, radius, 4.0, start, start + sweep * self.value, accent);
+        res
+    }
+}
+
+implement_widget!(Switch, SwitchExt, switch);
+implement_widget!(Knob, KnobExt, knob);
+
+struct State {
+    window: WindowHandle,
+    switches: Vec<Switch>,
+    knob: Knob,
+}
+
+fn main() {
+    let slots = vec![];
+    let atlas = microui_redux::builder::Builder::from_config(&application::atlas_config(&slots)).unwrap().to_atlas();
+    let mut fw = Application::new(atlas.clone(), move |_gl, ctx| State {
+        window: ctx.new_window("Custom Widgets", rect(40, 40, 300, 300)),
+        switches: vec![Switch { label: "Wi-Fi".into(), on: true }, Switch { label: "Bluetooth".into(), on: false }],
+        knob: Knob { value: 0.25 },
+    })
+    .unwrap();
+
+    fw.event_loop(|ctx, state| {
+        ctx.frame(|ctx| {
+            ctx.window(&mut state.window.clone(), ContainerOption::NONE, |container| {
+                container.set_row_sizes(&[SizePolicy::Auto], 0);
+                for s in state.switches.iter_mut() {
+                    container.switch(s);
+                }
+                container.set_row_sizes(&[SizePolicy::Auto, SizePolicy::Auto], 48);
+                if container.knob(&mut state.knob).is_changed() {
+                    println!("knob: {:.2}", state.knob.value);
+                }
+                container.label(&format!("{:.0}%", state.knob.value * 100.0));
+                WindowState::Open
+            });
+        });
+    });
+}
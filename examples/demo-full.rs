@@ -99,26 +99,26 @@ pub struct SuzaneData {
     mesh: PolyMesh,
 }
 
-struct State<'a> {
+struct State {
     gl: Arc<glow::Context>,
     rng: Rc<RefCell<ThreadRng>>,
     slots: Vec<SlotId>,
-    label_colors: [LabelColor<'a>; 15],
     bg: [Real; 3],
     logbuf: String,
     logbuf_updated: bool,
     submit_buf: String,
     checks: [bool; 3],
-    style: Style,
 
     demo_window: Option<WindowHandle>,
-    style_window: Option<WindowHandle>,
+    style_editor: Option<StyleEditorState>,
     log_window: Option<WindowHandle>,
     popup_window: Option<WindowHandle>,
     log_output: Option<ContainerHandle>,
     triangle_window: Option<WindowHandle>,
     suzane_window: Option<WindowHandle>,
     dialog_window: Option<FileDialogState>,
+    text_menu: Option<TextMenuState>,
+    clipboard: MemoryClipboard,
 
     window_header: NodeState,
     test_buttons_header: NodeState,
@@ -138,16 +138,9 @@ struct State<'a> {
     triangle_data: Arc<RwLock<TriangleRenderData>>,
 }
 
-#[derive(Copy, Clone)]
-#[repr(C)]
-pub struct LabelColor<'a> {
-    pub label: &'a str,
-    pub idx: ControlColor,
-}
-
 const MAX_POLYMESH_TRIS: usize = 65536;
 
-impl<'a> State<'a> {
+impl State {
     pub fn new(gl: Arc<glow::Context>, slots: Vec<SlotId>) -> Self {
         let pm_renderer = Arc::new(RwLock::new(PolyMeshRenderer::create(
             &gl,
@@ -185,69 +178,6 @@ impl<'a> State<'a> {
             gl,
             rng: Rc::new(RefCell::new(thread_rng())),
             slots,
-            style: Style::default(),
-            label_colors: [
-                LabelColor {
-                    label: "text",
-                    idx: ControlColor::Text,
-                },
-                LabelColor {
-                    label: "border:",
-                    idx: ControlColor::Border,
-                },
-                LabelColor {
-                    label: "windowbg:",
-                    idx: ControlColor::WindowBG,
-                },
-                LabelColor {
-                    label: "titlebg:",
-                    idx: ControlColor::TitleBG,
-                },
-                LabelColor {
-                    label: "titletext:",
-                    idx: ControlColor::TitleText,
-                },
-                LabelColor {
-                    label: "panelbg:",
-                    idx: ControlColor::PanelBG,
-                },
-                LabelColor {
-                    label: "button:",
-                    idx: ControlColor::Button,
-                },
-                LabelColor {
-                    label: "buttonhover:",
-                    idx: ControlColor::ButtonHover,
-                },
-                LabelColor {
-                    label: "buttonfocus:",
-                    idx: ControlColor::ButtonFocus,
-                },
-                LabelColor {
-                    label: "base:",
-                    idx: ControlColor::Base,
-                },
-                LabelColor {
-                    label: "basehover:",
-                    idx: ControlColor::BaseHover,
-                },
-                LabelColor {
-                    label: "basefocus:",
-                    idx: ControlColor::BaseFocus,
-                },
-                LabelColor {
-                    label: "scrollbase:",
-                    idx: ControlColor::ScrollBase,
-                },
-                LabelColor {
-                    label: "scrollthumb:",
-                    idx: ControlColor::ScrollThumb,
-                },
-                LabelColor {
-                    label: "",
-                    idx: ControlColor::Text,
-                },
-            ],
             bg: [90.0, 95.0, 100.0],
             logbuf: String::new(),
             logbuf_updated: false,
@@ -255,13 +185,15 @@ impl<'a> State<'a> {
             checks: [false, true, false],
 
             demo_window: None,
-            style_window: None,
+            style_editor: None,
             log_window: None,
             popup_window: None,
             log_output: None,
             triangle_window: None,
             suzane_window: None,
             dialog_window: None,
+            text_menu: None,
+            clipboard: MemoryClipboard::default(),
 
             window_header: NodeState::Closed,
             test_buttons_header: NodeState::Expanded,
@@ -409,13 +341,16 @@ impl<'a> State<'a> {
                 container.set_row_widths_height(&[-78, -1], 74);
                 container.column(|container| {
                     container.set_row_widths_height(&[46, -1], 0);
-                    container.label("Red:");
+                    container.scrub_label("Red:", &mut self.bg[0], 1.0);
                     container.slider_ex(&mut self.bg[0], 0 as Real, 255 as Real, 0 as Real, 0, WidgetOption::ALIGN_CENTER);
-                    container.label("Green:");
+                    container.scrub_label("Green:", &mut self.bg[1], 1.0);
                     container.slider_ex(&mut self.bg[1], 0 as Real, 255 as Real, 0 as Real, 0, WidgetOption::ALIGN_CENTER);
-                    container.label("Blue:");
+                    container.scrub_label("Blue:", &mut self.bg[2], 1.0);
                     container.slider_ex(&mut self.bg[2], 0 as Real, 255 as Real, 0 as Real, 0, WidgetOption::ALIGN_CENTER);
                 });
+                for c in &mut self.bg {
+                    *c = c.clamp(0.0, 255.0);
+                }
                 let r: Recti = container.next_cell();
                 container.draw_rect(r, color(self.bg[0] as u8, self.bg[1] as u8, self.bg[2] as u8, 255));
                 let mut buff = String::new();
@@ -435,7 +370,10 @@ impl<'a> State<'a> {
                     let mut rm = rng.borrow_mut();
                     color4b(rm.gen(), rm.gen(), rm.gen(), rm.gen())
                 }));
-
+                container.set_row_widths_height(&[67, -1], 67);
+                let r = container.next_cell();
+                container.masked(r, r.width / 2, |container| container.draw_slot(self.slots[2], r, color(255, 255, 255, 255)));
+                container.label("Slot 3 - Circular");
 
             });
             WindowState::Open
@@ -524,23 +462,12 @@ impl<'a> State<'a> {
             ContainerOption::NONE,
             |container| {
                 container.set_row_widths_height(&[-1], -1);
-                container.custom_render_widget("Triangle", WidgetOption::NONE, move |dim, cra| {
+                container.custom_render_widget("Triangle", WidgetOption::NONE, move |_, _| {
                     let gl = &gl;
 
                     match tdi.try_read() {
+                        // the renderer already set the viewport and the scissor of the widget
                         Ok(td) => unsafe {
-                            gl.viewport(
-                                cra.content_area.x,
-                                dim.height - cra.content_area.y - cra.content_area.height,
-                                cra.content_area.width,
-                                cra.content_area.height,
-                            );
-                            gl.scissor(
-                                cra.content_area.x,
-                                dim.height - cra.content_area.y - cra.content_area.height,
-                                cra.content_area.width,
-                                cra.content_area.height,
-                            );
                             gl.clear_color(0.5, 0.5, 0.5, 1.0);
                             gl.clear(COLOR_BUFFER_BIT | DEPTH_BUFFER_BIT);
 
@@ -628,81 +555,6 @@ impl<'a> State<'a> {
         );
     }
 
-    fn uint8_slider(
-        &mut self,
-        value: &mut u8,
-        low: i32,
-        high: i32,
-        ctx: &mut Container,
-    ) -> ResourceState {
-        let mut tmp = *value as f32;
-        ctx.idmngr.push_id_from_ptr(value);
-        let res = ctx.slider_ex(
-            &mut tmp,
-            low as Real,
-            high as Real,
-            0 as Real,
-            0,
-            WidgetOption::ALIGN_CENTER,
-        );
-        *value = tmp as u8;
-        ctx.idmngr.pop_id();
-        return res;
-    }
-
-    fn style_window(&mut self, ctx: &mut Context<GLRenderer>) {
-        ctx.window(
-            &mut self.style_window.as_mut().unwrap().clone(),
-            ContainerOption::NONE,
-            |container| {
-                let sw = (container.body.width as f64 * 0.14) as i32;
-                container.set_row_widths_height(&[80, sw, sw, sw, sw, -1], 0);
-                let mut i = 0;
-                while self.label_colors[i].label.len() > 0 {
-                    container.label(self.label_colors[i].label);
-                    unsafe {
-                        let color = self.style.colors.as_mut_ptr().offset(i as isize);
-                        self.uint8_slider(&mut (*color).r, 0, 255, container);
-                        self.uint8_slider(&mut (*color).g, 0, 255, container);
-                        self.uint8_slider(&mut (*color).b, 0, 255, container);
-                        self.uint8_slider(&mut (*color).a, 0, 255, container);
-                    }
-                    let next_layout = container.next_cell();
-                    let color = self.style.colors[i];
-                    container.draw_rect(next_layout, color);
-                    i += 1;
-                }
-                container.set_row_widths_height(&[80, sw], 0);
-                container.label("padding");
-                let mut tmp = self.style.padding as u8;
-                self.uint8_slider(&mut tmp, 0, 16, container);
-                self.style.padding = tmp as i32;
-
-                container.label("spacing");
-                let mut tmp = self.style.spacing as u8;
-                self.uint8_slider(&mut tmp, 0, 16, container);
-                self.style.spacing = tmp as i32;
-
-                container.label("title height");
-                let mut tmp = self.style.title_height as u8;
-                self.uint8_slider(&mut tmp, 0, 128, container);
-                self.style.title_height = tmp as i32;
-
-                container.label("thumb size");
-                let mut tmp = self.style.thumb_size as u8;
-                self.uint8_slider(&mut tmp, 0, 128, container);
-                self.style.thumb_size = tmp as i32;
-
-                container.label("scroll size");
-                let mut tmp = self.style.scrollbar_size as u8;
-                self.uint8_slider(&mut tmp, 0, 128, container);
-                self.style.scrollbar_size = tmp as i32;
-                WindowState::Open
-            },
-        );
-        ctx.set_style(&self.style);
-    }
-
     fn suzane_window(&mut self, ctx: &mut Context<GLRenderer>) {
         let gl = self.gl.clone();
         let renderer = self.pm_renderer.clone();
@@ -710,7 +562,7 @@ impl<'a> State<'a> {
 
         ctx.window(
             &mut self.suzane_window.as_mut().unwrap().clone(),
-            ContainerOption::NONE,
+            ContainerOption::MAXIMIZE,
             |container| {
                 container.set_row_widths_height(&[-1], -1);
                 container.custom_render_widget("Suzane", WidgetOption::NONE, move |dim, cra| {
@@ -768,11 +620,12 @@ impl<'a> State<'a> {
 
     fn process_frame(&mut self, ctx: &mut Context<GLRenderer>) {
         ctx.frame(|ctx| {
-            self.style_window(ctx);
+            self.style_editor.as_mut().unwrap().eval(ctx);
             self.log_window(ctx);
             self.test_window(ctx);
             self.triangle_window(ctx);
             self.suzane_window(ctx);
+            self.text_menu.as_mut().unwrap().eval(ctx, &mut self.clipboard);
         })
     }
 }
@@ -799,12 +652,15 @@ fn main() {
 
         state.demo_window = Some(ctx.new_window("Demo Window", rect(40, 40, 300, 450)));
         state.log_window = Some(ctx.new_window("Log Window", rect(350, 40, 300, 200)));
-        state.style_window = Some(ctx.new_window("Style Editor", rect(350, 250, 300, 240)));
+        state.style_editor = Some(StyleEditorState::new(ctx, "Style Editor", rect(350, 250, 300, 240)));
+        state.log_window.as_mut().unwrap().on_title_double_click(TitleDoubleClick::Collapse);
         state.popup_window = Some(ctx.new_popup("Test Popup"));
         state.log_output = Some(ctx.new_panel("Log Outputman, "));
         state.triangle_window = Some(ctx.new_window("Triangle Window", rect(200, 100, 200, 200)));
         state.suzane_window = Some(ctx.new_window("Suzane Window", rect(220, 220, 300, 300)));
+        state.suzane_window.as_mut().unwrap().on_title_double_click(TitleDoubleClick::Maximize);
         state.dialog_window = Some(FileDialogState::new(ctx));
+        state.text_menu = Some(TextMenuState::new(ctx));
 
         state
     })
@@ -0,0 +1,52 @@
+#[path = "./common/mod.rs"]
+mod common;
+
+use std::{thread, time::Duration};
+
+use application::Application;
+use common::*;
+use microui_redux::*;
+
+struct State {
+    splash: Splash,
+    main: WindowHandle,
+}
+
+fn main() {
+    let slots = vec![Dimensioni::new(64, 64), Dimensioni::new(24, 32), Dimensioni::new(64, 24)];
+    let atlas = builder::Builder::from_config(&application::atlas_config(&slots)).unwrap().to_atlas();
+    let mut fw = Application::new(atlas.clone(), move |_gl, ctx| {
+        let (splash, handle) = Splash::new(ctx, "Splash", &[("Reading settings", 1.0), ("Loading assets", 4.0), ("Warming up", 1.0)]);
+
+        // the loading work, it only reports through the handle
+        thread::spawn(move || {
+            for phase in 0..3 {
+                handle.phase(phase);
+                for i in 1..=20 {
+                    thread::sleep(Duration::from_millis(40));
+                    handle.progress(i as f32 / 20.0);
+                    if phase == 1 {
+                        handle.status(&format!("Loading asset {} of 20", i));
+                    }
+                }
+            }
+            handle.finish();
+        });
+
+        State { splash, main: ctx.new_window("Loaded", rect(40, 40, 300, 120)) }
+    })
+    .unwrap();
+
+    fw.event_loop(|ctx, state| {
+        ctx.frame(|ctx| {
+            if state.splash.eval(ctx) {
+                return;
+            }
+            ctx.window(&mut state.main.clone(), ContainerOption::NONE, |cont| {
+                cont.set_row_widths_height(&[-1], 0);
+                cont.label("Everything is loaded");
+                WindowState::Open
+            });
+        });
+    });
+}
@@ -0,0 +1,80 @@
+#[path = "./common/mod.rs"]
+mod common;
+
+use application::Application;
+use common::*;
+use microui_redux::*;
+
+// The scenes of benches/ui.rs, interactive, next to the metrics window
+#[derive(Clone, Copy, PartialEq)]
+enum Scene {
+    Buttons,
+    Windows,
+    HugeText,
+}
+
+struct State {
+    controls: WindowHandle,
+    buttons: WindowHandle,
+    windows: Vec<WindowHandle>,
+    text_window: WindowHandle,
+    text: String,
+    scene: Scene,
+}
+
+fn main() {
+    let slots = vec![Dimensioni::new(64, 64), Dimensioni::new(24, 32), Dimensioni::new(64, 24)];
+    let atlas = builder::Builder::from_config(&application::atlas_config(&slots)).unwrap().to_atlas();
+    let mut fw = Application::new(atlas.clone(), move |_gl, ctx| State {
+        controls: ctx.new_window("Stress", rect(280, 10, 200, 120)),
+        buttons: ctx.new_window("10k buttons", rect(10, 280, 760, 300)),
+        windows: (0..1000).map(|i| ctx.new_window(&format!("window {}", i), rect((i % 40) * 18, 140 + (i / 40) * 16, 200, 120))).collect(),
+        text_window: ctx.new_window("Huge text", rect(10, 280, 760, 300)),
+        text: "The quick brown fox jumps over the lazy dog. ".repeat(24_000),
+        scene: Scene::Buttons,
+    })
+    .unwrap();
+
+    fw.event_loop(|ctx, state| {
+        ctx.frame(|ctx| {
+            ctx.show_metrics_window();
+            let scene = state.scene;
+            match scene {
+                Scene::Buttons => ctx.window(&mut state.buttons.clone(), ContainerOption::NONE, |cont| {
+                    cont.set_row_widths_height(&[70; 10], 0);
+                    for i in 0..10_000 {
+                        cont.button_ex(&format!("button {}", i), None, WidgetOption::NONE);
+                    }
+                    WindowState::Open
+                }),
+                Scene::Windows => {
+                    for w in &mut state.windows {
+                        ctx.window(w, ContainerOption::NONE, |cont| {
+                            cont.set_row_widths_height(&[-1], 0);
+                            cont.label("label");
+                            cont.button_ex("button", None, WidgetOption::NONE);
+                            WindowState::Open
+                        });
+                    }
+                }
+                Scene::HugeText => {
+                    let text = &state.text;
+                    ctx.window(&mut state.text_window.clone(), ContainerOption::NONE, |cont| {
+                        cont.set_row_widths_height(&[-1], 0);
+                        cont.text(text);
+                        WindowState::Open
+                    })
+                }
+            }
+            ctx.window(&mut state.controls.clone(), ContainerOption::NONE, |cont| {
+                cont.set_row_widths_height(&[-1], 0);
+                for (name, s) in [("10k buttons", Scene::Buttons), ("1k windows", Scene::Windows), ("Huge text", Scene::HugeText)] {
+                    if cont.button_ex(name, None, WidgetOption::NONE).is_submitted() {
+                        state.scene = s;
+                    }
+                }
+                WindowState::Open
+            });
+        });
+    });
+}
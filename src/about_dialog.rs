@@ -0,0 +1,150 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// A standard "About" window: the application icon and name, version fields (version, build, ...), the license
+// or credits in a scrolling area and a row of link buttons. Links aren't opened by the library, the url of
+// the clicked one is returned to the application.
+pub struct AboutDialog {
+    win: WindowHandle,
+    text_panel: ContainerHandle,
+    app_name: String,
+    icon: Option<SlotId>,
+    fields: Vec<(String, String)>,
+    text: String,
+    links: Vec<(String, String)>, // label, url
+}
+
+impl AboutDialog {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, app_name: &str) -> Self {
+        let area = ctx.safe_area();
+        let (w, h) = (400.min(area.width), 360.min(area.height));
+        let r = rect(area.x + (area.width - w) / 2, area.y + (area.height - h) / 2, w, h);
+        Self {
+            win: ctx.new_dialog(&format!("About {}", app_name), r),
+            text_panel: ctx.new_panel("!about_text"),
+            app_name: app_name.to_string(),
+            icon: None,
+            fields: Vec::new(),
+            text: String::new(),
+            links: Vec::new(),
+        }
+    }
+
+    pub fn set_icon(&mut self, icon: Option<SlotId>) -> &mut Self {
+        self.icon = icon;
+        self
+    }
+
+    // shown under the name, e.g. ("Version", "1.2.0")
+    pub fn add_field(&mut self, name: &str, value: &str) -> &mut Self {
+        self.fields.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    // the license, credits, ... one paragraph per line
+    pub fn set_text(&mut self, text: &str) -> &mut Self {
+        self.text = text.to_string();
+        self
+    }
+
+    pub fn add_link(&mut self, label: &str, url: &str) -> &mut Self {
+        self.links.push((label.to_string(), url.to_string()));
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.win.is_open()
+    }
+
+    pub fn open<R: Renderer>(&mut self, ctx: &mut Context<R>) {
+        ctx.open_dialog(&mut self.win);
+    }
+
+    fn header(&self, cont: &mut Container) {
+        let lines = |cont: &mut Container| {
+            cont.set_row_widths_height(&[-1], 0);
+            cont.label(&self.app_name);
+            for (name, value) in &self.fields {
+                cont.label(&format!("{}: {}", name, value));
+            }
+        };
+        match self.icon {
+            Some(icon) => {
+                let size = cont.atlas.get_slot_size(icon);
+                cont.set_row_widths_height(&[size.width, -1], size.height);
+                let r = cont.next_cell();
+                cont.draw_slot(icon, r, color(255, 255, 255, 255));
+                cont.column(lines);
+            }
+            None => lines(cont),
+        }
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Shows `about` while it's open, returns the url of the link clicked during this frame
+    pub fn about_dialog(&mut self, about: &mut AboutDialog) -> Option<String> {
+        let mut clicked = None;
+        let mut text_panel = about.text_panel.clone();
+        self.dialog(&mut about.win.clone(), ContainerOption::NONE, |cont| {
+            about.header(cont);
+
+            // leaves room for the row of buttons
+            let buttons_h = cont.style.default_cell_size.height + cont.style.padding * 2 + cont.style.spacing;
+            cont.set_row_widths_height(&[-1], -buttons_h);
+            cont.panel(&mut text_panel, ContainerOption::NONE, |panel| {
+                let panel = &mut panel.inner_mut();
+                panel.set_row_widths_height(&[-1], 0);
+                panel.text(&about.text);
+            });
+
+            // the links, then Close in the last cell
+            let cells = about.links.len() as i32 + 1;
+            let w = (cont.body.width - cont.style.spacing * (cells - 1)) / cells;
+            let mut widths = vec![w; about.links.len()];
+            widths.push(-1);
+            cont.set_row_widths_height(&widths, 0);
+            // links can share a label, their ids come from their index
+            for (i, (label, url)) in about.links.iter().enumerate() {
+                cont.idmngr.push_id_u32(i as u32);
+                if cont.button_ex(label, None, WidgetOption::ALIGN_CENTER).is_submitted() {
+                    clicked = Some(url.clone());
+                }
+                cont.idmngr.pop_id();
+            }
+            if cont.button_ex("Close", None, WidgetOption::ALIGN_CENTER).is_submitted() {
+                return WindowState::Closed;
+            }
+            WindowState::Open
+        });
+        clicked
+    }
+}
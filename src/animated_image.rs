@@ -0,0 +1,291 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+const MIN_FRAME_DELAY: Real = 0.01;
+
+// where the frames of an animation come from
+#[derive(Clone)]
+pub enum AnimationFrames {
+    // `count` frames laid out left to right then top to bottom over `columns` columns of the slot
+    SpriteSheet { slot: SlotId, columns: usize, count: usize },
+    // decoded frames of the slot size, copied into the slot when the shown frame changes
+    Pixels { slot: SlotId, frames: Vec<Vec<Color4b>> },
+}
+
+#[derive(Clone)]
+pub struct AnimatedImage {
+    frames: AnimationFrames,
+    delays: Vec<Real>, // seconds each frame stays on screen
+    frame: usize,
+    time: Real,
+    uploaded: Option<usize>,
+    pub playing: bool,
+    pub looping: bool,
+    pub speed: Real,
+}
+
+impl AnimatedImage {
+    // every frame of the sheet is shown for 1 / fps seconds
+    pub fn from_sprite_sheet(slot: SlotId, columns: usize, count: usize, fps: Real) -> Self {
+        let delay = if fps > 0.0 { 1.0 / fps } else { Real::MAX };
+        Self::new(AnimationFrames::SpriteSheet { slot, columns: max(columns, 1), count }, vec![delay; count])
+    }
+
+    // frames with their delay in seconds, each one holds as many pixels as the slot
+    pub fn from_frames(slot: SlotId, frames: Vec<(Vec<Color4b>, Real)>) -> Self {
+        let (frames, delays) = frames.into_iter().unzip();
+        Self::new(AnimationFrames::Pixels { slot, frames }, delays)
+    }
+
+    fn new(frames: AnimationFrames, delays: Vec<Real>) -> Self {
+        Self {
+            frames,
+            // a zero delay is shown for 10ms like the browsers do, it would stall `advance` otherwise
+            delays: delays.into_iter().map(|d| d.max(MIN_FRAME_DELAY)).collect(),
+            frame: 0,
+            time: 0.0,
+            uploaded: None,
+            playing: true,
+            looping: true,
+            speed: 1.0,
+        }
+    }
+
+    // size of one frame on screen
+    pub fn frame_size(&self, atlas: &AtlasHandle) -> Dimensioni {
+        match &self.frames {
+            AnimationFrames::SpriteSheet { slot, columns, count } => {
+                let sheet = atlas.get_slot_size(*slot);
+                let rows = count.div_ceil(*columns).max(1);
+                Dimensioni::new(sheet.width / *columns as i32, sheet.height / rows as i32)
+            }
+            AnimationFrames::Pixels { slot, .. } => atlas.get_slot_size(*slot),
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.delays.len()
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn set_frame(&mut self, frame: usize) {
+        self.frame = min(frame, self.frame_count().saturating_sub(1));
+        self.time = 0.0;
+    }
+
+    pub fn play(&mut self) {
+        if self.is_finished() {
+            self.set_frame(0);
+        }
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn stop(&mut self) {
+        self.playing = false;
+        self.set_frame(0);
+    }
+
+    // true once a non looping animation showed its last frame
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.frame + 1 >= self.frame_count() && self.time >= self.delays.last().copied().unwrap_or(0.0)
+    }
+
+    // moves the animation `dt` seconds forward, returns true when the shown frame changed
+    pub fn advance(&mut self, dt: Real) -> bool {
+        let count = self.frame_count();
+        if !self.playing || count == 0 {
+            return false;
+        }
+        let last = self.frame;
+        self.time += dt * self.speed;
+        // a long frame time skips frames rather than slowing the animation down
+        while self.time >= self.delays[self.frame] {
+            if self.frame + 1 < count {
+                self.time -= self.delays[self.frame];
+                self.frame += 1;
+            } else if self.looping {
+                self.time -= self.delays[self.frame];
+                self.frame = 0;
+            } else {
+                self.time = self.delays[self.frame];
+                self.playing = false;
+                break;
+            }
+        }
+        self.frame != last
+    }
+}
+
+#[cfg(feature = "apng")]
+impl AnimatedImage {
+    // Decodes an APNG, a plain PNG gives a single frame. The canvas of the image has to match the slot size.
+    pub fn from_apng_bytes(slot: SlotId, bytes: &[u8]) -> std::io::Result<Self> {
+        use png::{BlendOp, ColorType, DisposeOp};
+
+        let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let (width, height) = (reader.info().width as usize, reader.info().height as usize);
+        let count = reader.info().animation_control().map(|a| a.num_frames as usize).unwrap_or(1);
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let mut canvas = vec![Color4b::default(); width * height];
+        let mut frames = Vec::new();
+        for _ in 0..count {
+            let info = reader.next_frame(&mut buf)?;
+            let fc = reader.info().frame_control().copied();
+            let (fx, fy) = fc.map(|f| (f.x_offset as usize, f.y_offset as usize)).unwrap_or((0, 0));
+            let (fw, fh) = (info.width as usize, info.height as usize);
+            let pixel_size = info.color_type.samples();
+            let previous = canvas.clone();
+            for y in 0..fh {
+                let line = &buf[y * info.line_size..];
+                for x in 0..fw {
+                    let p = &line[x * pixel_size..];
+                    let src = match info.color_type {
+                        ColorType::Grayscale => color4b(p[0], p[0], p[0], 0xFF),
+                        ColorType::GrayscaleAlpha => color4b(p[0], p[0], p[0], p[1]),
+                        ColorType::Rgb | ColorType::Indexed => color4b(p[0], p[1], p[2], 0xFF),
+                        ColorType::Rgba => color4b(p[0], p[1], p[2], p[3]),
+                    };
+                    let (cx, cy) = (fx + x, fy + y);
+                    if cx >= width || cy >= height {
+                        continue;
+                    }
+                    let dst = &mut canvas[cx + cy * width];
+                    *dst = match fc.map(|f| f.blend_op) {
+                        Some(BlendOp::Over) => blend_over(*dst, src),
+                        _ => src,
+                    };
+                }
+            }
+            frames.push((canvas.clone(), fc.map(|f| frame_delay(f.delay_num, f.delay_den)).unwrap_or(0.1)));
+
+            // prepare the canvas of the next frame
+            match fc.map(|f| f.dispose_op) {
+                Some(DisposeOp::Background) => {
+                    for y in fy..min(fy + fh, height) {
+                        for x in fx..min(fx + fw, width) {
+                            canvas[x + y * width] = Color4b::default();
+                        }
+                    }
+                }
+                Some(DisposeOp::Previous) => canvas = previous,
+                _ => (),
+            }
+        }
+        let mut anim = Self::from_frames(slot, frames);
+        anim.looping = reader.info().animation_control().map(|a| a.num_plays == 0).unwrap_or(true);
+        Ok(anim)
+    }
+}
+
+#[cfg(feature = "apng")]
+fn frame_delay(num: u16, den: u16) -> Real {
+    // a zero denominator means hundredths of a second
+    let den = if den == 0 { 100 } else { den };
+    num as Real / den as Real
+}
+
+#[cfg(feature = "apng")]
+fn blend_over(dst: Color4b, src: Color4b) -> Color4b {
+    let sa = src.w as u32;
+    let da = dst.w as u32 * (255 - sa) / 255;
+    let a = sa + da;
+    if a == 0 {
+        return Color4b::default();
+    }
+    let mix = |s: u8, d: u8| ((s as u32 * sa + d as u32 * da) / a) as u8;
+    color4b(mix(src.x, dst.x), mix(src.y, dst.y), mix(src.z, dst.z), a as u8)
+}
+
+impl Container {
+    // Shows the current frame of `anim` centered in the cell and moves it forward with the frame time.
+    // Clicking the image toggles play/pause. Returns CHANGE when the shown frame changed.
+    #[inline(never)]
+    pub fn animated_image(&mut self, anim: &mut AnimatedImage, opt: WidgetOption) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let id = self.idmngr.get_id_from_ptr(anim);
+        let frame_size = anim.frame_size(&self.atlas);
+        let r = self.layout.next_sized(frame_size);
+        self.update_control(id, r, opt);
+        if self.is_clicked(id) {
+            if anim.playing {
+                anim.pause();
+            } else {
+                anim.play();
+            }
+            res |= ResourceState::SUBMIT;
+        }
+        if anim.advance(self.frame_dt()) {
+            res |= ResourceState::CHANGE;
+        }
+        if anim.frame_count() == 0 {
+            return res;
+        }
+
+        let white = color(0xFF, 0xFF, 0xFF, 0xFF);
+        match &anim.frames {
+            AnimationFrames::SpriteSheet { slot, columns, .. } => {
+                let sheet = self.atlas.get_slot_rect(*slot);
+                let (fw, fh) = (frame_size.width, frame_size.height);
+                let (col, row) = ((anim.frame % columns) as i32, (anim.frame / columns) as i32);
+                let src = rect(sheet.x + col * fw, sheet.y + row * fh, fw, fh);
+                let dst = rect(r.x + (r.width - fw) / 2, r.y + (r.height - fh) / 2, fw, fh);
+                self.draw_atlas_region(src, dst, white);
+            }
+            AnimationFrames::Pixels { slot, frames } => {
+                // the slot is only rewritten when the frame changes, not every time it's drawn
+                if anim.uploaded != Some(anim.frame) {
+                    let slot_rect = self.atlas.get_slot_rect(*slot);
+                    let pixels = frames[anim.frame].clone();
+                    let width = slot_rect.width as usize;
+                    self.atlas.render_slot(
+                        *slot,
+                        Rc::new(move |x, y| {
+                            let i = (x - slot_rect.x as usize) + (y - slot_rect.y as usize) * width;
+                            pixels.get(i).copied().unwrap_or_default()
+                        }),
+                    );
+                    anim.uploaded = Some(anim.frame);
+                }
+                self.draw_slot(*slot, r, white);
+            }
+        }
+        res
+    }
+}
@@ -0,0 +1,84 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Per-frame bump storage for the strings carried by the draw commands. Everything is released at once when the
+// container is prepared for the next frame and the buffer keeps its capacity, so steady frames don't allocate.
+// (layout, clip and command stacks are plain vectors that are cleared, they keep their capacity as well)
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ArenaStr {
+    start: u32,
+    len: u32,
+}
+
+#[derive(Default)]
+pub struct FrameArena {
+    text: String,
+    peak: usize,
+}
+
+impl FrameArena {
+    pub fn alloc_str(&mut self, s: &str) -> ArenaStr {
+        let start = self.text.len() as u32;
+        self.text.push_str(s);
+        ArenaStr { start, len: s.len() as u32 }
+    }
+
+    pub fn get(&self, s: ArenaStr) -> &str {
+        &self.text[s.start as usize..(s.start + s.len) as usize]
+    }
+
+    pub fn reset(&mut self) {
+        self.peak = self.peak.max(self.text.len());
+        self.text.clear();
+    }
+
+    pub fn used(&self) -> usize {
+        self.text.len()
+    }
+
+    // highest usage of a frame so far
+    pub fn peak(&self) -> usize {
+        self.peak.max(self.text.len())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.text.capacity()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FrameStats {
+    pub containers: usize,
+    pub commands: usize,
+    pub arena_used: usize,
+    pub arena_peak: usize,
+    pub arena_capacity: usize,
+    pub state_entries: usize, // per widget state kept across frames
+}
@@ -50,14 +50,19 @@ use super::*;
 pub struct CharEntry {
     pub offset: Vec2i,
     pub advance: Vec2i,
-    pub rect: Recti, // coordinates in the atlas
+    pub rect: Recti,    // coordinates in the atlas
+    pub advance_x: f32, // unrounded horizontal advance, 0 falls back to `advance`
+    pub colored: bool,  // RGBA glyph drawn with its own colors (emoji), only the alpha of the text color applies
 }
 
 #[derive(Clone)]
 struct Font {
-    line_size: usize,                  // line size
-    font_size: usize,                  // font size in pixels
-    entries: HashMap<char, CharEntry>, // all printable chars [32-127]
+    line_size: usize,                    // line size
+    font_size: usize,                    // font size in pixels
+    entries: HashMap<char, CharEntry>,   // all printable chars [32-127]
+    kerning: HashMap<(char, char), f32>, // adjustment of the advance between two chars, in pixels
+    letter_spacing: i32,                 // pixels added to every advance, set at runtime
+    has_colored: bool,                   // skips the per glyph lookup when drawing fonts without color glyphs
 }
 
 impl Debug for Font {
@@ -74,7 +79,7 @@ impl Debug for Font {
     }
 }
 
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
 pub struct FontId(usize);
 
 #[derive(Default, Copy, Clone)]
@@ -83,6 +88,12 @@ pub struct IconId(usize);
 #[derive(Default, Copy, Clone)]
 pub struct SlotId(usize);
 
+impl From<FontId> for u32 {
+    fn from(font: FontId) -> u32 {
+        font.0 as _
+    }
+}
+
 impl Into<u32> for IconId {
     fn into(self) -> u32 {
         self.0 as _
@@ -95,6 +106,35 @@ impl Into<u32> for SlotId {
     }
 }
 
+// How the texels are filtered when an image is drawn scaled
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TextureFilter {
+    #[default]
+    Nearest,
+    Linear,
+}
+
+// Sampling requested for a slot, the renderer receives it through `Renderer::set_sampler`. Mipmaps keep
+// scaled down thumbnails from shimmering, the renderer generates them when the atlas is uploaded.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Sampler {
+    pub filter: TextureFilter,
+    pub mipmaps: bool,
+}
+
+impl Sampler {
+    pub const NEAREST: Sampler = Sampler { filter: TextureFilter::Nearest, mipmaps: false };
+    pub const LINEAR: Sampler = Sampler { filter: TextureFilter::Linear, mipmaps: false };
+    pub const LINEAR_MIPMAPPED: Sampler = Sampler { filter: TextureFilter::Linear, mipmaps: true };
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AtlasRectKind {
+    Glyph,
+    Icon,
+    Slot,
+}
+
 #[derive(Debug, Clone)]
 struct Icon {
     rect: Recti,
@@ -107,6 +147,7 @@ struct Atlas {
     fonts: Vec<(String, Font)>,
     icons: Vec<(String, Icon)>,
     slots: Vec<Recti>,
+    samplers: Vec<Sampler>, // one per slot
     last_update_id: usize,
 }
 
@@ -201,6 +242,7 @@ pub mod builder {
         pub default_font: String,
         pub default_font_size: usize,
         pub slots: &'a [Dimensioni],
+        pub packing: PackingAlgorithm,
     }
 
     impl Builder {
@@ -211,6 +253,7 @@ pub mod builder {
 
                 border_padding: 1,
                 rectangle_padding: 1,
+                algorithm: config.packing,
             };
 
             let atlas = Atlas {
@@ -220,6 +263,7 @@ pub mod builder {
                 fonts: Vec::new(),
                 icons: Vec::new(),
                 slots: Vec::new(),
+                samplers: Vec::new(),
                 last_update_id: 0,
             };
 
@@ -242,6 +286,23 @@ pub mod builder {
             Ok(builder)
         }
 
+        // Adds an RGBA glyph to a font, its bottom sits on the bottom of the line. Used for emoji, the pixels
+        // keep their colors when drawn.
+        pub fn add_color_glyph(&mut self, font: FontId, ch: char, width: usize, height: usize, pixels: &[Color4b]) -> Result<()> {
+            let rect = self.add_tile(width, height, pixels)?;
+            let ce = CharEntry {
+                offset: Vec2i::new(0, 0),
+                advance: Vec2i::new(width as _, 0),
+                rect,
+                advance_x: width as f32,
+                colored: true,
+            };
+            let font = &mut self.atlas.fonts[font.0].1;
+            font.entries.insert(ch, ce);
+            font.has_colored = true;
+            Ok(())
+        }
+
         pub fn add_icon(&mut self, path: &str) -> Result<IconId> {
             let (width, height, pixels) = Self::load_icon(path)?;
             let rect = self.add_tile(width, height, pixels.as_slice())?;
@@ -275,17 +336,34 @@ pub mod builder {
                     offset: Vec2i::new(metrics.xmin, metrics.ymin),
                     advance: Vec2i::new(metrics.advance_width as _, metrics.advance_height as _),
                     rect,
+                    advance_x: metrics.advance_width,
+                    colored: false,
                 };
                 entries.insert(i as u8 as char, ce);
                 min_y = min_y.min(size as i32 - metrics.ymin - metrics.height as i32);
                 max_y = max_y.max(size as i32 - metrics.ymin - metrics.height as i32);
             }
 
+            let mut kerning = HashMap::new();
+            for left in 32..127u8 {
+                for right in 32..127u8 {
+                    match font.horizontal_kern(left as char, right as char, size as f32) {
+                        Some(k) if k != 0.0 => {
+                            kerning.insert((left as char, right as char), k);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
             let id = self.atlas.fonts.len();
             let font = super::Font {
                 line_size: (max_y - min_y) as usize,
                 font_size: size,
                 entries,
+                kerning,
+                letter_spacing: 0,
+                has_colored: false,
             };
             self.atlas
                 .fonts
@@ -342,6 +420,7 @@ pub mod builder {
             match rect {
                 Some(r) => {
                     self.atlas.slots.push(r);
+                    self.atlas.samplers.push(Sampler::default());
                     Ok(r)
                 }
                 None => {
@@ -412,6 +491,7 @@ pub struct FontEntry<'a> {
     pub line_size: usize,                 // line size
     pub font_size: usize,                 // font size in pixels
     pub entries: &'a [(char, CharEntry)], // all printable chars [32-127]
+    pub kerning: &'a [((char, char), f32)],
 }
 
 pub enum SourceFormat {
@@ -449,11 +529,15 @@ impl AtlasHandle {
                         .iter()
                         .map(|(ch, e)| (ch.clone(), e.clone()))
                         .collect(),
+                    kerning: f.kerning.iter().copied().collect(),
+                    letter_spacing: 0,
+                    has_colored: f.entries.iter().any(|(_, e)| e.colored),
                 };
                 (name.to_string(), font)
             })
             .collect();
         let slots: Vec<Recti> = source.slots.iter().map(|p| *p).collect();
+        let samplers = vec![Sampler::default(); slots.len()];
         let pixels = match source.format {
             SourceFormat::Raw => {
                 let mut v = Vec::new();
@@ -477,6 +561,7 @@ impl AtlasHandle {
             icons,
             fonts,
             slots,
+            samplers,
             pixels,
             last_update_id: 0,
         })))
@@ -518,8 +603,11 @@ impl AtlasHandle {
         slots.push_str("]");
         let mut fonts = String::from_str("&[\n").unwrap();
         for (n, f) in &self.0.borrow().fonts {
+            // the maps are sorted so the generated source doesn't change between runs
+            let mut glyphs: Vec<_> = f.entries.iter().collect();
+            glyphs.sort_by_key(|(ch, _)| **ch);
             let mut char_entries = String::from_str("&[\n").unwrap();
-            for (ch, entry) in &f.entries {
+            for (ch, entry) in glyphs {
                 let str = match ch {
                     '\'' => String::from_str("\\'").unwrap(),
                     '\\' => String::from_str("\\\\").unwrap(),
@@ -527,17 +615,24 @@ impl AtlasHandle {
                 };
                 char_entries.push_str(
                     format!(
-                        "('{}', CharEntry {{ offset: Vec2i {{ x: {}, y:{} }}, advance: Vec2i {{ x:{}, y: {} }}, rect: Recti {{x: {}, y: {}, width: {}, height: {} }}, }}),\n",
-                        str, entry.offset.x, entry.offset.y, entry.advance.x, entry.advance.y, entry.rect.x, entry.rect.y, entry.rect.width, entry.rect.height,
+                        "('{}', CharEntry {{ offset: Vec2i {{ x: {}, y:{} }}, advance: Vec2i {{ x:{}, y: {} }}, rect: Recti {{x: {}, y: {}, width: {}, height: {} }}, advance_x: {:?}, colored: {}, }}),\n",
+                        str, entry.offset.x, entry.offset.y, entry.advance.x, entry.advance.y, entry.rect.x, entry.rect.y, entry.rect.width, entry.rect.height, entry.advance_x, entry.colored,
                     )
                         .as_str(),
                 );
             }
             char_entries.push_str("]\n");
+            let mut pairs: Vec<_> = f.kerning.iter().collect();
+            pairs.sort_by_key(|(pair, _)| **pair);
+            let mut kerning = String::from_str("&[").unwrap();
+            for ((l, r), k) in pairs {
+                kerning.push_str(format!("(({:?}, {:?}), {:?}),", l, r, k).as_str());
+            }
+            kerning.push(']');
             fonts.push_str(
                 format!(
-                    "(\"{}\", FontEntry {{ line_size: {}, font_size: {}, entries: {} }}),\n",
-                    n, f.line_size, f.font_size, char_entries
+                    "(\"{}\", FontEntry {{ line_size: {}, font_size: {}, entries: {}, kerning: {} }}),\n",
+                    n, f.line_size, f.font_size, char_entries, kerning
                 )
                 .as_str(),
             );
@@ -633,6 +728,29 @@ impl AtlasHandle {
         self.0.borrow().fonts[font.0].1.line_size
     }
 
+    pub fn has_color_glyphs(&self, font: FontId) -> bool {
+        self.0.borrow().fonts[font.0].1.has_colored
+    }
+
+    pub fn get_kerning(&self, font: FontId, left: char, right: char) -> f32 {
+        let fonts = &self.0.borrow().fonts;
+        let kerning = &fonts[font.0].1.kerning;
+        if kerning.is_empty() {
+            0.0
+        } else {
+            kerning.get(&(left, right)).copied().unwrap_or(0.0)
+        }
+    }
+
+    // tracking of the font, negative values tighten it
+    pub fn set_letter_spacing(&mut self, font: FontId, spacing: i32) {
+        self.0.borrow_mut().fonts[font.0].1.letter_spacing = spacing;
+    }
+
+    pub fn get_letter_spacing(&self, font: FontId) -> i32 {
+        self.0.borrow().fonts[font.0].1.letter_spacing
+    }
+
     pub fn get_icon_size(&self, icon: IconId) -> Dimensioni {
         let r = self.0.borrow().icons[icon.0].1.rect;
         Dimensioni::new(r.width, r.height)
@@ -642,6 +760,20 @@ impl AtlasHandle {
         self.0.borrow().icons[icon.0].1.rect
     }
 
+    // every packed rectangle with what it holds
+    pub fn packed_rects(&self) -> Vec<(AtlasRectKind, Recti)> {
+        let atlas = self.0.borrow();
+        let icons = atlas.icons.iter().map(|(_, i)| (AtlasRectKind::Icon, i.rect));
+        let glyphs = atlas.fonts.iter().flat_map(|(_, f)| f.entries.values()).map(|e| (AtlasRectKind::Glyph, e.rect));
+        let slots = atlas.slots.iter().map(|r| (AtlasRectKind::Slot, *r));
+        icons.chain(glyphs).chain(slots).collect()
+    }
+
+    // pixels covered by icons, glyphs and slots
+    pub fn used_area(&self) -> usize {
+        self.packed_rects().iter().map(|(_, r)| (r.width.max(0) * r.height.max(0)) as usize).sum()
+    }
+
     pub fn get_slot_size(&self, slot: SlotId) -> Dimensioni {
         let r = self.0.borrow().slots[slot.0];
         Dimension::new(r.width, r.height)
@@ -651,6 +783,25 @@ impl AtlasHandle {
         self.0.borrow().slots[slot.0]
     }
 
+    pub fn get_slot_sampler(&self, slot: SlotId) -> Sampler {
+        self.0.borrow().samplers[slot.0]
+    }
+
+    // the atlas is marked as updated when mipmaps get requested so the renderer generates them
+    pub fn set_slot_sampler(&mut self, slot: SlotId, sampler: Sampler) {
+        let mut atlas = self.0.borrow_mut();
+        let regenerate = sampler.mipmaps && !atlas.samplers.iter().any(|s| s.mipmaps);
+        atlas.samplers[slot.0] = sampler;
+        if regenerate {
+            atlas.last_update_id = atlas.last_update_id.wrapping_add(1);
+        }
+    }
+
+    // true when at least one slot samples mipmaps
+    pub fn has_mipmaps(&self) -> bool {
+        self.0.borrow().samplers.iter().any(|s| s.mipmaps)
+    }
+
     pub fn get_texture_dimension(&self) -> Dimensioni {
         Dimension::new(self.0.borrow().width as _, self.0.borrow().height as _)
     }
@@ -668,8 +819,11 @@ impl AtlasHandle {
             height: 0,
         };
         let fh = self.get_font_height(font) as i32;
-        let mut acc_x = 0;
+        let spacing = self.get_letter_spacing(font) as f32;
+        // the pen advances with sub-pixel precision, glyphs are placed at the nearest pixel
+        let mut pen_x = 0.0;
         let mut acc_y = 0;
+        let mut prev = None;
         for chr in text.chars() {
             // string could be empty
             if acc_y == 0 {
@@ -677,19 +831,24 @@ impl AtlasHandle {
             }
 
             if chr == '\n' || chr == '\r' {
-                acc_x = 0;
+                pen_x = 0.0;
                 acc_y += fh;
+                prev = None;
             } else {
                 let src = match self.get_char_entry(font, chr) {
                     Some(ce) => ce,
                     None => self.get_char_entry(font, '_').unwrap(),
                 };
+                if let Some(p) = prev {
+                    pen_x += self.get_kerning(font, p, chr);
+                }
                 dst.width = src.rect.width;
                 dst.height = src.rect.height;
-                dst.x = acc_x + src.offset.x;
+                dst.x = pen_x.round() as i32 + src.offset.x;
                 dst.y = acc_y - src.offset.y - src.rect.height;
                 f(chr, src.advance, dst, src.rect);
-                acc_x += src.advance.x;
+                pen_x += if src.advance_x != 0.0 { src.advance_x } else { src.advance.x as f32 } + spacing;
+                prev = Some(chr);
             }
         }
     }
@@ -0,0 +1,126 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+pub(crate) struct AtlasWindow {
+    win: WindowHandle,
+    zoom: Real,
+    overlays: [bool; 3], // glyphs, icons, slots
+}
+
+const OVERLAY_KINDS: [(AtlasRectKind, &str); 3] = [(AtlasRectKind::Glyph, "Glyphs"), (AtlasRectKind::Icon, "Icons"), (AtlasRectKind::Slot, "Slots")];
+
+impl AtlasWindow {
+    fn overlay_color(kind: AtlasRectKind, style: &Style) -> Color {
+        let c = match kind {
+            AtlasRectKind::Glyph => style.color(ControlColor::Success),
+            AtlasRectKind::Icon => style.color(ControlColor::Warning),
+            AtlasRectKind::Slot => style.color(ControlColor::Error),
+        };
+        color(c.r, c.g, c.b, 160)
+    }
+
+    fn eval(&mut self, cont: &mut Container, atlas: &AtlasHandle) {
+        let dim = atlas.get_texture_dimension();
+        let rects = atlas.packed_rects();
+        let area = |r: &Recti| (r.width.max(0) * r.height.max(0)) as usize;
+        let used = atlas.used_area();
+        let occupancy = used as Real * 100.0 / (dim.width * dim.height).max(1) as Real;
+
+        cont.set_row_widths_height(&[-1], 0);
+        cont.label(&format!("{}x{}, {:.1}% used, {} rects", dim.width, dim.height, occupancy, rects.len()));
+        cont.set_row_widths_height(&[90, 70, -1], 0);
+        for (i, (kind, name)) in OVERLAY_KINDS.iter().enumerate() {
+            let (count, pixels) = rects.iter().filter(|(k, _)| k == kind).fold((0, 0), |(c, p), (_, r)| (c + 1, p + area(r)));
+            cont.checkbox(name, &mut self.overlays[i]);
+            cont.label(&format!("{}", count));
+            cont.label(&format!("{} px", pixels));
+        }
+        cont.set_row_widths_height(&[50, -1], 0);
+        cont.label("Zoom");
+        cont.slider_ex(&mut self.zoom, 0.25, 4.0, 0.25, 2, WidgetOption::NONE);
+
+        let w = (dim.width as Real * self.zoom) as i32;
+        let h = (dim.height as Real * self.zoom) as i32;
+        cont.set_row_widths_height(&[w], h);
+        let r = cont.next_cell();
+        let r = rect(r.x, r.y, w, h);
+        // pinching the atlas zooms it
+        let id = cont.idmngr.get_id_from_str("!atlas_image");
+        cont.update_control(id, r, WidgetOption::GESTURES);
+        if let Some(g) = cont.gesture(id) {
+            self.zoom = (self.zoom * g.zoom).clamp(0.25, 4.0);
+        }
+        cont.draw_rect(r, color(0, 0, 0, 255));
+        cont.draw_atlas_region(rect(0, 0, dim.width, dim.height), r, color(255, 255, 255, 255));
+
+        let mut hovered = None;
+        for (kind, src) in &rects {
+            let i = OVERLAY_KINDS.iter().position(|(k, _)| k == kind).unwrap_or(0);
+            if !self.overlays[i] {
+                continue;
+            }
+            let dst = rect(
+                r.x + (src.x as Real * self.zoom) as i32,
+                r.y + (src.y as Real * self.zoom) as i32,
+                ((src.width as Real * self.zoom) as i32).max(1),
+                ((src.height as Real * self.zoom) as i32).max(1),
+            );
+            cont.draw_box(dst, Self::overlay_color(*kind, &cont.style));
+            if cont.mouse_over(dst, cont.in_hover_root) {
+                hovered = Some((*kind, *src));
+            }
+        }
+
+        cont.set_row_widths_height(&[-1], 0);
+        match hovered {
+            Some((kind, src)) => cont.label(&format!("{:?} {}x{} at {},{}", kind, src.width, src.height, src.x, src.y)),
+            None => cont.label(""),
+        }
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // A debug window showing the atlas texture with the packed glyphs, icons and slots outlined.
+    // Call it every frame while it should be visible.
+    pub fn show_atlas_window(&mut self) {
+        let mut view = self.atlas_view.take().unwrap_or_else(|| AtlasWindow {
+            win: self.new_window("Atlas", rect(40, 40, 420, 480)),
+            zoom: 1.0,
+            overlays: [true; 3],
+        });
+        let atlas = self.canvas.get_atlas();
+        self.window(&mut view.win.clone(), ContainerOption::NONE, |cont| {
+            view.eval(cont, &atlas);
+            WindowState::Open
+        });
+        self.atlas_view = Some(view);
+    }
+}
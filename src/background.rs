@@ -0,0 +1,94 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BackgroundMode {
+    // fills the body, ignoring the aspect ratio
+    Stretch,
+    // repeats the image at its size from the top left corner of the body
+    Tile,
+    // the image at its size in the middle of the body, as a watermark
+    Center,
+}
+
+// An image drawn under the widgets of a window or a panel body
+#[derive(Copy, Clone)]
+pub struct BackgroundImage {
+    pub image: SlotId,
+    pub mode: BackgroundMode,
+    pub opacity: Real,
+}
+
+impl BackgroundImage {
+    pub fn new(image: SlotId, mode: BackgroundMode, opacity: Real) -> Self {
+        Self { image, mode, opacity }
+    }
+}
+
+impl Container {
+    pub fn set_background(&mut self, background: Option<BackgroundImage>) {
+        self.background = background;
+    }
+
+    pub fn background(&self) -> Option<BackgroundImage> {
+        self.background
+    }
+
+    pub(crate) fn draw_background(&mut self, body: Recti) {
+        let bg = match self.background {
+            Some(bg) if bg.opacity > 0.0 && body.width > 0 && body.height > 0 => bg,
+            _ => return,
+        };
+        let color = color(255, 255, 255, (bg.opacity.min(1.0) * 255.0) as u8);
+        let size = self.atlas.get_slot_size(bg.image);
+        self.push_clip_rect(body);
+        match bg.mode {
+            BackgroundMode::Stretch => self.draw_slot(bg.image, body, color),
+            BackgroundMode::Center => {
+                let r = rect(body.x + (body.width - size.width) / 2, body.y + (body.height - size.height) / 2, size.width, size.height);
+                self.draw_slot(bg.image, r, color);
+            }
+            BackgroundMode::Tile if size.width > 0 && size.height > 0 => {
+                let mut y = body.y;
+                while y < body.y + body.height {
+                    let mut x = body.x;
+                    while x < body.x + body.width {
+                        self.draw_slot(bg.image, rect(x, y, size.width, size.height), color);
+                        x += size.width;
+                    }
+                    y += size.height;
+                }
+            }
+            BackgroundMode::Tile => (),
+        }
+        self.pop_clip_rect();
+    }
+}
@@ -0,0 +1,120 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+// A binding is a value living outside of the widget, read once per frame and written back only when the widget
+// changes it. This avoids copying values from the application state (Rc<RefCell<..>>, atomics, ECS components, ...)
+// into temporaries and back manually every frame.
+pub trait Binding<T> {
+    fn get(&self) -> T;
+    fn set(&mut self, value: T);
+}
+
+impl<T: Clone> Binding<T> for Rc<RefCell<T>> {
+    fn get(&self) -> T {
+        self.borrow().clone()
+    }
+
+    fn set(&mut self, value: T) {
+        *self.borrow_mut() = value;
+    }
+}
+
+impl<T: Copy> Binding<T> for Rc<Cell<T>> {
+    fn get(&self) -> T {
+        Cell::get(self)
+    }
+
+    fn set(&mut self, value: T) {
+        Cell::set(self, value)
+    }
+}
+
+impl Binding<bool> for Arc<AtomicBool> {
+    fn get(&self) -> bool {
+        self.load(Ordering::Relaxed)
+    }
+
+    fn set(&mut self, value: bool) {
+        self.store(value, Ordering::Relaxed)
+    }
+}
+
+// Real stored as its bit pattern, since there's no atomic float in std
+#[derive(Default)]
+pub struct AtomicReal(AtomicU32);
+
+impl AtomicReal {
+    pub fn new(value: Real) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    pub fn load(&self) -> Real {
+        Real::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    pub fn store(&self, value: Real) {
+        self.0.store(value.to_bits(), Ordering::Relaxed)
+    }
+}
+
+impl Binding<Real> for Arc<AtomicReal> {
+    fn get(&self) -> Real {
+        self.load()
+    }
+
+    fn set(&mut self, value: Real) {
+        self.store(value)
+    }
+}
+
+// Binding from a pair of closures, useful to reach into components that can't be borrowed directly
+pub struct FnBinding<G, S> {
+    getter: G,
+    setter: S,
+}
+
+impl<T, G: Fn() -> T, S: FnMut(T)> FnBinding<G, S> {
+    pub fn new(getter: G, setter: S) -> Self {
+        Self { getter, setter }
+    }
+}
+
+impl<T, G: Fn() -> T, S: FnMut(T)> Binding<T> for FnBinding<G, S> {
+    fn get(&self) -> T {
+        (self.getter)()
+    }
+
+    fn set(&mut self, value: T) {
+        (self.setter)(value)
+    }
+}
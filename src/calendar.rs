@@ -0,0 +1,76 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+impl Container {
+    // GitHub style activity grid: one column per week, one row per day. `values` holds one entry per day,
+    // `first_weekday` is the row (0..7) of the first entry. Cell intensity is `value / max` quantized in 5 levels.
+    #[inline(never)]
+    pub fn activity_calendar<F: Fn(usize, Real) -> String>(&mut self, values: &[Real], first_weekday: usize, max: Real, accent: Color, tooltip: F) -> Option<usize> {
+        let id = self.idmngr.get_id_from_ptr(values);
+        let r = self.layout.next();
+        self.update_control(id, r, WidgetOption::NONE);
+
+        let gap = 2;
+        let cell = max_cell_size(r.height, gap);
+        let base = self.style.colors[ControlColor::Base as usize];
+        let mouse = self.input.borrow().mouse_pos;
+        let mut hovered = None;
+
+        for (i, value) in values.iter().enumerate() {
+            let day = i + first_weekday % 7;
+            let (week, weekday) = ((day / 7) as i32, (day % 7) as i32);
+            let c = rect(r.x + week * (cell + gap), r.y + weekday * (cell + gap), cell, cell);
+            if c.x + c.width > r.x + r.width {
+                break;
+            }
+
+            let t = if max > 0.0 { (value / max).clamp(0.0, 1.0) } else { 0.0 };
+            let level = (t * 4.0).ceil() / 4.0;
+            let lerp = |a: u8, b: u8| (a as Real + (b as Real - a as Real) * level) as u8;
+            let color = color(lerp(base.r, accent.r), lerp(base.g, accent.g), lerp(base.b, accent.b), 0xFF);
+            self.draw_rect(c, color);
+
+            if self.hover == Some(id) && c.contains(&mouse) {
+                hovered = Some(i);
+            }
+        }
+
+        if let Some(i) = hovered {
+            let text = tooltip(i, values[i]);
+            self.tooltip(text.as_str());
+        }
+        hovered
+    }
+}
+
+fn max_cell_size(height: i32, gap: i32) -> i32 {
+    max((height - gap * 6) / 7, 1)
+}
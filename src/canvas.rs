@@ -38,10 +38,23 @@ pub struct Vertex {
     color: Color4b,
 }
 
+impl Vertex {
+    // for backends drawing their own quads, e.g. compositing an offscreen target
+    pub fn new(pos: Vec2f, tex: Vec2f, color: Color4b) -> Self {
+        Self { pos, tex, color }
+    }
+}
+
 pub struct Canvas<R: Renderer> {
     current_dim: Dimensioni,
     renderer: RendererHandle<R>,
     clip: Recti,
+    mask: Option<ClipMask>,
+    sampler: Sampler,
+    hardware_clip: bool,
+    pixel_snap: Option<f32>,
+    color_space: ColorSpace,
+    vertex_count: usize,
 }
 
 impl<R: Renderer> Canvas<R> {
@@ -50,6 +63,12 @@ impl<R: Renderer> Canvas<R> {
             current_dim: dim,
             renderer,
             clip: Recti::new(0, 0, dim.width, dim.height),
+            mask: None,
+            sampler: Sampler::default(),
+            hardware_clip: false,
+            pixel_snap: None,
+            color_space: ColorSpace::Srgb,
+            vertex_count: 0,
         }
     }
 
@@ -59,92 +78,182 @@ impl<R: Renderer> Canvas<R> {
 
     #[inline(never)]
     pub fn clip_rect(dst_r: Recti, src_r: Recti, clip_r: Recti) -> Option<(Recti, Recti)> {
-        match dst_r.intersect(&clip_r) {
-            Some(rect) if rect.width == dst_r.width && rect.height == dst_r.height => {
-                Some((dst_r, src_r))
-            }
-            Some(rect) if rect.width != 0 && rect.height != 0 => {
-                let dx = dst_r.x as f32;
-                let dy = dst_r.y as f32;
-                let dw = dst_r.width as f32;
-                let dh = dst_r.height as f32;
-
-                let rx = rect.x as f32;
-                let ry = rect.y as f32;
-                let rw = rect.width as f32;
-                let rh = rect.height as f32;
-
-                let tx = (rx - dx) / dw;
-                let ty = (ry - dy) / dh;
-                let tw = (rx + rw - dx) / dw;
-                let th = (ry + rh - dy) / dh;
-
-                let sx = src_r.x as f32;
-                let sy = src_r.y as f32;
-                let sw = src_r.width as f32;
-                let sh = src_r.height as f32;
-
-                let st_x = sx + tx * sw;
-                let st_y = sy + ty * sh;
-                let st_w = sx + tw * sw - st_x;
-                let st_h = sy + th * sh - st_y;
-
-                Some((rect, Recti::new(st_x as _, st_y as _, st_w as _, st_h as _)))
-            }
-            _ => None,
+        let rect = rect_intersect(&dst_r, &clip_r);
+        if rect_is_empty(&rect) {
+            return None;
+        }
+        if rect.width == dst_r.width && rect.height == dst_r.height {
+            return Some((dst_r, src_r));
+        }
+
+        let dx = dst_r.x as f32;
+        let dy = dst_r.y as f32;
+        let dw = dst_r.width as f32;
+        let dh = dst_r.height as f32;
+
+        let rx = rect.x as f32;
+        let ry = rect.y as f32;
+        let rw = rect.width as f32;
+        let rh = rect.height as f32;
+
+        let tx = (rx - dx) / dw;
+        let ty = (ry - dy) / dh;
+        let tw = (rx + rw - dx) / dw;
+        let th = (ry + rh - dy) / dh;
+
+        let sx = src_r.x as f32;
+        let sy = src_r.y as f32;
+        let sw = src_r.width as f32;
+        let sh = src_r.height as f32;
+
+        let st_x = sx + tx * sw;
+        let st_y = sy + ty * sh;
+        let st_w = sx + tw * sw - st_x;
+        let st_h = sy + th * sh - st_y;
+
+        Some((rect, Recti::new(st_x as _, st_y as _, st_w as _, st_h as _)))
+    }
+
+    fn quad_vertices(dst: Recti, src: Recti, atlas_dim: Dimensioni, color: Color) -> [Vertex; 4] {
+        let x = src.x as f32 / atlas_dim.width as f32;
+        let y = src.y as f32 / atlas_dim.height as f32;
+        let w = src.width as f32 / atlas_dim.width as f32;
+        let h = src.height as f32 / atlas_dim.height as f32;
+
+        let (dx, dy) = (dst.x as f32, dst.y as f32);
+        let (dw, dh) = (dst.width as f32, dst.height as f32);
+        let color = color4b(color.r, color.g, color.b, color.a);
+        let vertex = |px: f32, py: f32, tx: f32, ty: f32| Vertex {
+            pos: Vec2f::new(px, py),
+            tex: Vec2f::new(tx, ty),
+            color,
+        };
+        [
+            vertex(dx, dy, x, y),
+            vertex(dx + dw, dy, x + w, y),
+            vertex(dx + dw, dy + dh, x + w, y + h),
+            vertex(dx, dy + dh, x, y + h),
+        ]
+    }
+
+    // The colors are converted from sRGB when the renderer expects linear vertex colors
+    pub fn set_vertex_color_space(&mut self, space: ColorSpace) {
+        self.color_space = space;
+    }
+
+    pub fn vertex_color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    fn vertex_color(&self, color: Color) -> Color {
+        match self.color_space {
+            ColorSpace::Srgb => color,
+            ColorSpace::Linear => color.to_linear(),
+        }
+    }
+
+    // Rounds the vertex positions to the device pixel grid, `scale` being the device pixels per UI unit,
+    // so 1px frames and separators stay sharp at fractional scales. `None` keeps the positions as is.
+    pub fn set_pixel_snap(&mut self, scale: Option<f32>) {
+        self.pixel_snap = scale.filter(|s| *s > 0.0);
+    }
+
+    pub fn pixel_snap(&self) -> Option<f32> {
+        self.pixel_snap
+    }
+
+    // vertices pushed to the renderer since `begin`
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    fn snap(pixel_snap: Option<f32>, p: Vec2f) -> Vec2f {
+        match pixel_snap {
+            Some(s) => Vec2f::new((p.x * s).round() / s, (p.y * s).round() / s),
+            None => p,
         }
     }
 
     #[inline(never)]
     pub fn push_rect(&mut self, dst: Recti, src: Recti, color: Color) {
-        let atlas_dim = self
-            .renderer
-            .scope(|r| r.get_atlas())
-            .get_texture_dimension();
+        let color = self.vertex_color(color);
+        let atlas_dim = self.renderer.scope(|r| r.get_atlas()).get_texture_dimension();
 
-        let clip = self.clip;
-        self.renderer.scope_mut(move |r| {
-            match Self::clip_rect(dst, src, clip) {
-                Some((dst, src)) => {
-                    let x = src.x as f32 / atlas_dim.width as f32;
-                    let y = src.y as f32 / atlas_dim.height as f32;
-                    let w = src.width as f32 / atlas_dim.width as f32;
-                    let h = src.height as f32 / atlas_dim.height as f32;
-
-                    let mut v0 = Vertex::default();
-                    let mut v1 = Vertex::default();
-                    let mut v2 = Vertex::default();
-                    let mut v3 = Vertex::default();
-
-                    // tex coordinates
-                    v0.tex.x = x;
-                    v0.tex.y = y;
-                    v1.tex.x = x + w;
-                    v1.tex.y = y;
-                    v2.tex.x = x + w;
-                    v2.tex.y = y + h;
-                    v3.tex.x = x;
-                    v3.tex.y = y + h;
-
-                    // position
-                    v0.pos.x = dst.x as f32;
-                    v0.pos.y = dst.y as f32;
-                    v1.pos.x = dst.x as f32 + dst.width as f32;
-                    v1.pos.y = dst.y as f32;
-                    v2.pos.x = dst.x as f32 + dst.width as f32;
-                    v2.pos.y = dst.y as f32 + dst.height as f32;
-                    v3.pos.x = dst.x as f32;
-                    v3.pos.y = dst.y as f32 + dst.height as f32;
-
-                    // color
-                    v0.color = color4b(color.r, color.g, color.b, color.a);
-                    v1.color = v0.color;
-                    v2.color = v0.color;
-                    v3.color = v0.color;
-
-                    r.push_quad_vertices(&v0, &v1, &v2, &v3);
+        // with hardware clipping, only what's entirely outside is dropped
+        let clip = if self.hardware_clip { rect_touches(&dst, &self.clip).then_some(dst) } else { Some(self.clip) };
+        if let Some((dst, src)) = clip.and_then(|clip| Self::clip_rect(dst, src, clip)) {
+            let [mut v0, mut v1, mut v2, mut v3] = Self::quad_vertices(dst, src, atlas_dim, color);
+            if self.pixel_snap.is_some() {
+                for v in [&mut v0, &mut v1, &mut v2, &mut v3] {
+                    v.pos = Self::snap(self.pixel_snap, v.pos);
                 }
-                None => (),
+            }
+            self.vertex_count += 4;
+            self.renderer.scope_mut(move |r| r.push_quad_vertices(&v0, &v1, &v2, &v3))
+        }
+    }
+
+    // Sutherland-Hodgman clipping of a convex polygon against an axis aligned rectangle
+    fn clip_polygon(points: &[Vec2f], clip: Recti) -> Vec<Vec2f> {
+        let x0 = clip.x as f32;
+        let y0 = clip.y as f32;
+        let x1 = clip.x as f32 + clip.width as f32;
+        let y1 = clip.y as f32 + clip.height as f32;
+
+        // each edge is given by its signed distance function, positive inside
+        let edges = [(1.0, 0.0, -x0), (-1.0, 0.0, x1), (0.0, 1.0, -y0), (0.0, -1.0, y1)];
+
+        let mut output = points.to_vec();
+        for (ex, ey, ec) in edges {
+            let dist = |p: &Vec2f| p.x * ex + p.y * ey + ec;
+            let input = std::mem::take(&mut output);
+            for i in 0..input.len() {
+                let curr = input[i];
+                let prev = input[(i + input.len() - 1) % input.len()];
+                let (dp, dc) = (dist(&prev), dist(&curr));
+                if (dp >= 0.0) != (dc >= 0.0) {
+                    let t = dp / (dp - dc);
+                    output.push(Vec2f::new(prev.x + (curr.x - prev.x) * t, prev.y + (curr.y - prev.y) * t));
+                }
+                if dc >= 0.0 {
+                    output.push(curr);
+                }
+            }
+        }
+        output
+    }
+
+    #[inline(never)]
+    pub fn draw_triangle(&mut self, p0: Vec2f, p1: Vec2f, p2: Vec2f, color: Color) {
+        let color = self.vertex_color(color);
+        let atlas = self.renderer.scope(|r| r.get_atlas());
+        let atlas_dim = atlas.get_texture_dimension();
+        let white = atlas.get_icon_rect(WHITE_ICON);
+        let tex = Vec2f::new(
+            (white.x as f32 + white.width as f32 / 2.0) / atlas_dim.width as f32,
+            (white.y as f32 + white.height as f32 / 2.0) / atlas_dim.height as f32,
+        );
+
+        let polygon = if self.hardware_clip { vec![p0, p1, p2] } else { Self::clip_polygon(&[p0, p1, p2], self.clip) };
+        if polygon.len() < 3 {
+            return;
+        }
+
+        let pixel_snap = self.pixel_snap;
+        let vertex = |pos: Vec2f| Vertex {
+            pos: Self::snap(pixel_snap, pos),
+            tex,
+            color: color4b(color.r, color.g, color.b, color.a),
+        };
+
+        // the clipped polygon is convex, emit it as a fan of degenerate quads
+        self.vertex_count += (polygon.len() - 2) * 4;
+        self.renderer.scope_mut(|r| {
+            let v0 = vertex(polygon[0]);
+            for i in 1..polygon.len() - 1 {
+                let v1 = vertex(polygon[i]);
+                let v2 = vertex(polygon[i + 1]);
+                r.push_quad_vertices(&v0, &v1, &v2, &v2);
             }
         })
     }
@@ -157,13 +266,44 @@ impl<R: Renderer> Canvas<R> {
         self.push_rect(rect, icon_rect, color);
     }
 
+    // The glyphs of the string are emitted as one run of quads, clipping is only done per glyph when the
+    // string crosses the clip rect
     #[inline(never)]
     pub fn draw_chars(&mut self, font: FontId, text: &str, pos: Vec2i, color: Color) {
+        let color = self.vertex_color(color);
         let atlas = self.renderer.scope(|r| r.get_atlas());
-        atlas.draw_string(font, text, |_, _, dst, src| {
+        let atlas_dim = atlas.get_texture_dimension();
+        let clip = self.clip;
+        let size = atlas.get_text_size(font, text);
+        let inside = pos.x >= clip.x
+            && pos.y >= clip.y
+            && pos.x + size.width <= clip.x + clip.width
+            && pos.y + size.height <= clip.y + clip.height;
+
+        let has_colored = atlas.has_color_glyphs(font);
+        let white = Color { r: 255, g: 255, b: 255, a: color.a };
+        let pixel_snap = self.pixel_snap;
+
+        let mut vertices = Vec::with_capacity(text.len() * 4);
+        atlas.draw_string(font, text, |chr, _, dst, src| {
             let dst = Rect::new(pos.x + dst.x, pos.y + dst.y, dst.width, dst.height);
-            self.push_rect(dst, src, color)
+            let quad = if inside { Some((dst, src)) } else { Self::clip_rect(dst, src, clip) };
+            if let Some((dst, src)) = quad {
+                // color glyphs use a white vertex color so the texture colors are kept
+                let colored = has_colored && atlas.get_char_entry(font, chr).map(|e| e.colored).unwrap_or(false);
+                let mut quad = Self::quad_vertices(dst, src, atlas_dim, if colored { white } else { color });
+                if pixel_snap.is_some() {
+                    for v in quad.iter_mut() {
+                        v.pos = Self::snap(pixel_snap, v.pos);
+                    }
+                }
+                vertices.extend_from_slice(&quad);
+            }
         });
+        if !vertices.is_empty() {
+            self.vertex_count += vertices.len();
+            self.renderer.scope_mut(|r| r.push_quads(&vertices));
+        }
     }
 
     pub fn draw_icon(&mut self, id: IconId, r: Recti, color: Color) {
@@ -174,10 +314,14 @@ impl<R: Renderer> Canvas<R> {
     }
 
     pub fn draw_slot(&mut self, id: SlotId, r: Recti, color: Color) {
-        let src = self.renderer.scope(|r| r.get_atlas()).get_slot_rect(id);
+        let atlas = self.renderer.scope(|r| r.get_atlas());
+        let src = atlas.get_slot_rect(id);
         let x = r.x + (r.width - src.width) / 2;
         let y = r.y + (r.height - src.height) / 2;
+        let prev = self.sampler;
+        self.set_sampler(atlas.get_slot_sampler(id));
         self.push_rect(rect(x, y, src.width, src.height), src, color);
+        self.set_sampler(prev);
     }
 
     pub fn draw_slot_with_function(
@@ -187,24 +331,115 @@ impl<R: Renderer> Canvas<R> {
         color: Color,
         payload: Rc<dyn Fn(usize, usize) -> Color4b>,
     ) {
-        let src = self.renderer.scope(|r| r.get_atlas()).get_slot_rect(id);
+        let atlas = self.renderer.scope(|r| r.get_atlas());
+        let src = atlas.get_slot_rect(id);
         let pl = payload.clone();
         self.renderer
             .scope_mut(move |r| r.get_atlas().borrow_mut().render_slot(id, pl.clone()));
         let x = r.x + (r.width - src.width) / 2;
         let y = r.y + (r.height - src.height) / 2;
+        let prev = self.sampler;
+        self.set_sampler(atlas.get_slot_sampler(id));
         self.push_rect(rect(x, y, src.width, src.height), src, color);
+        self.set_sampler(prev);
+    }
+
+    // the renderer converts the frame itself when it can, otherwise the atlas pixels are rewritten
+    pub fn upload_yuv420(&mut self, slot: SlotId, frame: &Yuv420Frame) {
+        let mut atlas = self.get_atlas();
+        let dst = atlas.get_slot_rect(slot);
+        if self.renderer.scope_mut(|r| r.upload_yuv420(dst, frame)) {
+            return;
+        }
+        let pixels = frame.to_rgba(dst.width as usize, dst.height as usize);
+        let width = dst.width as usize;
+        atlas.render_slot(slot, Rc::new(move |x, y| pixels[(x - dst.x as usize) + (y - dst.y as usize) * width]));
     }
 
     pub fn set_clip_rect(&mut self, rect: Recti) {
+        if self.hardware_clip && !(self.clip.x == rect.x && self.clip.y == rect.y && self.clip.width == rect.width && self.clip.height == rect.height) {
+            let scissor = rect_intersect(&rect, &Recti::new(0, 0, self.current_dim.width, self.current_dim.height));
+            self.renderer.scope_mut(move |r| {
+                r.flush();
+                r.set_scissor(Some(scissor))
+            });
+        }
         self.clip = rect;
     }
 
+    // Points the renderer to a custom render widget: the viewport covers its content and the scissor its
+    // visible part
+    pub fn begin_custom_render(&mut self, content_area: Recti, view: Recti) {
+        let view = rect_intersect(&view, &Recti::new(0, 0, self.current_dim.width, self.current_dim.height));
+        self.renderer.scope_mut(move |r| {
+            r.flush();
+            r.set_viewport(content_area);
+            r.set_scissor(Some(view))
+        });
+    }
+
+    pub fn end_custom_render(&mut self) {
+        let full = Recti::new(0, 0, self.current_dim.width, self.current_dim.height);
+        let scissor = if self.hardware_clip { Some(rect_intersect(&self.clip, &full)) } else { None };
+        self.renderer.scope_mut(move |r| {
+            r.set_viewport(full);
+            r.set_scissor(scissor)
+        });
+    }
+
+    // Draws what follows `scale` times larger offscreen, `end_scaled_layer` filters it back down over `area`.
+    // Returns false when the renderer doesn't support it.
+    pub fn begin_scaled_layer(&mut self, area: Recti, scale: Real) -> bool {
+        self.renderer.scope_mut(move |r| {
+            r.flush();
+            r.begin_scaled_layer(area, scale)
+        })
+    }
+
+    pub fn end_scaled_layer(&mut self) {
+        self.renderer.scope_mut(|r| {
+            r.flush();
+            r.end_scaled_layer()
+        })
+    }
+
+    // what was pushed so far is flushed since the mask applies to the whole batch
+    pub fn set_mask(&mut self, mask: Option<ClipMask>) {
+        if self.mask != mask {
+            self.mask = mask;
+            self.renderer.scope_mut(move |r| {
+                r.flush();
+                r.set_mask(mask)
+            });
+        }
+    }
+
+    // like the mask, the sampler applies to the whole batch
+    pub fn set_sampler(&mut self, sampler: Sampler) {
+        if self.sampler != sampler {
+            self.sampler = sampler;
+            self.renderer.scope_mut(move |r| {
+                r.flush();
+                r.set_sampler(sampler)
+            });
+        }
+    }
+
     pub fn begin(&mut self, width: i32, height: i32, clr: Color) {
         self.current_dim = Dimensioni::new(width, height);
-        self.set_clip_rect(Rect::new(0, 0, width, height));
-        self.renderer
-            .scope_mut(move |r| r.begin(width, height, clr));
+        self.clip = Rect::new(0, 0, width, height);
+        self.mask = None;
+        self.sampler = Sampler::default();
+        self.vertex_count = 0;
+        self.hardware_clip = self.renderer.scope(|r| r.hardware_clip());
+        let clr = self.vertex_color(clr);
+        self.renderer.scope_mut(move |r| {
+            r.begin(width, height, clr);
+            r.set_mask(None);
+            r.set_sampler(Sampler::default());
+            r.set_viewport(Rect::new(0, 0, width, height));
+            r.set_scissor(None)
+        });
     }
 
     pub fn end(&mut self) {
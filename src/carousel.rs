@@ -0,0 +1,147 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Clone, Default)]
+pub struct CarouselState {
+    page: usize,
+    offset: Real, // current scroll position in pixels, animated towards page * width
+    pub speed: Real,
+    pub extras: Extras,
+}
+
+impl CarouselState {
+    pub fn new() -> Self {
+        Self {
+            page: 0,
+            offset: 0.0,
+            speed: 0.25,
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    pub fn set_page(&mut self, page: usize) {
+        self.page = page;
+    }
+}
+
+impl Container {
+    // Pages through `count` items, `f` is called with the index of each visible item and lays it out inside
+    // its own page. Returns CHANGE when the current page changed.
+    #[inline(never)]
+    pub fn carousel<F: FnMut(&mut Self, usize)>(&mut self, state: &mut CarouselState, count: usize, mut f: F) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let id = self.idmngr.get_id_from_ptr(state);
+        let r = self.layout.next();
+        if count == 0 {
+            return res;
+        }
+
+        let last_page = state.page;
+        let button_w = min(r.height, 24);
+        let dots_h = 12;
+        let area = rect(r.x + button_w, r.y, r.width - button_w * 2, r.height - dots_h);
+        let page_w = max(area.width, 1) as Real;
+
+        // left/right buttons
+        let left = rect(r.x, area.y, button_w, area.height);
+        let right = rect(r.x + r.width - button_w, area.y, button_w, area.height);
+        self.idmngr.push_id(id);
+        let left_id = self.idmngr.get_id_from_str("!carousel_left");
+        let right_id = self.idmngr.get_id_from_str("!carousel_right");
+        self.idmngr.pop_id();
+        for (bid, br, label) in [(left_id, left, "<"), (right_id, right, ">")] {
+            self.update_control(bid, br, WidgetOption::NONE);
+            self.draw_widget_frame(bid, br, ControlColor::Button, WidgetOption::NONE);
+            self.draw_control_text(label, br, ControlColor::Text, WidgetOption::ALIGN_CENTER);
+        }
+        let pressed = self.input.borrow().mouse_pressed.is_left();
+        if pressed && self.focus == Some(left_id) && state.page > 0 {
+            state.page -= 1;
+        }
+        if pressed && self.focus == Some(right_id) && state.page + 1 < count {
+            state.page += 1;
+        }
+
+        // swipe: follow the mouse while dragging, snap to the nearest page on release
+        self.update_control(id, area, WidgetOption::NONE);
+        let dragging = self.focus == Some(id) && self.input.borrow().mouse_down.is_left();
+        if dragging {
+            state.offset -= self.input.borrow().mouse_delta.x as Real;
+            state.offset = state.offset.clamp(0.0, page_w * (count - 1) as Real);
+            state.page = min((state.offset / page_w).round() as usize, count - 1);
+        } else {
+            state.page = min(state.page, count - 1);
+            let target = state.page as Real * page_w;
+            state.offset += (target - state.offset) * state.speed;
+            if (target - state.offset).abs() < 0.5 {
+                state.offset = target;
+            }
+        }
+
+        // only the pages intersecting the area are laid out
+        self.push_clip_rect(area);
+        let first = (state.offset / page_w).floor().max(0.0) as usize;
+        for i in first..min(first + 2, count) {
+            let x = area.x + (i as Real * page_w - state.offset) as i32;
+            self.layout.begin_region(rect(x, area.y, area.width, area.height));
+            self.idmngr.push_id_u32(i as u32);
+            f(self, i);
+            self.idmngr.pop_id();
+            self.layout.end_region();
+        }
+        self.pop_clip_rect();
+
+        // page dots, clickable
+        let dot = 6;
+        let total_w = count as i32 * dot * 2 - dot;
+        let mut dx = r.x + (r.width - total_w) / 2;
+        let dy = r.y + r.height - dots_h + (dots_h - dot) / 2;
+        let mouse = self.input.borrow().mouse_pos;
+        for i in 0..count {
+            let d = rect(dx, dy, dot, dot);
+            let colorid = if i == state.page { ControlColor::ButtonFocus } else { ControlColor::Button };
+            self.draw_rect(d, self.style.colors[colorid as usize]);
+            if pressed && self.in_hover_root && expand_rect(d, 2).contains(&mouse) {
+                state.page = i;
+            }
+            dx += dot * 2;
+        }
+
+        if state.page != last_page {
+            res |= ResourceState::CHANGE;
+        }
+        res
+    }
+}
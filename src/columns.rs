@@ -0,0 +1,88 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+// Columns of equal width sharing the rest of the container width, each with its own vertical flow
+pub struct Columns<'a> {
+    container: &'a mut Container,
+    area: Recti,
+    width: i32,
+    heights: Vec<i32>,
+}
+
+impl<'a> Columns<'a> {
+    pub fn container(&mut self) -> &mut Container {
+        self.container
+    }
+
+    pub fn count(&self) -> usize {
+        self.heights.len()
+    }
+
+    // Lays out `f` at the bottom of the shortest column, which keeps the column heights balanced
+    pub fn item<F: FnOnce(&mut Container)>(&mut self, f: F) {
+        let (i, _) = self.heights.iter().enumerate().min_by_key(|(_, h)| **h).unwrap();
+        self.column(i, f);
+    }
+
+    // Lays out `f` at the bottom of column `i`
+    pub fn column<F: FnOnce(&mut Container)>(&mut self, i: usize, f: F) {
+        let spacing = self.container.style.spacing;
+        let x = self.area.x + i as i32 * (self.width + spacing);
+        let y = self.area.y + self.heights[i];
+        let height = max(self.container.body.y + self.container.body.height - y, 0);
+        self.container.layout.begin_region(rect(x, y, self.width, height));
+        self.container.layout.row(&[-1], 0);
+        f(self.container);
+        let bottom = self.container.layout.top().max.y;
+        self.container.layout.end_region();
+        if bottom > y {
+            self.heights[i] = bottom - self.area.y + spacing;
+        }
+    }
+}
+
+impl Container {
+    pub fn columns<F: FnOnce(&mut Columns)>(&mut self, n: usize, f: F) {
+        let n = max(n, 1);
+        let spacing = self.style.spacing;
+        self.layout.row(&[-1], 0);
+        let area = self.layout.peek();
+        let width = max((area.width - spacing * (n as i32 - 1)) / n as i32, 0);
+        let mut columns = Columns { container: self, area, width, heights: vec![0; n] };
+        f(&mut columns);
+        // the tallest column takes a single cell of the parent layout
+        let height = columns.heights.iter().copied().max().unwrap_or(0) - spacing;
+        if height > 0 {
+            self.layout.row(&[-1], height);
+            self.layout.next();
+        }
+    }
+}
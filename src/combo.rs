@@ -0,0 +1,228 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Drop down list. The field is a widget of the container, the list is a popup shown by `Context::combo_popup`
+// once per frame after the windows, like the text menu.
+use super::*;
+
+#[derive(Clone)]
+pub enum ComboItem {
+    Header(String), // section title, not selectable
+    Separator,
+    Entry { label: String, icon: Option<IconId>, enabled: bool },
+}
+
+impl ComboItem {
+    pub fn entry(label: &str) -> Self {
+        Self::Entry { label: label.to_string(), icon: None, enabled: true }
+    }
+
+    pub fn header(label: &str) -> Self {
+        Self::Header(label.to_string())
+    }
+
+    pub fn with_icon(self, icon: IconId) -> Self {
+        match self {
+            Self::Entry { label, enabled, .. } => Self::Entry { label, icon: Some(icon), enabled },
+            item => item,
+        }
+    }
+
+    pub fn disabled(self) -> Self {
+        match self {
+            Self::Entry { label, icon, .. } => Self::Entry { label, icon, enabled: false },
+            item => item,
+        }
+    }
+
+    pub fn is_selectable(&self) -> bool {
+        matches!(self, Self::Entry { enabled: true, .. })
+    }
+}
+
+pub struct ComboState {
+    popup: WindowHandle,
+    items: Vec<ComboItem>,
+    selected: Option<usize>,
+    field: Option<Recti>, // where the field was clicked, the list opens under it
+    list_width: i32,
+    changed: bool,
+}
+
+impl ComboState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str, items: Vec<ComboItem>) -> Self {
+        let selected = items.iter().position(|i| i.is_selectable());
+        Self { popup: ctx.new_popup(name), items, selected, field: None, list_width: 0, changed: false }
+    }
+
+    pub fn items(&self) -> &[ComboItem] {
+        &self.items
+    }
+
+    // the selection is kept when it's still a selectable entry
+    pub fn set_items(&mut self, items: Vec<ComboItem>) {
+        self.items = items;
+        if !self.selected.is_some_and(|i| self.items.get(i).is_some_and(|i| i.is_selectable())) {
+            self.selected = self.items.iter().position(|i| i.is_selectable());
+        }
+    }
+
+    // index into the items
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|i| self.items.get(*i).is_some_and(|i| i.is_selectable()));
+    }
+
+    pub fn selected_label(&self) -> Option<&str> {
+        match self.selected.and_then(|i| self.items.get(i)) {
+            Some(ComboItem::Entry { label, .. }) => Some(label.as_str()),
+            _ => None,
+        }
+    }
+
+    fn selected_icon(&self) -> Option<IconId> {
+        match self.selected.and_then(|i| self.items.get(i)) {
+            Some(ComboItem::Entry { icon, .. }) => *icon,
+            _ => None,
+        }
+    }
+}
+
+impl Container {
+    // The field showing the selected entry, clicking it opens the list. CHANGE is reported on the frame after
+    // an entry was picked.
+    pub fn combo(&mut self, state: &mut ComboState) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        if std::mem::take(&mut state.changed) {
+            res |= ResourceState::CHANGE;
+        }
+        let id = self.idmngr.get_id_from_ptr(state);
+        let label = state.selected_label().unwrap_or("").to_string();
+        let icon = state.selected_icon();
+        let icon_size = icon.map(|i| self.atlas.get_icon_size(i)).unwrap_or(Dimensioni::new(0, 0));
+        let arrow = self.atlas.get_icon_size(COLLAPSE_ICON);
+        let mut size = self.content_size(&label, icon_size);
+        size.width += arrow.width + self.style.padding;
+        let r = self.layout.next_sized(size);
+        self.update_control(id, r, WidgetOption::NONE);
+        if self.is_clicked(id) {
+            state.field = Some(r);
+            res |= ResourceState::ACTIVE;
+        }
+        self.draw_widget_frame(id, r, ControlColor::Base, WidgetOption::NONE);
+        self.draw_icon_label(&label, icon, rect(r.x, r.y, r.width - arrow.width - self.style.padding, r.height));
+        let color = self.style.colors[ControlColor::Text as usize];
+        self.draw_icon(COLLAPSE_ICON, rect(r.x + r.width - arrow.width - self.style.padding, r.y, arrow.width, r.height), color);
+        res
+    }
+
+    // the icon, if any, left of the text
+    fn draw_icon_label(&mut self, label: &str, icon: Option<IconId>, r: Recti) {
+        let mut text_r = r;
+        if let Some(icon) = icon {
+            let size = self.atlas.get_icon_size(icon);
+            let color = self.style.colors[ControlColor::Text as usize];
+            self.draw_icon(icon, rect(r.x + self.style.padding, r.y, size.width, r.height), color);
+            text_r.x += size.width + self.style.padding;
+            text_r.width -= size.width + self.style.padding;
+        }
+        self.draw_control_text(label, text_r, ControlColor::Text, WidgetOption::NONE);
+    }
+
+    fn combo_entry(&mut self, label: &str, icon: Option<IconId>, selected: bool) -> bool {
+        let id = self.idmngr.get_id_from_str(label);
+        let r = self.layout.next();
+        self.update_control(id, r, WidgetOption::NONE);
+        let clicked = self.is_clicked(id);
+        if self.hover == Some(id) || self.focus == Some(id) {
+            self.draw_widget_frame(id, r, ControlColor::Button, WidgetOption::NONE);
+        }
+        if selected {
+            let accent = self.style.colors[ControlColor::Accent as usize];
+            self.draw_rect(rect(r.x, r.y, 2, r.height), accent);
+        }
+        self.draw_icon_label(label, icon, r);
+        clicked
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Shows the list of `state` while it's open, call once per frame after the windows
+    pub fn combo_popup(&mut self, state: &mut ComboState) {
+        if let Some(field) = state.field.take() {
+            self.open_popup(&mut state.popup);
+            state.popup.inner_mut().main.rect = rect(field.x, field.y + field.height, field.width, 1);
+            // the popup sizes itself around the rows, they keep the width of the field
+            state.list_width = (field.width - self.style.padding * 2).max(1);
+        }
+
+        let width = state.list_width;
+        let mut picked = None;
+        let items = &state.items;
+        let selected = state.selected;
+        self.popup(&mut state.popup.clone(), |c| {
+            for (i, item) in items.iter().enumerate() {
+                match item {
+                    ComboItem::Header(label) => {
+                        c.set_row_widths_height(&[width], 0);
+                        let r = c.next_cell();
+                        c.draw_control_text(label, r, ControlColor::TitleText, WidgetOption::NONE);
+                    }
+                    ComboItem::Separator => {
+                        c.set_row_widths_height(&[width], 1);
+                        let r = c.next_cell();
+                        let color = c.style.colors[ControlColor::Border as usize];
+                        c.draw_rect(r, color);
+                    }
+                    ComboItem::Entry { label, icon, enabled } => {
+                        c.set_row_widths_height(&[width], 0);
+                        // entries may share a label across sections
+                        c.idmngr.push_id_u32(i as u32);
+                        if c.disabled(!enabled, |c| c.combo_entry(label, *icon, selected == Some(i))) {
+                            picked = Some(i);
+                        }
+                        c.idmngr.pop_id();
+                    }
+                }
+            }
+            if picked.is_some() {
+                WindowState::Closed
+            } else {
+                WindowState::Open
+            }
+        });
+        if let Some(i) = picked {
+            state.changed = state.selected != Some(i);
+            state.selected = Some(i);
+        }
+    }
+}
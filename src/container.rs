@@ -52,6 +52,7 @@
 //
 use super::*;
 use std::cell::RefCell;
+use std::collections::HashMap;
 
 pub struct CustomRenderArgs {
     pub content_area: Rect<i32>,
@@ -71,13 +72,17 @@ pub enum Command {
         font: FontId,
         pos: Vec2i,
         color: Color,
-        text: String,
+        text: ArenaStr, // resolved with `Container::command_text`
     },
     Icon {
         rect: Recti,
         id: IconId,
         color: Color,
     },
+    Triangle {
+        points: [Vec2f; 3],
+        color: Color,
+    },
     Slot {
         rect: Recti,
         id: SlotId,
@@ -89,6 +94,19 @@ pub enum Command {
         color: Color,
         payload: Rc<dyn Fn(usize, usize) -> Color4b>,
     },
+    // a region of the atlas texture stretched over `rect`
+    Atlas {
+        rect: Recti,
+        src: Recti,
+        color: Color,
+    },
+    Mask {
+        mask: Option<ClipMask>,
+    },
+    // sampling of the following atlas regions, slots use their own
+    Sampler {
+        sampler: Sampler,
+    },
     CustomRender(CustomRenderArgs, Box<dyn FnMut(Dimensioni, &CustomRenderArgs)>),
     None,
 }
@@ -99,10 +117,42 @@ impl Default for Command {
     }
 }
 
+// where the expand/collapse icon of a header or tree node goes
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ChevronPosition {
+    Left,
+    Right,
+    Hidden,
+}
+
+#[derive(Copy, Clone)]
+pub struct NodeOptions {
+    pub expand_icon: IconId,
+    pub collapse_icon: IconId,
+    pub chevron: ChevronPosition,
+    pub trailing_width: i32,
+}
+
+impl Default for NodeOptions {
+    fn default() -> Self {
+        Self { expand_icon: EXPAND_ICON, collapse_icon: COLLAPSE_ICON, chevron: ChevronPosition::Left, trailing_width: 0 }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct Tint {
+    pub(crate) color: Color,
+    pub(crate) grayscale: bool,
+}
+
+// alpha applied to the subtree of `Container::disabled`
+pub const DISABLED_TINT: Color = Color { r: 255, g: 255, b: 255, a: 110 };
+
 pub struct Container {
     pub(crate) atlas: AtlasHandle,
     pub style: Style,
     pub name: String,
+    pub(crate) path: UiPath,
     pub rect: Recti,
     pub body: Recti,
     pub content_size: Vec2i,
@@ -117,16 +167,37 @@ pub struct Container {
     pub idmngr: IdManager,
     pub input: Rc<RefCell<Input>>,
     pub in_hover_root: bool,
+    pub(crate) mnemonic_scope: bool, // the window taking the Alt+key mnemonics
     pub number_edit_buf: String,
     pub number_edit: Option<Id>,
-
-    panels: Vec<ContainerHandle>,
+    pub text_edit: Option<Id>,
+    pub text_cursor: usize,
+    pub extras: Extras,
+
+    pub(crate) arena: FrameArena,
+    widget_extras: HashMap<Id, Extras>,
+    pub(crate) widget_rects: HashMap<Id, Recti>,
+    pub(crate) focus_order: Vec<(Id, Recti)>, // visible interactive widgets, in evaluation order
+    pub(crate) flashes: HashMap<Id, Real>,      // seconds left
+    pub(crate) flash_rects: Vec<(Recti, Recti, Real)>, // rect, clip, fade
+    pub(crate) panels: Vec<ContainerHandle>,
+    pub(crate) tint_stack: Vec<Tint>,
+    pub(crate) mask_stack: Vec<ClipMask>,
+    pub(crate) clicks: Vec<(Id, String)>, // clicked widgets and their label, for the telemetry
+    sampler: Sampler, // the one set by the last Sampler command
+    pub(crate) activated: Option<Id>,
+    pub(crate) activate_next: Option<Id>,
+    pub(crate) disabled: usize,
+    pub(crate) background: Option<BackgroundImage>,
+    marks: Vec<(String, Recti)>,
+    pub(crate) retained: RetainedTrees,
 }
 
 impl Container {
     pub(crate) fn new(name: &str, atlas: AtlasHandle, style: &Style, input: Rc<RefCell<Input>>) -> Self {
         Self {
             name: name.to_string(),
+            path: UiPath::new(name),
             style: style.clone(),
             atlas: atlas,
             rect: Recti::default(),
@@ -143,10 +214,30 @@ impl Container {
             idmngr: IdManager::new(),
             number_edit_buf: String::default(),
             number_edit: None,
+            text_edit: None,
+            text_cursor: 0,
             in_hover_root: false,
+            mnemonic_scope: false,
             input: input,
-
+            extras: Extras::default(),
+
+            arena: FrameArena::default(),
+            widget_extras: HashMap::new(),
+            widget_rects: HashMap::new(),
+            focus_order: Vec::new(),
+            flashes: HashMap::new(),
+            flash_rects: Vec::new(),
             panels: Default::default(),
+            tint_stack: Vec::new(),
+            mask_stack: Vec::new(),
+            clicks: Vec::new(),
+            sampler: Sampler::default(),
+            activated: None,
+            activate_next: None,
+            disabled: 0,
+            background: None,
+            marks: Vec::new(),
+            retained: RetainedTrees::new(),
         }
     }
 
@@ -157,50 +248,165 @@ impl Container {
         self.in_hover_root = false;
     }
 
+    // data attached to a widget id, kept across frames until removed
+    pub fn widget_extras(&mut self, id: Id) -> &mut Extras {
+        self.widget_extras.entry(id).or_default()
+    }
+
+    pub fn get_widget_extras(&self, id: Id) -> Option<&Extras> {
+        self.widget_extras.get(&id)
+    }
+
+    pub fn remove_widget_extras(&mut self, id: Id) -> Option<Extras> {
+        self.widget_extras.remove(&id)
+    }
+
+    // rect of a widget laid out in this container (or one of its panels) during the current frame
+    pub fn widget_rect(&self, id: Id) -> Option<Recti> {
+        match self.widget_rects.get(&id) {
+            Some(r) => Some(*r),
+            None => self.panels.iter().find_map(|p| p.inner().widget_rect(id)),
+        }
+    }
+
+    // Names the rect of the last laid out widget for this frame, so tools can find it afterwards (e.g. to
+    // crop a screenshot)
+    pub fn mark_region(&mut self, name: &str) {
+        let r = self.layout.last_rect;
+        self.mark_region_rect(name, r);
+    }
+
+    pub fn mark_region_rect(&mut self, name: &str, r: Recti) {
+        self.marks.push((name.to_string(), r));
+    }
+
+    // the regions marked in this container, followed by its panels
+    pub fn marked_regions(&self) -> Vec<(String, Recti)> {
+        let mut res = self.marks.clone();
+        for panel in &self.panels {
+            res.extend(panel.inner().marked_regions());
+        }
+        res
+    }
+
+    // drops what a measuring pass produced, the content size is kept
+    pub(crate) fn discard_frame(&mut self) {
+        self.command_list.clear();
+        self.arena.reset();
+        self.panels.clear();
+        self.widget_rects.clear();
+        self.focus_order.clear();
+        self.marks.clear();
+        self.flash_rects.clear();
+        self.tint_stack.clear();
+        self.mask_stack.clear();
+        self.clicks.clear();
+        self.sampler = Sampler::default();
+        self.disabled = 0;
+    }
+
     pub(crate) fn prepare(&mut self) {
+        self.layout.begin_frame();
         self.command_list.clear();
+        self.arena.reset();
+        self.widget_rects.clear();
+        self.focus_order.clear();
+        self.marks.clear();
         assert!(self.clip_stack.len() == 0);
         self.panels.clear();
+        self.tint_stack.clear();
+        self.mask_stack.clear();
+        self.clicks.clear();
+        self.sampler = Sampler::default();
+        self.disabled = 0;
+        self.activated = self.activate_next.take();
     }
 
     #[inline(never)]
     pub(crate) fn render<R: Renderer>(&mut self, canvas: &mut Canvas<R>) {
-        for command in self.command_list.drain(0..) {
+        // commands are kept until the next frame so they can be inspected after rendering
+        for command in self.command_list.iter_mut() {
             match command {
                 Command::Text { text, pos, color, font } => {
-                    canvas.draw_chars(font, &text, pos, color);
+                    canvas.draw_chars(*font, self.arena.get(*text), *pos, *color);
                 }
                 Command::Recti { rect, color } => {
-                    canvas.draw_rect(rect, color);
+                    canvas.draw_rect(*rect, *color);
                 }
                 Command::Icon { id, rect, color } => {
-                    canvas.draw_icon(id, rect, color);
+                    canvas.draw_icon(*id, *rect, *color);
+                }
+                Command::Triangle { points, color } => {
+                    canvas.draw_triangle(points[0], points[1], points[2], *color);
                 }
                 Command::Clip { rect } => {
-                    canvas.set_clip_rect(rect);
+                    canvas.set_clip_rect(*rect);
+                }
+                Command::Mask { mask } => {
+                    canvas.set_mask(*mask);
+                }
+                Command::Sampler { sampler } => {
+                    canvas.set_sampler(*sampler);
                 }
                 Command::Slot { rect, id, color } => {
-                    canvas.draw_slot(id, rect, color);
+                    canvas.draw_slot(*id, *rect, *color);
                 }
                 Command::SlotRedraw { rect, id, color, payload } => {
-                    canvas.draw_slot_with_function(id, rect, color, payload.clone());
+                    canvas.draw_slot_with_function(*id, *rect, *color, payload.clone());
+                }
+                Command::Atlas { rect, src, color } => {
+                    canvas.push_rect(*rect, *src, *color);
                 }
-                Command::CustomRender(cra, mut f) => {
+                Command::CustomRender(cra, f) => {
                     canvas.end();
-                    (*f)(canvas.current_dimension(), &cra);
+                    canvas.begin_custom_render(cra.content_area, cra.view);
+                    (*f)(canvas.current_dimension(), cra);
+                    canvas.end_custom_render();
                 }
                 Command::None => (),
             }
         }
+        canvas.set_mask(None);
 
         for ap in &mut self.panels {
             ap.render(canvas)
         }
     }
 
+    // the string of a `Command::Text`, stored in the frame arena of this container
+    pub fn command_text(&self, text: ArenaStr) -> &str {
+        self.arena.get(text)
+    }
+
+    pub fn arena(&self) -> &FrameArena {
+        &self.arena
+    }
+
+    pub(crate) fn collect_stats(&self, stats: &mut FrameStats) {
+        stats.containers += 1;
+        stats.commands += self.command_list.len();
+        stats.arena_used += self.arena.used();
+        stats.arena_peak += self.arena.peak();
+        stats.arena_capacity += self.arena.capacity();
+        stats.state_entries += self.widget_extras.len();
+        for panel in &self.panels {
+            panel.inner().collect_stats(stats);
+        }
+    }
+
+    // the recorded commands of this container, followed by its panels, in render order
+    pub fn visit_commands<F: FnMut(&Container, &Command)>(&self, f: &mut F) {
+        for command in &self.command_list {
+            f(self, command);
+        }
+        for panel in &self.panels {
+            panel.inner().visit_commands(f);
+        }
+    }
+
     pub fn push_clip_rect(&mut self, rect: Recti) {
         let last = self.get_clip_rect();
-        self.clip_stack.push(rect.intersect(&last).unwrap_or_default());
+        self.clip_stack.push(rect_intersect(&rect, &last));
     }
 
     pub fn pop_clip_rect(&mut self) {
@@ -216,30 +422,149 @@ impl Container {
 
     pub fn check_clip(&mut self, r: Recti) -> Clip {
         let cr = self.get_clip_rect();
-        if r.x > cr.x + cr.width || r.x + r.width < cr.x || r.y > cr.y + cr.height || r.y + r.height < cr.y {
-            return Clip::All;
-        }
-        if r.x >= cr.x && r.x + r.width <= cr.x + cr.width && r.y >= cr.y && r.y + r.height <= cr.y + cr.height {
-            return Clip::None;
+        rect_clip_state(&r, &cr)
+    }
+
+    pub fn is_rect_visible(&mut self, r: Recti) -> bool {
+        !matches!(self.check_clip(r), Clip::All)
+    }
+
+    pub fn peek_next_cell(&mut self) -> Recti {
+        self.layout.peek()
+    }
+
+    // Evaluates the widget `f` only if its cell is visible, otherwise the cell is consumed and nothing is
+    // done. Useful for long scrolled forms, a culled widget loses the focus like a closed one.
+    pub fn cull_widget<F: FnOnce(&mut Self) -> ResourceState>(&mut self, f: F) -> ResourceState {
+        let r = self.layout.peek();
+        if self.is_rect_visible(r) {
+            f(self)
+        } else {
+            self.layout.next();
+            ResourceState::NONE
         }
-        return Clip::Part;
     }
 
-    pub fn push_command(&mut self, cmd: Command) {
+    pub fn push_command(&mut self, mut cmd: Command) {
+        if let Some(tint) = self.tint_stack.last() {
+            match &mut cmd {
+                Command::Recti { color, .. }
+                | Command::Text { color, .. }
+                | Command::Icon { color, .. }
+                | Command::Triangle { color, .. }
+                | Command::Slot { color, .. }
+                | Command::SlotRedraw { color, .. }
+                | Command::Atlas { color, .. } => {
+                    if tint.grayscale {
+                        *color = color.grayscale();
+                    }
+                    *color = color.modulate(tint.color);
+                }
+                _ => (),
+            }
+        }
         self.command_list.push(cmd);
     }
 
+    // Multiplies the colors of everything drawn until the matching `pop_tint`, nested tints combine.
+    // Pushing a white tint with a lower alpha fades a whole subtree.
+    pub fn push_tint(&mut self, color: Color) {
+        self.push_tint_ex(color, false)
+    }
+
+    // same as `push_tint` but the colors are turned to gray first
+    pub fn push_grayscale_tint(&mut self, color: Color) {
+        self.push_tint_ex(color, true)
+    }
+
+    fn push_tint_ex(&mut self, color: Color, grayscale: bool) {
+        let tint = match self.tint_stack.last() {
+            Some(top) => Tint { color: top.color.modulate(color), grayscale: grayscale || top.grayscale },
+            None => Tint { color, grayscale },
+        };
+        self.tint_stack.push(tint);
+    }
+
+    pub fn pop_tint(&mut self) {
+        self.tint_stack.pop().expect("pop_tint without push_tint");
+    }
+
+    pub fn current_tint(&self) -> Color {
+        self.tint_stack.last().map(|t| t.color).unwrap_or(color(255, 255, 255, 255))
+    }
+
+    // Evaluates `f` with its widgets drawn in gray at reduced alpha and ignoring the input when `disabled`.
+    pub fn disabled<R, F: FnOnce(&mut Self) -> R>(&mut self, disabled: bool, f: F) -> R {
+        if !disabled {
+            return f(self);
+        }
+        self.disabled += 1;
+        self.push_grayscale_tint(DISABLED_TINT);
+        let res = f(self);
+        self.pop_tint();
+        self.disabled -= 1;
+        res
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled > 0
+    }
+
+    // A group of widgets enabled together, e.g. the fields of a form section behind a checkbox. Groups nest,
+    // a disabled outer group disables everything inside.
+    pub fn group_enabled<R, F: FnOnce(&mut Self) -> R>(&mut self, enabled: bool, f: F) -> R {
+        self.disabled(!enabled, f)
+    }
+
+    // A group of widgets shown together: when hidden `f` isn't evaluated at all, so the group takes no room in
+    // the layout and its widgets keep no state for the frame.
+    pub fn group_visible<R, F: FnOnce(&mut Self) -> R>(&mut self, visible: bool, f: F) -> Option<R> {
+        if visible {
+            Some(f(self))
+        } else {
+            None
+        }
+    }
+
+    // Everything drawn until the matching `pop_mask` is clipped to the rounded rect, used for circular
+    // avatars and rounded frames. Masks don't nest, the innermost one wins.
+    pub fn push_mask(&mut self, rect: Recti, radius: i32) {
+        self.push_clip_rect(rect);
+        let mask = ClipMask { rect, radius };
+        self.mask_stack.push(mask);
+        self.push_command(Command::Mask { mask: Some(mask) });
+    }
+
+    pub fn pop_mask(&mut self) {
+        self.mask_stack.pop().expect("pop_mask without push_mask");
+        let mask = self.mask_stack.last().copied();
+        self.push_command(Command::Mask { mask });
+        self.pop_clip_rect();
+    }
+
+    pub fn masked<R, F: FnOnce(&mut Self) -> R>(&mut self, rect: Recti, radius: i32, f: F) -> R {
+        self.push_mask(rect, radius);
+        let res = f(self);
+        self.pop_mask();
+        res
+    }
+
     pub fn set_clip(&mut self, rect: Recti) {
         self.push_command(Command::Clip { rect });
     }
 
     pub fn set_focus(&mut self, id: Option<Id>) {
+        if let Some(id) = id {
+            if self.focus != Some(id) {
+                self.emit_event(id, WidgetEventKind::Focused, EventValue::None);
+            }
+        }
         self.focus = id;
         self.updated_focus = true;
     }
 
     pub fn draw_rect(&mut self, mut rect: Recti, color: Color) {
-        rect = rect.intersect(&self.get_clip_rect()).unwrap_or_default();
+        rect = rect_intersect(&rect, &self.get_clip_rect());
         if rect.width > 0 && rect.height > 0 {
             self.push_command(Command::Recti { rect, color });
         }
@@ -265,8 +590,9 @@ impl Container {
             _ => (),
         }
 
+        let text = self.arena.alloc_str(str);
         self.push_command(Command::Text {
-            text: String::from(str),
+            text,
             pos,
             color,
             font,
@@ -276,6 +602,33 @@ impl Container {
         }
     }
 
+    // Filtering of the atlas regions drawn next, e.g. `Sampler::LINEAR` for scaled down thumbnails. Set it
+    // back to the previous `sampler()` once done.
+    pub fn set_sampler(&mut self, sampler: Sampler) {
+        self.sampler = sampler;
+        self.push_command(Command::Sampler { sampler });
+    }
+
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    pub fn draw_atlas_region(&mut self, src: Recti, rect: Recti, color: Color) {
+        let clipped = self.check_clip(rect);
+        match clipped {
+            Clip::All => return,
+            Clip::Part => {
+                let clip = self.get_clip_rect();
+                self.set_clip(clip)
+            }
+            _ => (),
+        }
+        self.push_command(Command::Atlas { rect, src, color });
+        if clipped != Clip::None {
+            self.set_clip(UNCLIPPED_RECT);
+        }
+    }
+
     pub fn draw_icon(&mut self, id: IconId, rect: Recti, color: Color) {
         let clipped = self.check_clip(rect);
         match clipped {
@@ -292,6 +645,67 @@ impl Container {
         }
     }
 
+    pub fn draw_triangle(&mut self, p0: Vec2f, p1: Vec2f, p2: Vec2f, color: Color) {
+        let min_x = p0.x.min(p1.x).min(p2.x).floor() as i32;
+        let min_y = p0.y.min(p1.y).min(p2.y).floor() as i32;
+        let max_x = p0.x.max(p1.x).max(p2.x).ceil() as i32;
+        let max_y = p0.y.max(p1.y).max(p2.y).ceil() as i32;
+        let clipped = self.check_clip(rect(min_x, min_y, max_x - min_x, max_y - min_y));
+        match clipped {
+            Clip::All => return,
+            Clip::Part => {
+                let clip = self.get_clip_rect();
+                self.set_clip(clip)
+            }
+            _ => (),
+        }
+        self.push_command(Command::Triangle { points: [p0, p1, p2], color });
+        if clipped != Clip::None {
+            self.set_clip(UNCLIPPED_RECT);
+        }
+    }
+
+    pub fn draw_line(&mut self, p0: Vec2f, p1: Vec2f, thickness: f32, color: Color) {
+        let dx = p1.x - p0.x;
+        let dy = p1.y - p0.y;
+        let len = (dx * dx + dy * dy).sqrt();
+        if len == 0.0 {
+            return;
+        }
+        let nx = -dy / len * thickness / 2.0;
+        let ny = dx / len * thickness / 2.0;
+        let a = Vec2f::new(p0.x + nx, p0.y + ny);
+        let b = Vec2f::new(p1.x + nx, p1.y + ny);
+        let c = Vec2f::new(p1.x - nx, p1.y - ny);
+        let d = Vec2f::new(p0.x - nx, p0.y - ny);
+        self.draw_triangle(a, b, c, color);
+        self.draw_triangle(a, c, d, color);
+    }
+
+    // angles are in radians, clockwise starting from the positive x axis (y axis points down)
+    pub fn draw_arc(&mut self, center: Vec2f, radius: f32, thickness: f32, start_angle: f32, end_angle: f32, color: Color) {
+        let sweep = end_angle - start_angle;
+        let segments = ((sweep.abs() * radius / 4.0).ceil() as usize).clamp(1, 128);
+        let inner = (radius - thickness).max(0.0);
+        let point = |r: f32, a: f32| Vec2f::new(center.x + r * a.cos(), center.y + r * a.sin());
+        for i in 0..segments {
+            let a0 = start_angle + sweep * i as f32 / segments as f32;
+            let a1 = start_angle + sweep * (i + 1) as f32 / segments as f32;
+            let o0 = point(radius, a0);
+            let o1 = point(radius, a1);
+            let i0 = point(inner, a0);
+            let i1 = point(inner, a1);
+            self.draw_triangle(o0, o1, i1, color);
+            if inner > 0.0 {
+                self.draw_triangle(o0, i1, i0, color);
+            }
+        }
+    }
+
+    pub fn draw_circle(&mut self, center: Vec2f, radius: f32, color: Color) {
+        self.draw_arc(center, radius, radius, 0.0, 2.0 * f32::consts::PI, color);
+    }
+
     pub fn draw_slot(&mut self, id: SlotId, rect: Recti, color: Color) {
         let clipped = self.check_clip(rect);
         match clipped {
@@ -328,7 +742,7 @@ impl Container {
     pub fn text(&mut self, text: &str) {
         let font = self.style.font;
         let color = self.style.colors[ControlColor::Text as usize];
-        let h = self.atlas.get_font_height(font) as i32;
+        let h = max(self.atlas.get_font_height(font) as i32 + self.style.line_spacing, 1);
         self.layout.begin_column();
         self.layout.row(&[-1], h);
 
@@ -386,25 +800,122 @@ impl Container {
         self.draw_frame(rect, colorid);
     }
 
+    // Digits all take the width of the widest one so the columns of numbers line up with proportional fonts
+    fn tabular_digit_width(&self, font: FontId) -> i32 {
+        ('0'..='9').map(|d| self.atlas.get_text_size(font, d.encode_utf8(&mut [0; 4])).width).max().unwrap_or(0)
+    }
+
+    fn tabular_width(&self, font: FontId, digit_w: i32, text: &str) -> i32 {
+        text.chars()
+            .map(|c| if c.is_ascii_digit() { digit_w } else { self.atlas.get_text_size(font, c.encode_utf8(&mut [0; 4])).width })
+            .sum()
+    }
+
+    // the characters that keep their own advance are drawn in runs, only the narrower digits are centered one by one
+    fn draw_tabular_text(&mut self, font: FontId, digit_w: i32, text: &str, pos: Vec2i, color: Color) {
+        let mut x = pos.x;
+        let mut run_start = 0;
+        let mut run_w = 0;
+        for (i, c) in text.char_indices() {
+            let s = &text[i..i + c.len_utf8()];
+            let w = self.atlas.get_text_size(font, s).width;
+            if !c.is_ascii_digit() || w == digit_w {
+                run_w += w;
+                continue;
+            }
+            if run_start < i {
+                self.draw_text(font, &text[run_start..i], vec2(x, pos.y), color);
+            }
+            x += run_w;
+            self.draw_text(font, s, vec2(x + (digit_w - w) / 2, pos.y), color);
+            x += digit_w;
+            run_start = i + c.len_utf8();
+            run_w = 0;
+        }
+        if run_start < text.len() {
+            self.draw_text(font, &text[run_start..], vec2(x, pos.y), color);
+        }
+    }
+
+    // Right aligned numbers with tabular digits. With ALIGN_DECIMAL the point sits at the same place in
+    // every cell, leaving room for `style.decimal_digits` digits after it.
+    fn draw_numeric_text(&mut self, text: &str, rect: Recti, color: Color, opt: WidgetOption) {
+        let font = self.style.font;
+        let padding = self.style.padding;
+        let h = self.atlas.get_font_height(font) as i32;
+        let y = rect.y + (rect.height - h) / 2;
+        let right = rect.x + rect.width - padding;
+        let digit_w = self.tabular_digit_width(font);
+        let x = if opt.is_aligned_decimal() {
+            let point = text.find(['.', ',']).unwrap_or(text.len());
+            let frac_w = self.tabular_width(font, digit_w, &text[point..]);
+            let reserved = self.tabular_width(font, digit_w, ".") + digit_w * self.style.decimal_digits as i32;
+            // a longer fraction pushes the point left, it can't stay aligned then
+            right - reserved.max(frac_w) - self.tabular_width(font, digit_w, &text[..point])
+        } else {
+            right - self.tabular_width(font, digit_w, text)
+        };
+        self.draw_tabular_text(font, digit_w, text, vec2(x, y), color);
+    }
+
     #[inline(never)]
     pub fn draw_control_text(&mut self, str: &str, rect: Recti, colorid: ControlColor, opt: WidgetOption) {
-        let mut pos: Vec2i = Vec2i { x: 0, y: 0 };
+        if opt.is_aligned_numeric() || opt.is_aligned_decimal() {
+            let color = self.style.colors[colorid as usize];
+            self.push_clip_rect(rect);
+            self.draw_numeric_text(str, rect, color, opt);
+            self.pop_clip_rect();
+            return;
+        }
         let font = self.style.font;
-        let tsize = self.atlas.get_text_size(font, str);
-        let padding = self.style.padding;
         let color = self.style.colors[colorid as usize];
 
         self.push_clip_rect(rect);
-        pos.y = rect.y + (rect.height - tsize.height) / 2;
+        let pos = self.control_text_pos(str, rect, opt);
+        match self.style.text_effect {
+            TextEffect::None => (),
+            TextEffect::Shadow { offset, color } => self.draw_text(font, str, pos + offset, color),
+            TextEffect::Outline(color) => {
+                for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+                    self.draw_text(font, str, vec2(pos.x + dx, pos.y + dy), color);
+                }
+            }
+        }
+        self.draw_text(font, str, pos, color);
+        self.pop_clip_rect();
+    }
+
+    // where `draw_control_text` places `str` in `rect`
+    pub(crate) fn control_text_pos(&self, str: &str, rect: Recti, opt: WidgetOption) -> Vec2i {
+        let tsize = self.atlas.get_text_size(self.style.font, str);
+        let padding = self.style.padding;
+        let y = rect.y + (rect.height - tsize.height) / 2;
         if opt.is_aligned_center() {
-            pos.x = rect.x + (rect.width - tsize.width) / 2;
+            vec2(rect.x + (rect.width - tsize.width) / 2, y)
         } else if opt.is_aligned_right() {
-            pos.x = rect.x + rect.width - tsize.width - padding;
+            vec2(rect.x + rect.width - tsize.width - padding, y)
         } else {
-            pos.x = rect.x + padding;
+            vec2(rect.x + padding, y)
         }
-        self.draw_text(font, str, pos, color);
-        self.pop_clip_rect();
+    }
+
+    // small framed label next to the mouse cursor, drawn unclipped on top of what was drawn so far
+    pub fn tooltip(&mut self, text: &str) {
+        let mouse = self.input.borrow().mouse_pos;
+        let font = self.style.font;
+        let padding = self.style.padding;
+        let tsize = self.atlas.get_text_size(font, text);
+        let r = rect(mouse.x + 12, mouse.y + 12, tsize.width + padding * 2, tsize.height + padding * 2);
+        self.clip_stack.push(UNCLIPPED_RECT);
+        self.draw_frame(r, ControlColor::WindowBG);
+        let color = self.style.colors[ControlColor::Text as usize];
+        self.draw_text(font, text, vec2(r.x + padding, r.y + padding), color);
+        self.clip_stack.pop();
+    }
+
+    // seconds elapsed since the previous frame, drives the animations
+    pub fn frame_dt(&self) -> Real {
+        self.input.borrow().frame_dt
     }
 
     pub fn mouse_over(&mut self, rect: Recti, in_hover_root: bool) -> bool {
@@ -414,15 +925,32 @@ impl Container {
 
     #[inline(never)]
     pub fn update_control(&mut self, id: Id, rect: Recti, opt: WidgetOption) {
+        self.widget_rects.insert(id, rect);
+        self.update_flash(id, rect);
         let in_hover_root = self.in_hover_root;
         let mouseover = self.mouse_over(rect, in_hover_root);
         if self.focus == Some(id) {
             // is this the same ID of the focused widget? by default set it to true unless otherwise
             self.updated_focus = true;
         }
+        if self.disabled > 0 {
+            if self.focus == Some(id) {
+                self.set_focus(None);
+            }
+            if self.hover == Some(id) {
+                self.hover = None;
+            }
+            return;
+        }
         if opt.is_not_interactive() {
             return;
         }
+        if opt.wants_gestures() {
+            self.claim_gesture(id, rect);
+        }
+        if !rect_is_empty(&rect_intersect(&rect, &self.get_clip_rect())) {
+            self.focus_order.push((id, rect));
+        }
         if mouseover && self.input.borrow().mouse_down.is_none() {
             self.hover = Some(id);
         }
@@ -443,6 +971,41 @@ impl Container {
         }
     }
 
+    // pressed this frame, or activated from the code
+    pub fn is_clicked(&self, id: Id) -> bool {
+        (self.input.borrow().mouse_pressed.is_left() && self.focus == Some(id)) || (self.activated == Some(id) && self.disabled == 0)
+    }
+
+    // The widget behaves as if it was clicked when it's evaluated during the next frame
+    pub fn activate(&mut self, id: Id) {
+        self.activate_next = Some(id);
+    }
+
+    // gives the focus to a widget of this container or of one of its panels, laid out during the last frame
+    pub fn focus_widget(&mut self, id: Id) -> bool {
+        if self.widget_rects.contains_key(&id) {
+            self.set_focus(Some(id));
+            return true;
+        }
+        self.panels.iter_mut().any(|p| p.inner_mut().focus_widget(id))
+    }
+
+    pub fn activate_widget(&mut self, id: Id) -> bool {
+        if self.widget_rects.contains_key(&id) {
+            self.activate(id);
+            return true;
+        }
+        self.panels.iter_mut().any(|p| p.inner_mut().activate_widget(id))
+    }
+
+    // drops the focus of this container and of its panels
+    pub fn blur(&mut self) {
+        self.set_focus(None);
+        for p in &mut self.panels {
+            p.inner_mut().blur();
+        }
+    }
+
     pub fn finish(&mut self) {
         if !self.updated_focus {
             self.focus = None;
@@ -451,33 +1014,132 @@ impl Container {
     }
 
     #[inline(never)]
-    fn node(&mut self, label: &str, is_treenode: bool, state: NodeState) -> NodeState {
+    fn node(&mut self, label: &str, is_treenode: bool, state: NodeState, opts: &NodeOptions) -> (NodeState, Recti) {
         let id: Id = self.idmngr.get_id_from_str(label);
         self.layout.row(&[-1], 0);
-        let mut r = self.layout.next();
+        let row = self.layout.next();
+        // the trailing content is outside the control so its own widgets get the clicks
+        let trailing_w = min(max(opts.trailing_width, 0), row.width);
+        // a right chevron stays at the end of the row, after the trailing content
+        let chevron_w = if opts.chevron == ChevronPosition::Right && trailing_w > 0 { min(row.height, row.width - trailing_w) } else { 0 };
+        let mut r = rect(row.x, row.y, row.width - trailing_w - chevron_w, row.height);
+        let trailing = rect(r.x + r.width, row.y, trailing_w, row.height);
+        let chevron = rect(trailing.x + trailing.width, row.y, chevron_w, row.height);
         self.update_control(id, r, WidgetOption::NONE);
+        let mut clicked = self.is_clicked(id);
+        // the chevron is split from the label by the trailing content, it gets its own control
+        let chevron_id = (chevron_w > 0).then(|| {
+            self.idmngr.push_id(id);
+            let chevron_id = self.idmngr.get_id_from_str("!chevron");
+            self.idmngr.pop_id();
+            chevron_id
+        });
+        if let Some(chevron_id) = chevron_id {
+            self.update_control(chevron_id, chevron, WidgetOption::NONE);
+            clicked |= self.is_clicked(chevron_id);
+        }
 
         let expanded = state.is_expanded();
-        let active = expanded ^ (self.input.borrow().mouse_pressed.is_left() && self.focus == Some(id));
+        let active = expanded ^ clicked;
+        if active != expanded {
+            self.emit_event(id, WidgetEventKind::Changed, EventValue::Bool(active));
+        }
 
-        if is_treenode {
-            if self.hover == Some(id) {
-                self.draw_frame(r, ControlColor::ButtonHover);
+        for (id, r) in std::iter::once((id, r)).chain(chevron_id.map(|c| (c, chevron))) {
+            if is_treenode {
+                if self.hover == Some(id) {
+                    self.draw_frame(r, ControlColor::ButtonHover);
+                }
+            } else {
+                self.draw_widget_frame(id, r, ControlColor::Button, WidgetOption::NONE);
             }
-        } else {
-            self.draw_widget_frame(id, r, ControlColor::Button, WidgetOption::NONE);
         }
         let color = self.style.colors[ControlColor::Text as usize];
-        self.draw_icon(if expanded { COLLAPSE_ICON } else { EXPAND_ICON }, rect(r.x, r.y, r.height, r.height), color);
-        r.x += r.height - self.style.padding;
-        r.width -= r.height - self.style.padding;
+        let icon = if expanded { opts.collapse_icon } else { opts.expand_icon };
+        match opts.chevron {
+            ChevronPosition::Left => {
+                self.draw_icon(icon, rect(r.x, r.y, r.height, r.height), color);
+                r.x += r.height - self.style.padding;
+                r.width -= r.height - self.style.padding;
+            }
+            ChevronPosition::Right if chevron_w > 0 => self.draw_icon(icon, chevron, color),
+            ChevronPosition::Right => {
+                self.draw_icon(icon, rect(r.x + r.width - r.height, r.y, r.height, r.height), color);
+                r.width -= r.height;
+            }
+            ChevronPosition::Hidden => (),
+        }
         self.draw_control_text(label, r, ControlColor::Text, WidgetOption::NONE);
-        return if active { NodeState::Expanded } else { NodeState::Closed };
+        (if active { NodeState::Expanded } else { NodeState::Closed }, trailing)
+    }
+
+    // the trailing widgets get their ids under the one of the node, rows can use the same icons
+    fn node_trailing<T: FnOnce(&mut Self)>(&mut self, node_id: Option<Id>, r: Recti, trailing: T) {
+        if r.width > 0 {
+            if let Some(id) = node_id {
+                self.idmngr.push_id(id);
+            }
+            self.layout.begin_region(r);
+            trailing(self);
+            self.layout.end_region();
+            if node_id.is_some() {
+                self.idmngr.pop_id();
+            }
+        }
+    }
+
+    // width of the trailing region holding `count` action buttons
+    pub fn node_actions_width(&self, count: usize) -> i32 {
+        let h = self.style.default_cell_size.height + self.style.padding * 2;
+        count as i32 * h + max(count as i32 - 1, 0) * self.style.spacing
+    }
+
+    // Small icon buttons (visibility, lock, delete...) for the trailing content of a header or tree node
+    // row. They don't toggle the node, returns the index of the clicked one.
+    pub fn node_actions(&mut self, actions: &[IconId]) -> Option<usize> {
+        let h = self.style.default_cell_size.height + self.style.padding * 2;
+        let widths = vec![h; actions.len()];
+        self.layout.row(&widths, -1);
+        let mut clicked = None;
+        // the same icon can be used twice, the ids come from the index
+        for (i, icon) in actions.iter().enumerate() {
+            self.idmngr.push_id_u32(i as u32);
+            if self.button_ex("", Some(*icon), WidgetOption::NONE).is_submitted() {
+                clicked = Some(i);
+            }
+            self.idmngr.pop_id();
+        }
+        clicked
+    }
+
+    #[must_use]
+    pub fn header_with_actions<F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, actions: &[IconId], f: F) -> (NodeState, Option<usize>) {
+        let opts = NodeOptions { trailing_width: self.node_actions_width(actions.len()), ..NodeOptions::default() };
+        let mut clicked = None;
+        let state = self.header_ex(label, state, &opts, |c| clicked = c.node_actions(actions), f);
+        (state, clicked)
+    }
+
+    #[must_use]
+    pub fn treenode_with_actions<F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, actions: &[IconId], f: F) -> (NodeState, Option<usize>) {
+        let opts = NodeOptions { trailing_width: self.node_actions_width(actions.len()), ..NodeOptions::default() };
+        let mut clicked = None;
+        let state = self.treenode_ex(label, state, &opts, |c| clicked = c.node_actions(actions), f);
+        (state, clicked)
     }
 
     #[must_use]
     pub fn header<F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, f: F) -> NodeState {
-        let new_state = self.node(label, false, state);
+        self.header_ex(label, state, &NodeOptions::default(), |_| (), f)
+    }
+
+    // `trailing` lays out the content on the right of the header row (a count, a button...), in a region
+    // of `opts.trailing_width` pixels
+    #[must_use]
+    pub fn header_ex<T: FnOnce(&mut Self), F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, opts: &NodeOptions, trailing: T, f: F) -> NodeState {
+        let (new_state, r) = self.node(label, false, state, opts);
+        let node_id = self.idmngr.last_id();
+        self.node_trailing(node_id, r, trailing);
         if new_state.is_expanded() {
             f(self);
         }
@@ -486,11 +1148,18 @@ impl Container {
 
     #[must_use]
     pub fn treenode<F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, f: F) -> NodeState {
-        let res = self.node(label, true, state);
-        if res.is_expanded() && self.idmngr.last_id().is_some() {
+        self.treenode_ex(label, state, &NodeOptions::default(), |_| (), f)
+    }
+
+    #[must_use]
+    pub fn treenode_ex<T: FnOnce(&mut Self), F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, opts: &NodeOptions, trailing: T, f: F) -> NodeState {
+        let (res, r) = self.node(label, true, state, opts);
+        let node_id = self.idmngr.last_id();
+        self.node_trailing(node_id, r, trailing);
+        if let (true, Some(id)) = (res.is_expanded(), node_id) {
             let indent = self.style.indent;
             self.layout.top_mut().indent += indent;
-            self.idmngr.push_id(self.idmngr.last_id().unwrap());
+            self.idmngr.push_id(id);
         }
 
         if res.is_expanded() {
@@ -546,6 +1215,7 @@ impl Container {
                 // TODO: doesn't solve the issue where we have a panel inside a panel
                 self.scroll.y += self.input.borrow().scroll_delta.y;
             }
+            self.scroll.y += self.gesture_scroll(body).y;
             self.scroll.y = Self::clamp(self.scroll.y, 0, maxscroll);
         } else {
             self.scroll.y = 0;
@@ -574,6 +1244,7 @@ impl Container {
             if self.mouse_over(body, in_hover_root) {
                 self.scroll.x += self.input.borrow().scroll_delta.x;
             }
+            self.scroll.x += self.gesture_scroll(body).x;
             self.scroll.x = Self::clamp(self.scroll.x, 0, maxscroll_0);
         } else {
             self.scroll.x = 0;
@@ -586,6 +1257,7 @@ impl Container {
         if !opt.has_no_scroll() {
             self.scrollbars(&mut body);
         }
+        self.draw_background(body);
         let style = self.style;
         let padding = -style.padding;
         let scroll = self.scroll;
@@ -602,24 +1274,39 @@ impl Container {
         container.layout.stack.pop();
     }
 
-    #[inline(never)]
     fn begin_panel(&mut self, panel: &mut ContainerHandle, opt: ContainerOption) {
         let rect = self.layout.next();
+        self.begin_panel_at(panel, rect, opt);
+    }
+
+    #[inline(never)]
+    pub(crate) fn begin_panel_at(&mut self, panel: &mut ContainerHandle, rect: Recti, opt: ContainerOption) {
         let clip_rect = panel.inner().body;
         let container = &mut panel.inner_mut();
         container.prepare();
 
         container.rect = rect;
+        container.path = self.path.child(&container.name);
         if !opt.has_no_frame() {
             self.draw_frame(rect, ControlColor::PanelBG);
         }
 
         container.in_hover_root = self.in_hover_root;
+        container.mnemonic_scope = self.mnemonic_scope;
+        container.disabled = self.disabled;
+        if let Some(tint) = self.tint_stack.last() {
+            container.tint_stack.push(*tint);
+        }
+        if let Some(mask) = self.mask_stack.last() {
+            container.mask_stack.push(*mask);
+            container.push_command(Command::Mask { mask: Some(*mask) });
+        }
         container.push_container_body(rect, opt);
         container.push_clip_rect(clip_rect);
     }
 
-    fn end_panel(&mut self, panel: &mut ContainerHandle) {
+    pub(crate) fn end_panel(&mut self, panel: &mut ContainerHandle) {
+        panel.inner_mut().draw_flashes();
         panel.inner_mut().pop_clip_rect();
         self.pop_panel(panel);
         self.panels.push(panel.clone())
@@ -638,12 +1325,63 @@ impl Container {
         self.layout.row(widths, height);
     }
 
+    // Evaluates `f` with `px` as the padding of its widgets, the space between their frame and content
+    pub fn with_cell_padding<R, F: FnOnce(&mut Self) -> R>(&mut self, px: i32, f: F) -> R {
+        let prev = self.style.padding;
+        self.style.padding = px;
+        self.layout.style.padding = px;
+        let res = f(self);
+        self.style.padding = prev;
+        self.layout.style.padding = prev;
+        res
+    }
+
+    // Evaluates `f` with its widgets inset by `margin` inside their cells
+    pub fn with_cell_margin<R, F: FnOnce(&mut Self) -> R>(&mut self, margin: Margin, f: F) -> R {
+        let prev = self.layout.cell_margin;
+        self.layout.cell_margin = margin;
+        let res = f(self);
+        self.layout.cell_margin = prev;
+        res
+    }
+
+    // Aligns the cells of the row set last, e.g. `RowAlign::End` for the OK/Cancel buttons of a dialog.
+    // It has no effect on rows with a remainder cell since they are filled.
+    pub fn set_row_align(&mut self, align: RowAlign) {
+        self.layout.set_row_align(align);
+    }
+
+    // like `set_row_widths_height` with `SizePolicy::Auto` cells sized to their widget
+    pub fn set_row_sizes(&mut self, sizes: &[SizePolicy], height: i32) {
+        let widths: Vec<i32> = sizes.iter().map(|s| s.row_width()).collect();
+        self.layout.row(&widths, height);
+    }
+
+    // size of a widget showing `text` with an optional image, padding included
+    pub(crate) fn content_size(&self, text: &str, image: Dimensioni) -> Dimensioni {
+        let padding = self.style.padding;
+        let text = if text.is_empty() { Dimensioni::new(0, 0) } else { self.atlas.get_text_size(self.style.font, text) };
+        let gap = if text.width > 0 && image.width > 0 { padding } else { 0 };
+        Dimensioni::new(text.width + gap + image.width + padding * 2, max(text.height, image.height) + padding * 2)
+    }
+
     pub fn column<F: FnOnce(&mut Self)>(&mut self, f: F) {
         self.layout.begin_column();
         f(self);
         self.layout.end_column();
     }
 
+    // Lays out `f` with the cells computed the previous frame, as long as the body, the style, the current
+    // row and the number of cells didn't change. For large static forms.
+    pub fn cached_layout<F: FnOnce(&mut Self)>(&mut self, key: &str, f: F) {
+        let id = self.idmngr.get_id_from_str(key);
+        let cached = self.layout.begin_cached(id);
+        f(self);
+        if cached {
+            self.layout.end_cached();
+        }
+    }
+
     pub fn next_cell(&mut self) -> Recti {
         self.layout.next()
     }
@@ -657,8 +1395,14 @@ impl Container {
     }
 
     pub fn label(&mut self, text: &str) {
-        let layout = self.layout.next();
-        self.draw_control_text(text, layout, ControlColor::Text, WidgetOption::NONE);
+        self.label_ex(text, WidgetOption::NONE);
+    }
+
+    // takes the alignment options, ALIGN_NUMERIC and ALIGN_DECIMAL for columns of numbers
+    pub fn label_ex(&mut self, text: &str, opt: WidgetOption) {
+        let size = self.content_size(text, Dimensioni::new(0, 0));
+        let layout = self.layout.next_sized(size);
+        self.draw_control_text(text, layout, ControlColor::Text, opt);
     }
 
     #[inline(never)]
@@ -669,14 +1413,18 @@ impl Container {
         } else {
             self.idmngr.get_id_u32(icon.unwrap().into())
         };
-        let r: Recti = self.layout.next();
+        let icon_size = icon.map(|i| self.atlas.get_icon_size(i)).unwrap_or(Dimensioni::new(0, 0));
+        let (text, mnemonic) = mnemonic_label(label, opt);
+        let r: Recti = self.layout.next_sized(self.content_size(&text, icon_size));
         self.update_control(id, r, opt);
-        if self.input.borrow().mouse_pressed.is_left() && self.focus == Some(id) {
+        if self.is_clicked(id) || self.mnemonic_pressed(mnemonic) {
             res |= ResourceState::SUBMIT;
+            self.emit_click(id, &text);
         }
         self.draw_widget_frame(id, r, ControlColor::Button, opt);
         if label.len() > 0 {
-            self.draw_control_text(label, r, ControlColor::Text, opt);
+            self.draw_control_text(&text, r, ControlColor::Text, opt);
+            self.draw_mnemonic(&text, mnemonic, r, ControlColor::Text, opt);
         }
         match icon {
             Some(icon) => {
@@ -696,14 +1444,18 @@ impl Container {
         } else {
             self.idmngr.get_id_u32(slot.unwrap().into())
         };
-        let r: Recti = self.layout.next();
+        let slot_size = slot.map(|s| self.atlas.get_slot_size(s)).unwrap_or(Dimensioni::new(0, 0));
+        let (text, mnemonic) = mnemonic_label(label, opt);
+        let r: Recti = self.layout.next_sized(self.content_size(&text, slot_size));
         self.update_control(id, r, opt);
-        if self.input.borrow().mouse_pressed.is_left() && self.focus == Some(id) {
+        if self.is_clicked(id) || self.mnemonic_pressed(mnemonic) {
             res |= ResourceState::SUBMIT;
+            self.emit_click(id, &text);
         }
         self.draw_widget_frame(id, r, ControlColor::Button, opt);
         if label.len() > 0 {
-            self.draw_control_text(label, r, ControlColor::Text, opt);
+            self.draw_control_text(&text, r, ControlColor::Text, opt);
+            self.draw_mnemonic(&text, mnemonic, r, ControlColor::Text, opt);
         }
         match slot {
             Some(slot) => {
@@ -723,14 +1475,18 @@ impl Container {
         } else {
             self.idmngr.get_id_u32(slot.unwrap().into())
         };
-        let r: Recti = self.layout.next();
+        let slot_size = slot.map(|s| self.atlas.get_slot_size(s)).unwrap_or(Dimensioni::new(0, 0));
+        let (text, mnemonic) = mnemonic_label(label, opt);
+        let r: Recti = self.layout.next_sized(self.content_size(&text, slot_size));
         self.update_control(id, r, opt);
-        if self.input.borrow().mouse_pressed.is_left() && self.focus == Some(id) {
+        if self.is_clicked(id) || self.mnemonic_pressed(mnemonic) {
             res |= ResourceState::SUBMIT;
+            self.emit_click(id, &text);
         }
         self.draw_widget_frame(id, r, ControlColor::Button, opt);
         if label.len() > 0 {
-            self.draw_control_text(label, r, ControlColor::Text, opt);
+            self.draw_control_text(&text, r, ControlColor::Text, opt);
+            self.draw_mnemonic(&text, mnemonic, r, ControlColor::Text, opt);
         }
         match slot {
             Some(slot) => {
@@ -742,16 +1498,39 @@ impl Container {
         return res;
     }
 
-    #[inline(never)]
     pub fn checkbox(&mut self, label: &str, state: &mut bool) -> ResourceState {
-        let mut res = ResourceState::NONE;
         let id: Id = self.idmngr.get_id_from_ptr(state);
-        let mut r: Recti = self.layout.next();
+        let r: Recti = self.layout.next_sized(self.checkbox_size(label));
+        self.checkbox_raw(label, state, id, r)
+    }
+
+    pub fn checkbox_bound<B: Binding<bool>>(&mut self, label: &str, binding: &mut B) -> ResourceState {
+        let id: Id = self.idmngr.get_id_from_ptr(binding);
+        let r: Recti = self.layout.next_sized(self.checkbox_size(label));
+        let mut state = binding.get();
+        let res = self.checkbox_raw(label, &mut state, id, r);
+        if res.is_changed() {
+            binding.set(state);
+        }
+        res
+    }
+
+    // the box is as wide as the cell is high
+    fn checkbox_size(&self, label: &str) -> Dimensioni {
+        let box_size = self.style.default_cell_size.height + self.style.padding * 2;
+        let text = self.content_size(label, Dimensioni::new(0, 0));
+        Dimensioni::new(box_size + text.width, max(box_size, text.height))
+    }
+
+    #[inline(never)]
+    pub fn checkbox_raw(&mut self, label: &str, state: &mut bool, id: Id, mut r: Recti) -> ResourceState {
+        let mut res = ResourceState::NONE;
         let box_0: Recti = rect(r.x, r.y, r.height, r.height);
         self.update_control(id, r, WidgetOption::NONE);
-        if self.input.borrow().mouse_pressed.is_left() && self.focus == Some(id) {
+        if self.is_clicked(id) {
             res |= ResourceState::CHANGE;
             *state = *state == false;
+            self.emit_event(id, WidgetEventKind::Changed, EventValue::Bool(*state));
         }
         self.draw_widget_frame(id, box_0, ControlColor::Base, WidgetOption::NONE);
         if *state {
@@ -800,41 +1579,63 @@ impl Container {
     pub fn textbox_raw(&mut self, buf: &mut String, id: Id, r: Recti, opt: WidgetOption) -> ResourceState {
         let mut res = ResourceState::NONE;
         self.update_control(id, r, opt | WidgetOption::HOLD_FOCUS);
+        self.request_text_menu(id, r, true);
+        match self.take_text_action(id) {
+            Some(TextAction::Copy) => self.copy_to_clipboard(buf),
+            Some(TextAction::Cut) => {
+                self.copy_to_clipboard(buf);
+                buf.clear();
+                self.text_cursor = 0;
+                res |= ResourceState::CHANGE;
+            }
+            Some(TextAction::Paste(text)) => {
+                let text: String = text.chars().filter(|c| *c != '\n' && *c != '\r').collect();
+                if self.text_edit == Some(id) {
+                    self.text_cursor = clamp_cursor(buf, self.text_cursor);
+                    buf.insert_str(self.text_cursor, &text);
+                    self.text_cursor += text.len();
+                } else {
+                    buf.push_str(&text);
+                }
+                res |= ResourceState::CHANGE;
+            }
+            // the box has no selection, copy and cut always take the whole text
+            Some(TextAction::SelectAll) | None => (),
+        }
         if self.focus == Some(id) {
-            let mut len = buf.len();
-
-            if self.input.borrow().input_text.len() > 0 {
-                buf.push_str(self.input.borrow().input_text.as_str());
-                len += self.input.borrow().input_text.len() as usize;
-                res |= ResourceState::CHANGE
+            // the cursor starts at the end of the text when the box gets the focus
+            if self.text_edit != Some(id) {
+                self.text_edit = Some(id);
+                self.text_cursor = buf.len();
             }
-
-            if self.input.borrow().key_pressed.is_backspace() && len > 0 {
-                // skip utf-8 continuation bytes
-                buf.pop();
+            if edit_text(buf, &mut self.text_cursor, &self.input.borrow()) {
                 res |= ResourceState::CHANGE
             }
             if self.input.borrow().key_pressed.is_return() {
                 self.set_focus(None);
                 res |= ResourceState::SUBMIT;
             }
+        } else if self.text_edit == Some(id) {
+            self.text_edit = None;
         }
         self.draw_widget_frame(id, r, ControlColor::Base, opt);
         if self.focus == Some(id) {
             let color = self.style.colors[ControlColor::Text as usize];
             let font = self.style.font;
             let tsize = self.atlas.get_text_size(font, buf.as_str());
-            let ofx = r.width - self.style.padding - tsize.width - 1;
+            let cursor_x = self.atlas.get_text_size(font, &buf[..self.text_cursor]).width;
+            let ofx = r.width - self.style.padding - cursor_x - 1;
             let textx = r.x + (if ofx < self.style.padding { ofx } else { self.style.padding });
             let texty = r.y + (r.height - tsize.height) / 2;
 
             self.push_clip_rect(r);
             self.draw_text(font, buf.as_str(), vec2(textx, texty), color);
-            self.draw_rect(rect(textx + tsize.width, texty, 1, tsize.height), color);
+            self.draw_rect(rect(textx + cursor_x, texty, 1, tsize.height), color);
             self.pop_clip_rect();
         } else {
             self.draw_control_text(buf.as_str(), r, ControlColor::Text, opt);
         }
+        self.emit_state(id, &res, || EventValue::Text(buf.clone()));
         return res;
     }
 
@@ -872,13 +1673,41 @@ impl Container {
         return self.textbox_raw(buf, id, r, opt);
     }
 
-    #[inline(never)]
+    pub fn textbox_bound<B: Binding<String>>(&mut self, binding: &mut B, opt: WidgetOption) -> ResourceState {
+        let id: Id = self.idmngr.get_id_from_ptr(binding);
+        let r: Recti = self.layout.next();
+        let mut buf = binding.get();
+        let res = self.textbox_raw(&mut buf, id, r, opt);
+        if res.is_changed() {
+            binding.set(buf);
+        }
+        res
+    }
+
     pub fn slider_ex(&mut self, value: &mut Real, low: Real, high: Real, step: Real, precision: usize, opt: WidgetOption) -> ResourceState {
+        let id = self.idmngr.get_id_from_ptr(value);
+        let base = self.layout.next();
+        self.slider_raw(value, id, base, low, high, step, precision, opt)
+    }
+
+    pub fn slider_bound<B: Binding<Real>>(&mut self, binding: &mut B, low: Real, high: Real, step: Real, precision: usize, opt: WidgetOption) -> ResourceState {
+        let id = self.idmngr.get_id_from_ptr(binding);
+        let base = self.layout.next();
+        let mut value = binding.get();
+        let last = value;
+        let res = self.slider_raw(&mut value, id, base, low, high, step, precision, opt);
+        if value != last {
+            binding.set(value);
+        }
+        res
+    }
+
+    #[inline(never)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn slider_raw(&mut self, value: &mut Real, id: Id, base: Recti, low: Real, high: Real, step: Real, precision: usize, opt: WidgetOption) -> ResourceState {
         let mut res = ResourceState::NONE;
         let last = *value;
         let mut v = last;
-        let id = self.idmngr.get_id_from_ptr(value);
-        let base = self.layout.next();
         if !self.number_textbox(precision, &mut v, base, id).is_none() {
             return res;
         }
@@ -908,21 +1737,38 @@ impl Container {
         let mut buff = String::new();
         buff.push_str(format!("{:.*}", precision, value).as_str());
         self.draw_control_text(buff.as_str(), base, ControlColor::Text, opt);
+        self.emit_state(id, &res, || EventValue::Real(*value));
         return res;
     }
 
-    #[inline(never)]
     pub fn number_ex(&mut self, value: &mut Real, step: Real, precision: usize, opt: WidgetOption) -> ResourceState {
-        let mut res = ResourceState::NONE;
         let id: Id = self.idmngr.get_id_from_ptr(value);
         let base: Recti = self.layout.next();
+        self.number_raw(value, id, base, step, precision, opt)
+    }
+
+    pub fn number_bound<B: Binding<Real>>(&mut self, binding: &mut B, step: Real, precision: usize, opt: WidgetOption) -> ResourceState {
+        let id: Id = self.idmngr.get_id_from_ptr(binding);
+        let base: Recti = self.layout.next();
+        let mut value = binding.get();
+        let last = value;
+        let res = self.number_raw(&mut value, id, base, step, precision, opt);
+        if value != last {
+            binding.set(value);
+        }
+        res
+    }
+
+    #[inline(never)]
+    pub fn number_raw(&mut self, value: &mut Real, id: Id, base: Recti, step: Real, precision: usize, opt: WidgetOption) -> ResourceState {
+        let mut res = ResourceState::NONE;
         let last: Real = *value;
         if !self.number_textbox(precision, value, base, id).is_none() {
             return res;
         }
         self.update_control(id, base, opt);
         if self.focus == Some(id) && self.input.borrow().mouse_down.is_left() {
-            *value += self.input.borrow().mouse_delta.x as Real * step;
+            *value += self.scrub_delta(step);
         }
         if *value != last {
             res |= ResourceState::CHANGE;
@@ -931,6 +1777,42 @@ impl Container {
         let mut buff = String::new();
         buff.push_str(format!("{:.*}", precision, value).as_str());
         self.draw_control_text(buff.as_str(), base, ControlColor::Text, opt);
+        self.emit_state(id, &res, || EventValue::Real(*value));
         return res;
     }
+
+    // horizontal drag distance times `step`, Shift divides the speed by 10 and Ctrl multiplies it by 10
+    fn scrub_delta(&self, step: Real) -> Real {
+        let input = self.input.borrow();
+        let speed = if input.key_down.is_shift() {
+            0.1
+        } else if input.key_down.is_ctrl() {
+            10.0
+        } else {
+            1.0
+        };
+        input.mouse_delta.x as Real * step * speed
+    }
+
+    // A label that changes `value` when dragged horizontally, placed next to a number to save the room of
+    // a slider. The speed modifiers are the ones of the number widget.
+    pub fn scrub_label(&mut self, label: &str, value: &mut Real, step: Real) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        self.idmngr.push_id_from_ptr(value);
+        let id = self.idmngr.get_id_from_str(label);
+        self.idmngr.pop_id();
+        let r = self.layout.next();
+        self.update_control(id, r, WidgetOption::NONE);
+        if self.focus == Some(id) && self.input.borrow().mouse_down.is_left() {
+            let last = *value;
+            *value += self.scrub_delta(step);
+            if *value != last {
+                res |= ResourceState::CHANGE;
+            }
+        }
+        let color = if self.focus == Some(id) || self.hover == Some(id) { ControlColor::Accent } else { ControlColor::Text };
+        self.draw_control_text(label, r, color, WidgetOption::NONE);
+        self.emit_state(id, &res, || EventValue::Real(*value));
+        res
+    }
 }
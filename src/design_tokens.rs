@@ -0,0 +1,231 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Design token import (W3C draft format as exported by Figma plugins, Tokens Studio, Style Dictionary...).
+// Tokens are matched by their path: `color.button.hover` sets the `button_hover` color, `spacing.padding` the
+// padding, `radius.item` the item radius. Both `$value` and `value` are accepted, references aren't resolved.
+use super::*;
+use serde_json::{Map, Value};
+
+pub struct TokenImport {
+    pub style: Style,
+    pub unmapped: Vec<String>, // token paths that didn't match a style field, for the designer to check
+}
+
+// common token names for the style colors
+const COLOR_ALIASES: [(&str, &str); 8] = [
+    ("primary", "accent"),
+    ("background", "window_bg"),
+    ("foreground", "text"),
+    ("surface", "panel_bg"),
+    ("danger", "error"),
+    ("outline", "border"),
+    ("selection", "selection_bg"),
+    ("muted", "disabled"),
+];
+
+fn normalize(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '-' | ' ' | '.' => out.push('_'),
+            c if c.is_ascii_uppercase() => {
+                // camelCase to snake_case
+                if i > 0 && !out.ends_with('_') {
+                    out.push('_');
+                }
+                out.push(c.to_ascii_lowercase());
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let v = |i: usize, n: usize| u8::from_str_radix(hex.get(i..i + n)?, 16).ok();
+    match hex.len() {
+        3 => Some(color(v(0, 1)? * 17, v(1, 1)? * 17, v(2, 1)? * 17, 255)),
+        6 => Some(color(v(0, 2)?, v(2, 2)?, v(4, 2)?, 255)),
+        8 => Some(color(v(0, 2)?, v(2, 2)?, v(4, 2)?, v(6, 2)?)),
+        _ => None,
+    }
+}
+
+// #rgb, #rrggbb, #rrggbbaa, rgb(r, g, b) and rgba(r, g, b, a) with an alpha between 0 and 1
+fn parse_color(s: &str) -> Option<Color> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    let args = s.strip_prefix("rgba(").or_else(|| s.strip_prefix("rgb("))?.strip_suffix(')')?;
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    let c = |i: usize| parts.get(i)?.parse::<u8>().ok();
+    let a = match parts.get(3) {
+        Some(a) => (a.parse::<Real>().ok()?.clamp(0.0, 1.0) * 255.0).round() as u8,
+        None => 255,
+    };
+    Some(color(c(0)?, c(1)?, c(2)?, a))
+}
+
+// pixels from a number, "8px" or "0.5rem" (16px per rem)
+fn parse_dimension(v: &Value) -> Option<Real> {
+    match v {
+        Value::Number(n) => n.as_f64().map(|n| n as Real),
+        Value::String(s) => {
+            let s = s.trim();
+            if let Some(rem) = s.strip_suffix("rem") {
+                rem.trim().parse::<Real>().ok().map(|r| r * 16.0)
+            } else {
+                s.strip_suffix("px").unwrap_or(s).trim().parse::<Real>().ok()
+            }
+        }
+        _ => None,
+    }
+}
+
+fn token_value(obj: &Map<String, Value>) -> Option<&Value> {
+    obj.get("$value").or_else(|| obj.get("value"))
+}
+
+// the tokens under `value`, as (path segments, value)
+fn collect_tokens<'a>(path: &mut Vec<String>, value: &'a Value, out: &mut Vec<(Vec<String>, &'a Value)>) {
+    let Value::Object(obj) = value else {
+        return;
+    };
+    if let Some(v) = token_value(obj) {
+        out.push((path.clone(), v));
+        return;
+    }
+    for (key, child) in obj.iter().filter(|(k, _)| !k.starts_with('$')) {
+        path.push(normalize(key));
+        collect_tokens(path, child, out);
+        path.pop();
+    }
+}
+
+fn set_color(style: &mut Style, name: &str, c: Color) -> bool {
+    let name = COLOR_ALIASES.iter().find(|(alias, _)| *alias == name).map(|(_, n)| *n).unwrap_or(name);
+    match COLOR_NAMES.iter().position(|n| *n == name) {
+        Some(i) => {
+            style.colors[i] = c;
+            true
+        }
+        None => false,
+    }
+}
+
+fn set_metric(style: &mut Style, name: &str, px: i32) -> bool {
+    match name {
+        "padding" => style.padding = px,
+        "spacing" | "gap" => style.spacing = px,
+        "indent" => style.indent = px,
+        "title_height" => style.title_height = px,
+        "scrollbar_size" | "scrollbar" => style.scrollbar_size = px,
+        "thumb_size" | "thumb" => style.thumb_size = px,
+        "cell_height" => style.default_cell_size.height = px,
+        "cell_width" => style.default_cell_size.width = px,
+        "item" | "item_radius" | "default" | "radius" => style.item_radius = px,
+        _ => return false,
+    }
+    true
+}
+
+// fonts come from the atlas, only the line height of a typography token is used, as the gap between lines
+fn set_typography(style: &mut Style, value: &Value) -> bool {
+    let Value::Object(obj) = value else {
+        return false;
+    };
+    let size = obj.get("fontSize").or_else(|| obj.get("font_size")).and_then(parse_dimension);
+    let line = obj.get("lineHeight").or_else(|| obj.get("line_height"));
+    match (size, line) {
+        (Some(size), Some(line)) => {
+            // a bare number below 4 is a multiplier of the font size
+            let line_px = match (line, parse_dimension(line)) {
+                (Value::Number(_), Some(m)) if m < 4.0 => m * size,
+                (_, Some(px)) => px,
+                _ => return false,
+            };
+            style.line_spacing = (line_px - size).round() as i32;
+            true
+        }
+        _ => false,
+    }
+}
+
+fn apply_token(style: &mut Style, path: &[String], value: &Value) -> bool {
+    let Some((category, rest)) = path.split_first() else {
+        return false;
+    };
+    let name = rest.join("_");
+    let last = rest.last().map(|s| s.as_str()).unwrap_or("");
+    match category.as_str() {
+        "color" | "colors" => match value.as_str().and_then(parse_color) {
+            Some(c) => set_color(style, &name, c) || set_color(style, last, c),
+            None => false,
+        },
+        "spacing" | "space" | "size" | "sizing" | "dimension" | "radius" | "radii" | "border_radius" => {
+            let Some(px) = parse_dimension(value) else {
+                return false;
+            };
+            let px = px.round() as i32;
+            if category.contains("radi") {
+                set_metric(style, "item_radius", px)
+            } else {
+                set_metric(style, &name, px) || set_metric(style, last, px)
+            }
+        }
+        "typography" | "font" | "text" => set_typography(style, value),
+        _ => false,
+    }
+}
+
+// Maps a token file over `base`, the fields without a matching token keep their value
+pub fn style_from_design_tokens(json: &str, base: &Style) -> Result<TokenImport, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut tokens = Vec::new();
+    collect_tokens(&mut Vec::new(), &root, &mut tokens);
+
+    let mut style = *base;
+    let mut unmapped = Vec::new();
+    for (path, value) in tokens {
+        if !apply_token(&mut style, &path, value) {
+            unmapped.push(path.join("."));
+        }
+    }
+    Ok(TokenImport { style, unmapped })
+}
+
+impl Theme {
+    // the colors of a token file, over the colors of `base`
+    pub fn from_design_tokens(json: &str, base: &Theme) -> Result<Theme, String> {
+        let style = Style { colors: base.colors, ..Style::default() };
+        Ok(Theme { colors: style_from_design_tokens(json, &style)?.style.colors })
+    }
+}
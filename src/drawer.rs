@@ -0,0 +1,147 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DrawerEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+// the open/close slide of a panel along a screen or container edge
+pub(crate) struct Slide {
+    pub open: bool,
+    pub progress: Real,
+}
+
+impl Slide {
+    pub fn new() -> Self {
+        Self { open: false, progress: 0.0 }
+    }
+
+    // moves the progress toward the open or closed end by `step`
+    pub fn animate(&mut self, step: Real) {
+        let target = if self.open { 1.0 } else { 0.0 };
+        if self.progress < target {
+            self.progress = (self.progress + step).min(target);
+        } else {
+            self.progress = (self.progress - step).max(target);
+        }
+    }
+
+    // where a `size` thick panel along `edge` of `body` is at this point of the slide
+    pub fn rect(&self, edge: DrawerEdge, size: i32, body: Recti) -> Recti {
+        // ease out the slide
+        let t = 1.0 - (1.0 - self.progress) * (1.0 - self.progress);
+        let visible = (size as Real * t) as i32;
+        match edge {
+            DrawerEdge::Left => rect(body.x - size + visible, body.y, size, body.height),
+            DrawerEdge::Right => rect(body.x + body.width - visible, body.y, size, body.height),
+            DrawerEdge::Top => rect(body.x, body.y - size + visible, body.width, size),
+            DrawerEdge::Bottom => rect(body.x, body.y + body.height - visible, body.width, size),
+        }
+    }
+}
+
+pub struct DrawerState {
+    panel: ContainerHandle,
+    pub edge: DrawerEdge,
+    pub size: i32,   // width or height of the fully opened drawer
+    pub speed: Real, // fraction of the slide done per second
+    slide: Slide,
+    pub extras: Extras,
+}
+
+impl DrawerState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str, edge: DrawerEdge, size: i32) -> Self {
+        Self {
+            panel: ctx.new_panel(name),
+            edge,
+            size,
+            speed: 8.0,
+            slide: Slide::new(),
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.slide.open
+    }
+
+    pub fn set_open(&mut self, open: bool) {
+        self.slide.open = open;
+    }
+
+    pub fn toggle(&mut self) {
+        self.slide.open = !self.slide.open;
+    }
+
+    // true while the drawer is (at least partially) visible
+    pub fn is_visible(&self) -> bool {
+        self.slide.progress > 0.0
+    }
+
+    fn animate(&mut self, dt: Real) {
+        self.slide.animate(self.speed * dt);
+    }
+
+    fn rect(&self, body: Recti) -> Recti {
+        self.slide.rect(self.edge, self.size, body)
+    }
+}
+
+impl Container {
+    // The drawer overlaps the container body instead of taking a layout cell. Since panels render in the order
+    // they are added, call this after the rest of the container content so it ends up on top.
+    pub fn drawer<F: FnOnce(&mut ContainerHandle)>(&mut self, state: &mut DrawerState, opt: ContainerOption, f: F) {
+        state.animate(self.frame_dt());
+        if !state.is_visible() {
+            return;
+        }
+
+        let body = self.body;
+        let r = state.rect(body);
+        let mut panel = state.panel.clone();
+
+        self.begin_panel_at(&mut panel, r, opt);
+
+        // begin_panel_at clips to the body of the previous frame, which lags behind while sliding. The sliding
+        // part is kept inside the parent body.
+        let panel_body = panel.inner().body;
+        panel.inner_mut().pop_clip_rect();
+        panel.inner_mut().push_clip_rect(body);
+        panel.inner_mut().push_clip_rect(panel_body);
+        f(&mut panel);
+        self.end_panel(&mut panel);
+        panel.inner_mut().pop_clip_rect();
+    }
+}
@@ -0,0 +1,152 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// A label renamed in place: a double click turns it into a text box with the whole text selected, Enter or
+// clicking elsewhere commits the new text and Escape restores the old one.
+#[derive(Clone, Default)]
+pub struct EditableLabel {
+    text: String,
+    edit: Option<(String, bool)>, // text being edited and whether it's still all selected
+    grab_focus: bool,             // the edit started, the text box takes the focus on the next evaluation
+}
+
+impl EditableLabel {
+    pub fn new(text: &str) -> Self {
+        Self { text: text.to_string(), edit: None, grab_focus: false }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.edit.is_some()
+    }
+
+    // starts the rename without the double click, e.g. from a context menu or F2
+    pub fn start_editing(&mut self) {
+        self.edit = Some((self.text.clone(), true));
+        self.grab_focus = true;
+    }
+
+    pub fn cancel_editing(&mut self) {
+        self.edit = None;
+        self.grab_focus = false;
+    }
+}
+
+impl Widget for EditableLabel {
+    fn options(&self) -> WidgetOption {
+        if self.edit.is_some() {
+            WidgetOption::HOLD_FOCUS
+        } else {
+            WidgetOption::NONE
+        }
+    }
+
+    // SUBMIT once the rename is committed, with CHANGE when the text differs
+    fn handle(&mut self, container: &mut Container, id: Id, r: Recti) -> ResourceState {
+        if self.edit.is_none() {
+            if container.hover == Some(id) && container.input.borrow().mouse_double_clicked().is_left() {
+                self.start_editing();
+            } else {
+                container.draw_control_text(&self.text, r, ControlColor::Text, WidgetOption::NONE);
+                return ResourceState::NONE;
+            }
+        }
+        // only once, losing the focus later on commits the edit
+        if std::mem::take(&mut self.grab_focus) {
+            container.set_focus(Some(id));
+        }
+
+        let (escape, typed, erased, moved) = {
+            let input = container.input.borrow();
+            let k = input.key_pressed;
+            (k.is_escape(), !input.input_text.is_empty(), k.is_backspace() || k.is_delete(), k.is_left() || k.is_right() || k.is_home() || k.is_end())
+        };
+        if escape {
+            self.edit = None;
+            if container.focus == Some(id) {
+                container.set_focus(None);
+            }
+            container.draw_control_text(&self.text, r, ControlColor::Text, WidgetOption::NONE);
+            return ResourceState::NONE;
+        }
+
+        let Some((buf, selected)) = &mut self.edit else {
+            return ResourceState::NONE;
+        };
+        // typing replaces the selected text, moving the cursor drops the selection
+        if *selected && (typed || erased) {
+            buf.clear();
+        }
+        if typed || erased || moved {
+            *selected = false;
+        }
+
+        let res = container.textbox_raw(buf, id, r, WidgetOption::HOLD_FOCUS);
+        if *selected {
+            // same placement as the text box, the cursor sits at the end
+            let font = container.style.font;
+            let padding = container.style.padding;
+            let size = container.atlas.get_text_size(font, buf);
+            let x = r.x + (r.width - padding - size.width - 1).min(padding);
+            let y = r.y + (r.height - size.height) / 2;
+            let color = container.style.colors[ControlColor::Text as usize];
+            container.push_clip_rect(r);
+            container.draw_rect(rect(x, y, size.width, size.height), container.style.colors[ControlColor::SelectionBG as usize]);
+            container.draw_text(font, buf, vec2(x, y), color);
+            container.pop_clip_rect();
+        }
+
+        if res.is_submitted() || container.focus != Some(id) {
+            let changed = *buf != self.text;
+            if let Some((text, _)) = self.edit.take() {
+                self.text = text;
+            }
+            if container.focus == Some(id) {
+                container.set_focus(None);
+            }
+            return if changed { ResourceState::SUBMIT | ResourceState::CHANGE } else { ResourceState::SUBMIT };
+        }
+        ResourceState::ACTIVE
+    }
+}
+
+impl Container {
+    pub fn editable_label(&mut self, label: &mut EditableLabel) -> ResourceState {
+        self.widget(label)
+    }
+}
@@ -0,0 +1,118 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Type keyed storage for application data attached to containers and widget states (one value per type)
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+trait ExtraValue: Any {
+    fn clone_box(&self) -> Box<dyn ExtraValue>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Any + Clone> ExtraValue for T {
+    fn clone_box(&self) -> Box<dyn ExtraValue> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct Extras {
+    values: HashMap<TypeId, Box<dyn ExtraValue>>,
+}
+
+impl Clone for Extras {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.iter().map(|(k, v)| (*k, v.as_ref().clone_box())).collect(),
+        }
+    }
+}
+
+impl Extras {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // returns the previous value of the same type if any
+    pub fn insert<T: Any + Clone>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|v| v.into_any().downcast::<T>().ok())
+            .map(|v| *v)
+    }
+
+    pub fn get<T: Any + Clone>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>()).and_then(|v| v.as_ref().as_any().downcast_ref::<T>())
+    }
+
+    pub fn get_mut<T: Any + Clone>(&mut self) -> Option<&mut T> {
+        self.values.get_mut(&TypeId::of::<T>()).and_then(|v| v.as_mut().as_any_mut().downcast_mut::<T>())
+    }
+
+    pub fn get_or_insert_with<T: Any + Clone, F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .as_mut()
+            .as_any_mut()
+            .downcast_mut::<T>()
+            .unwrap()
+    }
+
+    pub fn remove<T: Any + Clone>(&mut self) -> Option<T> {
+        self.values.remove(&TypeId::of::<T>()).and_then(|v| v.into_any().downcast::<T>().ok()).map(|v| *v)
+    }
+
+    pub fn contains<T: Any + Clone>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear()
+    }
+}
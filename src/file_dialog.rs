@@ -40,6 +40,7 @@ pub struct FileDialogState {
     file_panel: ContainerHandle,
     folders: Vec<String>,
     files: Vec<String>,
+    pub extras: Extras,
 }
 
 impl FileDialogState {
@@ -76,6 +77,7 @@ impl FileDialogState {
             file_panel: ctx.new_panel("files"),
             folders,
             files,
+            extras: Extras::default(),
         }
     }
 
@@ -84,6 +86,7 @@ impl FileDialogState {
     }
 
     pub fn eval<R: Renderer>(&mut self, ctx: &mut Context<R>) {
+        let mut confirmed = None;
         ctx.dialog(&mut self.win, ContainerOption::NONE, |cont| {
             let content_size = cont.content_size;
             let half_width = content_size.x / 2;
@@ -133,13 +136,19 @@ impl FileDialogState {
                 if self.tmp_file_name != "" {
                     self.file_name = Some(self.tmp_file_name.clone())
                 }
+                confirmed = Some(true);
                 return WindowState::Closed;
             }
             if cont.button_ex("Cancel", None, WidgetOption::NONE).is_submitted() {
                 self.file_name = None;
+                confirmed = Some(false);
                 return WindowState::Closed;
             }
             WindowState::Open
         });
+        if let Some(confirmed) = confirmed {
+            let name = self.win.name();
+            ctx.report_telemetry(if confirmed { TelemetryEvent::DialogConfirmed { name } } else { TelemetryEvent::DialogCancelled { name } });
+        }
     }
 }
@@ -0,0 +1,85 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// seconds the outline takes to fade out
+const FLASH_TIME: Real = 0.8;
+
+impl Container {
+    // Outlines the widget in the accent color for a moment, e.g. when its value was changed by a script or a
+    // remote peer rather than by the user. Returns false when the widget wasn't laid out last frame.
+    pub fn flash_widget(&mut self, id: Id) -> bool {
+        if self.widget_rects.contains_key(&id) {
+            self.flashes.insert(id, FLASH_TIME);
+            return true;
+        }
+        self.panels.iter_mut().any(|p| p.inner_mut().flash_widget(id))
+    }
+
+    pub fn is_flashing(&self, id: Id) -> bool {
+        self.flashes.contains_key(&id)
+    }
+
+    // advances the flash of a widget being laid out, the outline is drawn over its content when the container ends
+    pub(crate) fn update_flash(&mut self, id: Id, rect: Recti) {
+        let dt = self.frame_dt();
+        let Some(t) = self.flashes.get_mut(&id) else {
+            return;
+        };
+        *t -= dt;
+        if *t <= 0.0 {
+            self.flashes.remove(&id);
+            return;
+        }
+        let fade = *t / FLASH_TIME;
+        let clip = self.get_clip_rect();
+        self.flash_rects.push((rect, clip, fade));
+    }
+
+    pub(crate) fn draw_flashes(&mut self) {
+        let accent = self.style.color(ControlColor::Accent);
+        for (r, clip, fade) in std::mem::take(&mut self.flash_rects) {
+            let c = color(accent.r, accent.g, accent.b, (accent.a as Real * fade) as u8);
+            self.clip_stack.push(clip);
+            self.draw_box(r, c);
+            self.draw_box(rect(r.x - 1, r.y - 1, r.width + 2, r.height + 2), c);
+            self.clip_stack.pop();
+        }
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // flashes the widget in the topmost window that laid it out last frame, see `Container::flash_widget`
+    pub fn flash_widget(&mut self, id: Id) -> bool {
+        let mut windows = self.root_list.clone();
+        windows.sort_by_key(|w| -w.zindex());
+        windows.iter_mut().any(|w| w.inner_mut().main.flash_widget(id))
+    }
+}
@@ -0,0 +1,168 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Copy, Clone, Default, Debug)]
+pub struct FormResponse {
+    pub changed: bool,
+    pub submitted: bool,
+    pub reset: bool,
+}
+
+// Keeps the committed value apart from the one being edited, so a form can be submitted or rolled back
+#[derive(Clone)]
+pub struct FormState<T: Clone> {
+    value: T,
+    edit: T,
+    pub extras: Extras,
+}
+
+impl<T: Clone> FormState<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            edit: value.clone(),
+            value,
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn edit(&self) -> &T {
+        &self.edit
+    }
+
+    pub fn edit_mut(&mut self) -> &mut T {
+        &mut self.edit
+    }
+
+    pub fn submit(&mut self) {
+        self.value = self.edit.clone();
+    }
+
+    pub fn reset(&mut self) {
+        self.edit = self.value.clone();
+    }
+
+    pub fn update(&mut self, response: &FormResponse) {
+        if response.submitted {
+            self.submit();
+        } else if response.reset {
+            self.reset();
+        }
+    }
+}
+
+pub struct Form<'a> {
+    container: &'a mut Container,
+    label_width: i32,
+    response: FormResponse,
+}
+
+impl<'a> Form<'a> {
+    fn track(&mut self, res: ResourceState) -> ResourceState {
+        self.response.changed |= res.is_changed();
+        self.response.submitted |= res.is_submitted();
+        res
+    }
+
+    fn row(&mut self, label: &str) {
+        self.container.set_row_widths_height(&[self.label_width, -1], 0);
+        self.container.label(label);
+    }
+
+    pub fn container(&mut self) -> &mut Container {
+        self.container
+    }
+
+    pub fn field<F: FnOnce(&mut Container) -> ResourceState>(&mut self, label: &str, f: F) -> ResourceState {
+        self.row(label);
+        let res = f(self.container);
+        self.track(res)
+    }
+
+    pub fn textbox(&mut self, label: &str, buf: &mut String) -> ResourceState {
+        self.field(label, |c| c.textbox_ex(buf, WidgetOption::NONE))
+    }
+
+    pub fn slider(&mut self, label: &str, value: &mut Real, low: Real, high: Real, step: Real, precision: usize) -> ResourceState {
+        self.field(label, |c| c.slider_ex(value, low, high, step, precision, WidgetOption::ALIGN_CENTER))
+    }
+
+    pub fn number(&mut self, label: &str, value: &mut Real, step: Real, precision: usize) -> ResourceState {
+        self.field(label, |c| c.number_ex(value, step, precision, WidgetOption::ALIGN_CENTER))
+    }
+
+    pub fn checkbox(&mut self, label: &str, state: &mut bool) -> ResourceState {
+        self.field(label, |c| c.checkbox("", state))
+    }
+
+    pub fn separator(&mut self) {
+        self.container.set_row_widths_height(&[-1], 1);
+        let r = self.container.next_cell();
+        let color = self.container.style.colors[ControlColor::Border as usize];
+        self.container.draw_rect(r, color);
+    }
+
+    // Submit and Reset buttons on a single row, aligned with the editor column
+    pub fn submit_reset(&mut self, submit_label: &str, reset_label: &str) {
+        let half = (self.container.body.width - self.label_width) / 2 - self.container.style.spacing;
+        self.container.set_row_widths_height(&[self.label_width, half, -1], 0);
+        self.container.next_cell();
+        if self.container.button_ex(submit_label, None, WidgetOption::ALIGN_CENTER).is_submitted() {
+            self.submit();
+        }
+        if self.container.button_ex(reset_label, None, WidgetOption::ALIGN_CENTER).is_submitted() {
+            self.reset();
+        }
+    }
+
+    pub fn submit(&mut self) {
+        self.response.submitted = true;
+    }
+
+    pub fn reset(&mut self) {
+        self.response.reset = true;
+    }
+}
+
+impl Container {
+    pub fn form<F: FnOnce(&mut Form)>(&mut self, label_width: i32, f: F) -> FormResponse {
+        let mut form = Form {
+            container: self,
+            label_width,
+            response: FormResponse::default(),
+        };
+        f(&mut form);
+        form.response
+    }
+}
@@ -0,0 +1,97 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::f32::consts::PI;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GaugeIndicator {
+    Needle,
+    Arc,
+}
+
+// the dial spans 270 degrees, leaving the opening at the bottom
+const GAUGE_START: f32 = 0.75 * PI;
+const GAUGE_SWEEP: f32 = 1.5 * PI;
+
+impl Container {
+    #[inline(never)]
+    pub fn gauge_ex(&mut self, value: Real, low: Real, high: Real, ticks: usize, indicator: GaugeIndicator, precision: usize) {
+        let r = self.layout.next();
+        let radius = (min(r.width, r.height) / 2 - self.style.padding) as f32;
+        if radius <= 0.0 {
+            return;
+        }
+
+        let center = Vec2f::new(r.x as f32 + r.width as f32 / 2.0, r.y as f32 + r.height as f32 / 2.0);
+        let thickness = (radius / 6.0).max(2.0);
+        let t = if high > low { ((value - low) / (high - low)).clamp(0.0, 1.0) } else { 0.0 };
+        let angle = GAUGE_START + GAUGE_SWEEP * t;
+
+        let base = self.style.colors[ControlColor::Base as usize];
+        let fill = self.style.colors[ControlColor::Accent as usize];
+        let text = self.style.colors[ControlColor::Text as usize];
+
+        self.draw_arc(center, radius, thickness, GAUGE_START, GAUGE_START + GAUGE_SWEEP, base);
+        if indicator == GaugeIndicator::Arc && t > 0.0 {
+            self.draw_arc(center, radius, thickness, GAUGE_START, angle, fill);
+        }
+
+        if ticks > 1 {
+            for i in 0..ticks {
+                let a = GAUGE_START + GAUGE_SWEEP * i as f32 / (ticks - 1) as f32;
+                let outer = radius - thickness - 2.0;
+                let inner = outer - thickness;
+                let p0 = Vec2f::new(center.x + inner * a.cos(), center.y + inner * a.sin());
+                let p1 = Vec2f::new(center.x + outer * a.cos(), center.y + outer * a.sin());
+                self.draw_line(p0, p1, 1.0, text);
+            }
+        }
+
+        if indicator == GaugeIndicator::Needle {
+            let tip = Vec2f::new(center.x + (radius - thickness) * angle.cos(), center.y + (radius - thickness) * angle.sin());
+            self.draw_line(center, tip, 2.0, fill);
+            self.draw_circle(center, thickness / 2.0 + 1.0, fill);
+        }
+
+        // value label in the center for the arc mode, below the hub for the needle one
+        let label = format!("{:.*}", precision, value);
+        let font = self.style.font;
+        let tsize = self.atlas.get_text_size(font, label.as_str());
+        let y = match indicator {
+            GaugeIndicator::Arc => center.y as i32 - tsize.height / 2,
+            GaugeIndicator::Needle => (center.y + radius / 2.0) as i32 - tsize.height / 2,
+        };
+        self.draw_text(font, label.as_str(), vec2(center.x as i32 - tsize.width / 2, y), text);
+    }
+
+    pub fn gauge(&mut self, value: Real, low: Real, high: Real) {
+        self.gauge_ex(value, low, high, 11, GaugeIndicator::Arc, 0)
+    }
+}
@@ -0,0 +1,130 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// What two fingers did since the previous frame: `pan` is the movement of their middle point and `zoom` the
+// ratio between their distances, above 1 when they move apart.
+#[derive(Copy, Clone, Debug)]
+pub struct Gesture {
+    pub center: Vec2i,
+    pub pan: Vec2i,
+    pub zoom: Real,
+}
+
+// the two fingers followed by the recognizer, with their middle point and distance at the last frame
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TouchPair {
+    ids: (u64, u64),
+    center: Vec2i,
+    distance: Real,
+}
+
+fn pair_geometry(a: Vec2i, b: Vec2i) -> (Vec2i, Real) {
+    let center = vec2((a.x + b.x) / 2, (a.y + b.y) / 2);
+    let (dx, dy) = ((a.x - b.x) as Real, (a.y - b.y) as Real);
+    (center, (dx * dx + dy * dy).sqrt())
+}
+
+impl Input {
+    pub fn touch_down(&mut self, finger: u64, x: i32, y: i32) {
+        self.touches.retain(|(f, _)| *f != finger);
+        self.touches.push((finger, vec2(x, y)));
+    }
+
+    pub fn touch_move(&mut self, finger: u64, x: i32, y: i32) {
+        if let Some((_, p)) = self.touches.iter_mut().find(|(f, _)| *f == finger) {
+            *p = vec2(x, y);
+        }
+    }
+
+    pub fn touch_up(&mut self, finger: u64) {
+        self.touches.retain(|(f, _)| *f != finger);
+    }
+
+    // the two finger gesture of this frame, if two fingers are down
+    pub fn gesture(&self) -> Option<Gesture> {
+        self.gesture
+    }
+
+    // Turns the fingers down into a gesture, once per frame. A gesture starts with a zero pan and a zoom of 1
+    // so widgets can claim it before it moves anything.
+    pub(crate) fn recognize_gesture(&mut self) {
+        if self.touches.len() != 2 {
+            self.touch_pair = None;
+            self.gesture = None;
+            self.gesture_owner = None;
+            return;
+        }
+
+        let ((fa, a), (fb, b)) = (self.touches[0], self.touches[1]);
+        let (center, distance) = pair_geometry(a, b);
+        let (pan, zoom) = match self.touch_pair {
+            Some(p) if p.ids == (fa, fb) => {
+                let zoom = if p.distance > 0.0 { distance / p.distance } else { 1.0 };
+                (vec2(center.x - p.center.x, center.y - p.center.y), zoom)
+            }
+            _ => {
+                self.gesture_owner = None;
+                (vec2(0, 0), 1.0)
+            }
+        };
+        self.touch_pair = Some(TouchPair { ids: (fa, fb), center, distance });
+        self.gesture = Some(Gesture { center, pan, zoom });
+    }
+}
+
+impl Container {
+    // called by update_control for the widgets laid out with WidgetOption::GESTURES, the first one under the
+    // fingers keeps the gesture until they're lifted
+    pub(crate) fn claim_gesture(&mut self, id: Id, rect: Recti) {
+        let clip = self.get_clip_rect();
+        let mut input = self.input.borrow_mut();
+        if let (Some(g), None) = (input.gesture, input.gesture_owner) {
+            if rect.contains(&g.center) && clip.contains(&g.center) {
+                input.gesture_owner = Some(id);
+            }
+        }
+    }
+
+    // the gesture of this frame when `id` claimed it, see WidgetOption::GESTURES
+    pub fn gesture(&self, id: Id) -> Option<Gesture> {
+        let input = self.input.borrow();
+        input.gesture.filter(|_| input.gesture_owner == Some(id))
+    }
+
+    // a two finger pan over `body` nobody claimed, the content follows the fingers
+    pub(crate) fn gesture_scroll(&self, body: Recti) -> Vec2i {
+        let input = self.input.borrow();
+        match input.gesture {
+            Some(g) if input.gesture_owner.is_none() && body.contains(&g.center) && self.in_hover_root => vec2(-g.pan.x, -g.pan.y),
+            _ => vec2(0, 0),
+        }
+    }
+}
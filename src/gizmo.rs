@@ -0,0 +1,113 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// Overlay drawn on top of a viewport (typically a custom render widget). All the positions are in viewport
+// coordinates, i.e. relative to the top left corner of the overlay area, as produced by the host projection.
+pub struct GizmoOverlay<'a> {
+    container: &'a mut Container,
+    area: Recti,
+}
+
+impl<'a> GizmoOverlay<'a> {
+    fn to_screen(&self, p: Vec2f) -> Vec2f {
+        Vec2f::new(p.x + self.area.x as f32, p.y + self.area.y as f32)
+    }
+
+    pub fn area(&self) -> Recti {
+        self.area
+    }
+
+    pub fn container(&mut self) -> &mut Container {
+        self.container
+    }
+
+    pub fn line(&mut self, p0: Vec2f, p1: Vec2f, thickness: f32, color: Color) {
+        let (p0, p1) = (self.to_screen(p0), self.to_screen(p1));
+        self.container.draw_line(p0, p1, thickness, color);
+    }
+
+    // closed polyline around the projected silhouette/bounds of the selection
+    pub fn outline(&mut self, points: &[Vec2f], thickness: f32, color: Color) {
+        for i in 0..points.len() {
+            self.line(points[i], points[(i + 1) % points.len()], thickness, color);
+        }
+    }
+
+    // label box placed at `anchor + offset`, linked to the anchor by a leader line
+    pub fn label(&mut self, anchor: Vec2f, offset: Vec2i, text: &str) {
+        let font = self.container.style.font;
+        let padding = self.container.style.padding;
+        let tsize = self.container.atlas.get_text_size(font, text);
+        let a = self.to_screen(anchor);
+        let r = rect(a.x as i32 + offset.x, a.y as i32 + offset.y, tsize.width + padding * 2, tsize.height + padding * 2);
+
+        let text_color = self.container.style.colors[ControlColor::Text as usize];
+        let end = Vec2f::new((r.x + r.width / 2) as f32, (r.y + r.height / 2) as f32);
+        self.container.draw_line(a, end, 1.0, text_color);
+        self.container.draw_frame(r, ControlColor::WindowBG);
+        self.container.draw_text(font, text, vec2(r.x + padding, r.y + padding), text_color);
+    }
+
+    // Draggable square handle. The id is derived from `name` so handles keep their focus across frames.
+    // Returns ACTIVE while dragged (and CHANGE when it moved) with the mouse delta of the frame.
+    pub fn handle(&mut self, name: &str, pos: Vec2f, size: i32) -> (ResourceState, Vec2i) {
+        let id = self.container.idmngr.get_id_from_str(name);
+        let p = self.to_screen(pos);
+        let r = rect(p.x as i32 - size / 2, p.y as i32 - size / 2, size, size);
+        self.container.update_control(id, r, WidgetOption::NONE);
+
+        let mut res = ResourceState::NONE;
+        let mut delta = Vec2i::default();
+        if self.container.focus == Some(id) && self.container.input.borrow().mouse_down.is_left() {
+            res |= ResourceState::ACTIVE;
+            delta = self.container.input.borrow().mouse_delta;
+            if delta.x != 0 || delta.y != 0 {
+                res |= ResourceState::CHANGE;
+            }
+        }
+        self.container.draw_widget_frame(id, r, ControlColor::Button, WidgetOption::NONE);
+        (res, delta)
+    }
+}
+
+impl Container {
+    // rect of the last laid out cell, e.g. the area of a custom render widget
+    pub fn last_cell(&self) -> Recti {
+        self.layout.last_rect
+    }
+
+    pub fn gizmo_overlay<F: FnOnce(&mut GizmoOverlay)>(&mut self, area: Recti, f: F) {
+        self.push_clip_rect(area);
+        let mut overlay = GizmoOverlay { container: self, area };
+        f(&mut overlay);
+        self.pop_clip_rect();
+    }
+}
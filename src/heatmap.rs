@@ -0,0 +1,106 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Colormap {
+    Grayscale,
+    Hot,
+    Viridis,
+    Turbo,
+}
+
+impl Colormap {
+    fn stops(&self) -> &'static [[u8; 3]] {
+        match self {
+            Self::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+            Self::Hot => &[[0, 0, 0], [230, 0, 0], [255, 210, 0], [255, 255, 255]],
+            Self::Viridis => &[[68, 1, 84], [59, 82, 139], [33, 145, 140], [94, 201, 98], [253, 231, 37]],
+            Self::Turbo => &[[48, 18, 59], [70, 134, 251], [27, 229, 181], [164, 252, 60], [251, 185, 56], [228, 70, 11], [122, 4, 3]],
+        }
+    }
+
+    // t is expected in [0, 1], values outside are clamped
+    pub fn map(&self, t: Real) -> Color4b {
+        let stops = self.stops();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let pos = t * (stops.len() - 1) as Real;
+        let i = min(pos as usize, stops.len() - 2);
+        let f = pos - i as Real;
+        let lerp = |a: u8, b: u8| (a as Real + (b as Real - a as Real) * f) as u8;
+        let (a, b) = (stops[i], stops[i + 1]);
+        color4b(lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2]), 0xFF)
+    }
+}
+
+impl Container {
+    // Renders a `cols` x `rows` grid of values (row major) into the given slot. The grid is resampled to the
+    // slot size. Returns the cell (column, row, value) under the mouse, if any.
+    #[inline(never)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn heatmap(&mut self, slot: SlotId, data: &[Real], cols: usize, rows: usize, low: Real, high: Real, colormap: Colormap) -> Option<(usize, usize, Real)> {
+        assert!(data.len() >= cols * rows);
+        let id = self.idmngr.get_id_from_ptr(data);
+        let cell = self.layout.next();
+        self.update_control(id, cell, WidgetOption::NONE);
+        if cols == 0 || rows == 0 {
+            return None;
+        }
+
+        let slot_rect = self.atlas.get_slot_rect(slot);
+        let dst = rect(
+            cell.x + (cell.width - slot_rect.width) / 2,
+            cell.y + (cell.height - slot_rect.height) / 2,
+            slot_rect.width,
+            slot_rect.height,
+        );
+
+        // the slot callback receives atlas coordinates
+        let values: Rc<Vec<Real>> = Rc::new(data[..cols * rows].to_vec());
+        let range = if high > low { high - low } else { 1.0 };
+        let payload = Rc::new(move |x: usize, y: usize| {
+            let col = min((x - slot_rect.x as usize) * cols / slot_rect.width as usize, cols - 1);
+            let row = min((y - slot_rect.y as usize) * rows / slot_rect.height as usize, rows - 1);
+            colormap.map((values[col + row * cols] - low) / range)
+        });
+        self.draw_slot_with_function(slot, cell, color(0xFF, 0xFF, 0xFF, 0xFF), payload);
+
+        let mouse = self.input.borrow().mouse_pos;
+        if self.hover == Some(id) && dst.contains(&mouse) {
+            let col = min(((mouse.x - dst.x) as usize) * cols / dst.width as usize, cols - 1);
+            let row = min(((mouse.y - dst.y) as usize) * rows / dst.height as usize, rows - 1);
+            let value = data[col + row * cols];
+
+            self.tooltip(format!("{:.3}", value).as_str());
+            return Some((col, row, value));
+        }
+        None
+    }
+}
@@ -136,6 +136,11 @@ impl IdManager {
         self.id_stack.push(id);
     }
 
+    pub fn push_id_u32(&mut self, orig_id: u32) {
+        let id = self.get_id_u32(orig_id);
+        self.id_stack.push(id);
+    }
+
     pub fn push_id_from_str(&mut self, s: &str) {
         let id = self.get_id_from_str(s);
         self.id_stack.push(id);
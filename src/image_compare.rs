@@ -0,0 +1,127 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CompareMode {
+    Split,
+    OnlyA,
+    OnlyB,
+}
+
+#[derive(Clone)]
+pub struct ImageCompareState {
+    pub split: Real, // divider position, normalized over the image width
+    pub mode: CompareMode,
+    pub extras: Extras,
+}
+
+impl Default for ImageCompareState {
+    fn default() -> Self {
+        Self {
+            split: 0.5,
+            mode: CompareMode::Split,
+            extras: Extras::default(),
+        }
+    }
+}
+
+impl ImageCompareState {
+    // cycles Split -> A -> B -> Split
+    pub fn toggle(&mut self) {
+        self.mode = match self.mode {
+            CompareMode::Split => CompareMode::OnlyA,
+            CompareMode::OnlyA => CompareMode::OnlyB,
+            CompareMode::OnlyB => CompareMode::Split,
+        }
+    }
+}
+
+impl Container {
+    // Shows slot `a` on the left of the divider and slot `b` on its right. Both slots are expected to have the
+    // same size, they are drawn centered in the cell.
+    #[inline(never)]
+    pub fn image_compare(&mut self, state: &mut ImageCompareState, a: SlotId, b: SlotId) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let id = self.idmngr.get_id_from_ptr(state);
+        self.idmngr.push_id(id);
+        let toggle_id = self.idmngr.get_id_from_str("!compare_toggle");
+        self.idmngr.pop_id();
+        let cell = self.layout.next();
+        let size = self.atlas.get_slot_size(a);
+        let img = rect(cell.x + (cell.width - size.width) / 2, cell.y + (cell.height - size.height) / 2, size.width, size.height);
+        let white = color(0xFF, 0xFF, 0xFF, 0xFF);
+
+        // A/B toggle in the top left corner, it's updated first so it takes precedence over the divider
+        let label = match state.mode {
+            CompareMode::Split => "A|B",
+            CompareMode::OnlyA => "A",
+            CompareMode::OnlyB => "B",
+        };
+        let font = self.style.font;
+        let tsize = self.atlas.get_text_size(font, "A|B");
+        let toggle = rect(cell.x, cell.y, tsize.width + self.style.padding * 2, tsize.height + self.style.padding);
+        self.update_control(toggle_id, toggle, WidgetOption::NONE);
+        if self.is_clicked(toggle_id) {
+            state.toggle();
+            res |= ResourceState::CHANGE;
+        }
+
+        if self.hover != Some(toggle_id) && self.focus != Some(toggle_id) {
+            self.update_control(id, cell, WidgetOption::NONE);
+        }
+        if state.mode == CompareMode::Split && self.focus == Some(id) && self.input.borrow().mouse_down.is_left() && img.width > 0 {
+            let x = self.input.borrow().mouse_pos.x - img.x;
+            state.split = (x as Real / img.width as Real).clamp(0.0, 1.0);
+            res |= ResourceState::CHANGE;
+        }
+
+        let divider = img.x + (img.width as Real * state.split) as i32;
+        match state.mode {
+            CompareMode::OnlyA => self.draw_slot(a, cell, white),
+            CompareMode::OnlyB => self.draw_slot(b, cell, white),
+            CompareMode::Split => {
+                self.push_clip_rect(rect(cell.x, cell.y, divider - cell.x, cell.height));
+                self.draw_slot(a, cell, white);
+                self.pop_clip_rect();
+                self.push_clip_rect(rect(divider, cell.y, cell.x + cell.width - divider, cell.height));
+                self.draw_slot(b, cell, white);
+                self.pop_clip_rect();
+
+                let colorid = if self.focus == Some(id) { ControlColor::ButtonFocus } else { ControlColor::Button };
+                self.draw_rect(rect(divider - 1, img.y, 2, img.height), self.style.colors[colorid as usize]);
+            }
+        }
+
+        self.draw_widget_frame(toggle_id, toggle, ControlColor::Button, WidgetOption::NONE);
+        self.draw_control_text(label, toggle, ControlColor::Text, WidgetOption::ALIGN_CENTER);
+        res
+    }
+}
@@ -0,0 +1,205 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// An interactive widget reachable from the keyboard, in focus order
+#[derive(Clone)]
+pub struct FocusTarget {
+    pub window: WindowHandle,
+    pub id: Id,
+    pub rect: Recti,
+}
+
+pub(crate) struct KeyboardNav {
+    show_order: bool,
+    current: Option<(WindowHandle, Id)>,
+    suspended: bool, // a widget held the keyboard when the frame started (text boxes, ...)
+}
+
+impl Container {
+    // the interactive widgets visible in this container, then in its panels
+    fn collect_focus_targets(&self, window: &WindowHandle, out: &mut Vec<FocusTarget>) {
+        out.extend(self.focus_order.iter().map(|(id, r)| FocusTarget { window: window.clone(), id: *id, rect: *r }));
+        for p in &self.panels {
+            p.inner().collect_focus_targets(window, out);
+        }
+    }
+
+    fn holds_focus(&self) -> bool {
+        self.focus.is_some() || self.panels.iter().any(|p| p.inner().holds_focus())
+    }
+
+    fn release_focus(&mut self) {
+        self.set_focus(None);
+        for p in &mut self.panels {
+            p.inner_mut().release_focus();
+        }
+    }
+}
+
+// the closest target in the direction of `dir`, the offset across the direction counts double
+fn spatial_next(targets: &[FocusTarget], from: usize, dir: Vec2i) -> Option<usize> {
+    let center = |r: &Recti| vec2(r.x + r.width / 2, r.y + r.height / 2);
+    let origin = center(&targets[from].rect);
+    targets
+        .iter()
+        .enumerate()
+        .filter(|(i, t)| *i != from && t.window.ptr_eq(&targets[from].window))
+        .filter_map(|(i, t)| {
+            let c = center(&t.rect);
+            let along = (c.x - origin.x) * dir.x + (c.y - origin.y) * dir.y;
+            let across = ((c.x - origin.x) * dir.y + (c.y - origin.y) * dir.x).abs();
+            if along > 0 {
+                Some((i, along + across * 2))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|(_, d)| *d)
+        .map(|(i, _)| i)
+}
+
+impl<R: Renderer> Context<R> {
+    // Keyboard only mode: Tab and Shift+Tab walk the interactive widgets of every window (top most first),
+    // the arrow keys move to the closest widget in that direction and Enter or Space activate it. The
+    // navigation pauses while a widget keeps the focus, e.g. a text box being edited, Escape gives the
+    // keyboard back to the navigation and Ctrl+Tab also moves on to the next widget.
+    pub fn set_keyboard_navigation(&mut self, enabled: bool) {
+        self.keyboard_nav = if enabled { Some(KeyboardNav { show_order: true, current: None, suspended: false }) } else { None };
+    }
+
+    pub fn is_keyboard_navigation(&self) -> bool {
+        self.keyboard_nav.is_some()
+    }
+
+    // draws the focus order number next to every reachable widget, on by default
+    pub fn set_focus_order_visible(&mut self, visible: bool) {
+        if let Some(nav) = &mut self.keyboard_nav {
+            nav.show_order = visible;
+        }
+    }
+
+    // the widget the keyboard is on, if any
+    pub fn keyboard_target(&self) -> Option<(WindowHandle, Id)> {
+        self.keyboard_nav.as_ref().and_then(|nav| nav.current.clone())
+    }
+
+    // the interactive widgets laid out during the last frame, in focus order
+    pub fn focus_targets(&self) -> Vec<FocusTarget> {
+        let mut windows = self.root_list.clone();
+        windows.sort_by_key(|w| -w.zindex());
+        let mut targets = Vec::new();
+        for w in &windows {
+            w.inner().main.collect_focus_targets(w, &mut targets);
+        }
+        targets
+    }
+
+    pub(crate) fn keyboard_nav_begin(&mut self) {
+        let held = self.root_list.iter().any(|w| w.inner().main.holds_focus());
+        if let Some(nav) = &mut self.keyboard_nav {
+            nav.suspended = held;
+        }
+    }
+
+    pub(crate) fn keyboard_nav_end(&mut self) {
+        let Some(mut nav) = self.keyboard_nav.take() else {
+            return;
+        };
+
+        let targets = self.focus_targets();
+        let mut current = nav.current.as_ref().and_then(|(w, id)| targets.iter().position(|t| t.window.ptr_eq(w) && t.id == *id));
+        let keys = self.input.borrow().key_pressed;
+        let shift = self.input.borrow().key_down.intersects(KeyMode::SHIFT);
+        let ctrl = self.input.borrow().key_down.intersects(KeyMode::CTRL);
+        if nav.suspended && (keys.is_escape() || (ctrl && keys.intersects(KeyMode::TAB))) {
+            for w in &mut self.root_list {
+                w.inner_mut().main.release_focus();
+            }
+            nav.suspended = keys.is_escape();
+        }
+        if !nav.suspended && !targets.is_empty() {
+            let count = targets.len();
+            if keys.intersects(KeyMode::TAB) {
+                current = Some(match (current, shift) {
+                    (None, false) => 0,
+                    (None, true) => count - 1,
+                    (Some(i), false) => (i + 1) % count,
+                    (Some(i), true) => (i + count - 1) % count,
+                });
+            } else if let Some(from) = current {
+                let dir = if keys.is_left() {
+                    Some(vec2(-1, 0))
+                } else if keys.is_right() {
+                    Some(vec2(1, 0))
+                } else if keys.is_up() {
+                    Some(vec2(0, -1))
+                } else if keys.is_down() {
+                    Some(vec2(0, 1))
+                } else {
+                    None
+                };
+                if let Some(next) = dir.and_then(|d| spatial_next(&targets, from, d)) {
+                    current = Some(next);
+                }
+                if keys.is_return() || keys.intersects(KeyMode::SPACE) {
+                    let t = &targets[from];
+                    let mut window = t.window.clone();
+                    let mut inner = window.inner_mut();
+                    inner.main.focus_widget(t.id);
+                    inner.main.activate_widget(t.id);
+                }
+            }
+        }
+        nav.current = current.map(|i| (targets[i].window.clone(), targets[i].id));
+
+        let show_order = nav.show_order;
+        self.overlay_layer(|o| {
+            let font = o.style.font;
+            let text_color = o.style.color(ControlColor::Text);
+            let accent = o.style.color(ControlColor::Accent);
+            if show_order {
+                for (i, t) in targets.iter().enumerate() {
+                    let label = format!("{}", i + 1);
+                    let size = o.atlas.get_text_size(font, &label);
+                    let r = rect(t.rect.x, t.rect.y, size.width + 4, size.height);
+                    o.draw_rect(r, o.style.color(ControlColor::WindowBG));
+                    o.draw_text(font, &label, vec2(r.x + 2, r.y), if current == Some(i) { accent } else { text_color });
+                }
+            }
+            if let Some(i) = current {
+                let r = targets[i].rect;
+                o.draw_box(rect(r.x - 1, r.y - 1, r.width + 2, r.height + 2), accent);
+                o.draw_box(rect(r.x - 2, r.y - 2, r.width + 4, r.height + 4), accent);
+            }
+        });
+        self.keyboard_nav = Some(nav);
+    }
+}
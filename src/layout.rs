@@ -51,12 +51,75 @@
 // IN THE SOFTWARE.
 //
 use super::*;
+use std::collections::HashMap;
+
+// row width of the cells sized by `SizePolicy::Auto`
+pub(crate) const AUTO_WIDTH: i32 = i32::MIN;
+
+// How a cell of a row gets its width
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SizePolicy {
+    // width in pixels, 0 takes the default cell width
+    Fixed(i32),
+    // what is left of the row minus the given pixels
+    Remainder(i32),
+    // the preferred width of the widget: text of a label, content of a button, size of an image. Widgets
+    // without one take the default cell width.
+    Auto,
+}
+
+impl SizePolicy {
+    // the encoding of the row widths: 0 is the default width and negative values are relative to the right edge
+    pub(crate) fn row_width(self) -> i32 {
+        match self {
+            SizePolicy::Fixed(w) => max(w, 0),
+            // i32::MAX would land on AUTO_WIDTH
+            SizePolicy::Remainder(m) => -m.clamp(0, i32::MAX - 1) - 1,
+            SizePolicy::Auto => AUTO_WIDTH,
+        }
+    }
+}
+
+// Space between a cell and the widget placed in it. Negative values grow the widget over the spacing,
+// e.g. `-style.spacing` on the side facing a neighbour makes them flush.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Margin {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Margin {
+    pub const ZERO: Margin = Margin { left: 0, top: 0, right: 0, bottom: 0 };
+
+    pub fn all(px: i32) -> Self {
+        Self { left: px, top: px, right: px, bottom: px }
+    }
+
+    pub fn symmetric(horizontal: i32, vertical: i32) -> Self {
+        Self { left: horizontal, top: vertical, right: horizontal, bottom: vertical }
+    }
+}
+
+// Where the cells of a row go when they don't fill it
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum RowAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    // the free space is spread between the cells
+    SpaceBetween,
+}
 
 #[derive(Clone, Default)]
 struct Row {
     start: usize,
     len: usize,
     item_index: usize,
+    align: RowAlign,
+    gap: i32,
 }
 
 #[derive(Default, Copy, Clone)]
@@ -70,6 +133,27 @@ pub struct Layout {
     pub indent: i32,
 }
 
+// the state `next` leaves behind, replayed instead of computed for cached subtrees
+#[derive(Clone, Copy)]
+struct LayoutStep {
+    rect: Recti,
+    top: Layout,
+    item_index: usize,
+    auto_width: Option<i32>, // preferred width of an auto cell, the cell changes with it
+}
+
+#[derive(Clone)]
+struct CachedLayout {
+    key: Vec<i32>,
+    steps: Vec<LayoutStep>,
+}
+
+#[derive(Clone)]
+enum CacheMode {
+    Record(Id, Vec<i32>, Vec<LayoutStep>),
+    Replay(Id, usize),
+}
+
 #[derive(Clone, Default)]
 pub(crate) struct LayoutManager {
     pub style: Style,
@@ -80,9 +164,24 @@ pub(crate) struct LayoutManager {
 
     pub current_row_widths: Vec<i32>,
     pub item_index: usize,
+    row_align: RowAlign,
+    row_gap: i32, // extra spacing between the cells of the current grid line
+    pub cell_margin: Margin,
+    // widths of the auto cells of the aligned rows during the last frame, by depth and position of the row,
+    // and the ones measured during this frame
+    last_auto_widths: HashMap<(usize, i32, i32), Vec<i32>>,
+    auto_widths: HashMap<(usize, i32, i32), Vec<i32>>,
+
+    caches: HashMap<Id, CachedLayout>,
+    cache_mode: Option<CacheMode>,
 }
 
 impl LayoutManager {
+    // the rows that weren't laid out during the frame that ended are forgotten
+    pub fn begin_frame(&mut self) {
+        self.last_auto_widths = std::mem::take(&mut self.auto_widths);
+    }
+
     pub fn push_layout(&mut self, body: Recti, scroll: Vec2i) {
         let mut layout: Layout = Layout {
             body: Recti {
@@ -132,6 +231,8 @@ impl LayoutManager {
             start: self.row_stack.len(),
             len: self.current_row_widths.len(),
             item_index: self.item_index,
+            align: self.row_align,
+            gap: self.row_gap,
         };
         for i in 0..self.current_row_widths.len() {
             self.row_widths_stack.push(self.current_row_widths[i]);
@@ -154,6 +255,8 @@ impl LayoutManager {
         self.row_widths_stack
             .shrink_to(self.row_widths_stack.len() - row.len);
         self.item_index = row.item_index;
+        self.row_align = row.align;
+        self.row_gap = row.gap;
 
         let a = self.top_mut();
         a.position.x = if a.position.x > b.position.x + b.body.x - a.body.x {
@@ -172,6 +275,33 @@ impl LayoutManager {
         a.max.y = max(a.max.y, b.max.y);
     }
 
+    // Lays out inside an arbitrary rectangle without advancing the parent layout (overlays, pages, ...)
+    pub fn begin_region(&mut self, region: Recti) {
+        let row = Row {
+            start: self.row_widths_stack.len(),
+            len: self.current_row_widths.len(),
+            item_index: self.item_index,
+            align: self.row_align,
+            gap: self.row_gap,
+        };
+        self.row_widths_stack.extend_from_slice(&self.current_row_widths);
+        self.current_row_widths.clear();
+        self.item_index = 0;
+        self.row_stack.push(row);
+        self.push_layout(region, vec2(0, 0));
+    }
+
+    pub fn end_region(&mut self) {
+        self.stack.pop();
+        let row = self.row_stack.pop().unwrap();
+        self.current_row_widths.clear();
+        self.current_row_widths.extend_from_slice(&self.row_widths_stack[row.start..row.start + row.len]);
+        self.row_widths_stack.truncate(row.start);
+        self.item_index = row.item_index;
+        self.row_align = row.align;
+        self.row_gap = row.gap;
+    }
+
     fn row_for_layout(&mut self, height: i32) {
         let layout = self.top_mut();
         layout.position = vec2(layout.indent, layout.next_row);
@@ -184,9 +314,53 @@ impl LayoutManager {
         for i in 0..widths.len() {
             self.current_row_widths.push(widths[i]);
         }
+        self.row_align = RowAlign::Start;
         self.row_for_layout(height);
     }
 
+    // aligns the cells of the current row, until the next call to `row`
+    pub fn set_row_align(&mut self, align: RowAlign) {
+        self.row_align = align;
+    }
+
+    fn auto_widths_key(&self) -> (usize, i32, i32) {
+        let t = self.top();
+        (self.stack.len(), t.indent, t.position.y)
+    }
+
+    // Shifts the first cell of a grid line and spreads the free space for the row alignment. The auto cells
+    // are sized by their widget once it's called, their width is taken from the previous frame.
+    fn align_line(&mut self) {
+        self.row_gap = 0;
+        let count = self.current_row_widths.len();
+        if self.row_align == RowAlign::Start || count == 0 {
+            return;
+        }
+        let dcell_width = self.style.default_cell_size.width + self.style.padding * 2;
+        let autos = self.last_auto_widths.get(&self.auto_widths_key());
+        let mut total = self.style.spacing * (count as i32 - 1);
+        for (i, w) in self.current_row_widths.iter().enumerate() {
+            total += match *w {
+                AUTO_WIDTH => autos.and_then(|a| a.get(i)).copied().unwrap_or(dcell_width),
+                0 => dcell_width,
+                // a remainder cell fills the row
+                w if w < 0 => return,
+                w => w,
+            };
+        }
+        let free = self.top().body.width - self.top().indent - total;
+        if free <= 0 {
+            return;
+        }
+        match self.row_align {
+            RowAlign::Start => (),
+            RowAlign::Center => self.top_mut().position.x += free / 2,
+            RowAlign::End => self.top_mut().position.x += free,
+            RowAlign::SpaceBetween if count > 1 => self.row_gap = free / (count as i32 - 1),
+            RowAlign::SpaceBetween => (),
+        }
+    }
+
     pub fn set_width(&mut self, width: i32) {
         self.top_mut().size.width = width;
     }
@@ -195,7 +369,126 @@ impl LayoutManager {
         self.top_mut().size.height = height;
     }
 
+    // rect the next call to `next` would return, without consuming it
+    pub fn peek(&mut self) -> Recti {
+        let top = *self.top();
+        let (item_index, last_rect) = (self.item_index, self.last_rect);
+        let mode = self.cache_mode.clone();
+        let res = self.next();
+        *self.top_mut() = top;
+        self.item_index = item_index;
+        self.last_rect = last_rect;
+        self.cache_mode = mode;
+        res
+    }
+
+    // everything the cells of a subtree depend on, besides its code
+    pub(crate) fn cache_key(&self) -> Vec<i32> {
+        let t = self.top();
+        let s = &self.style;
+        let mut key = vec![
+            t.body.x,
+            t.body.y,
+            t.body.width,
+            t.body.height,
+            t.position.x,
+            t.position.y,
+            t.size.width,
+            t.size.height,
+            t.next_row,
+            t.indent,
+            self.item_index as i32,
+            s.default_cell_size.width,
+            s.default_cell_size.height,
+            s.padding,
+            s.spacing,
+            self.row_align as i32,
+        ];
+        key.extend_from_slice(&self.current_row_widths);
+        key
+    }
+
+    // Starts a subtree whose cells are replayed from the previous frame when the key didn't change.
+    // Returns false while another cached subtree is open, the nested one is computed normally.
+    pub fn begin_cached(&mut self, id: Id) -> bool {
+        if self.cache_mode.is_some() {
+            return false;
+        }
+        let key = self.cache_key();
+        self.cache_mode = match self.caches.get(&id) {
+            Some(cached) if cached.key == key => Some(CacheMode::Replay(id, 0)),
+            _ => Some(CacheMode::Record(id, key, Vec::new())),
+        };
+        true
+    }
+
+    pub fn end_cached(&mut self) {
+        match self.cache_mode.take() {
+            Some(CacheMode::Record(id, key, steps)) => {
+                self.caches.insert(id, CachedLayout { key, steps });
+            }
+            // fewer cells than recorded, the subtree changed
+            Some(CacheMode::Replay(id, index)) if self.caches.get(&id).map(|c| c.steps.len()) != Some(index) => {
+                self.caches.remove(&id);
+            }
+            _ => (),
+        }
+    }
+
     pub fn next(&mut self) -> Recti {
+        self.next_sized(Dimensioni::new(0, 0))
+    }
+
+    // `preferred` is the size of the widget content, it gives the width of the auto cells
+    pub fn next_sized(&mut self, preferred: Dimensioni) -> Recti {
+        let m = self.cell_margin;
+        if m == Margin::ZERO {
+            return self.next_cell(preferred);
+        }
+        let preferred = if preferred.width > 0 { Dimensioni::new(preferred.width + m.left + m.right, preferred.height + m.top + m.bottom) } else { preferred };
+        let r = self.next_cell(preferred);
+        self.last_rect = rect(r.x + m.left, r.y + m.top, max(r.width - m.left - m.right, 0), max(r.height - m.top - m.bottom, 0));
+        self.last_rect
+    }
+
+    fn next_cell(&mut self, preferred: Dimensioni) -> Recti {
+        if let Some(CacheMode::Replay(id, index)) = self.cache_mode {
+            match self.caches.get(&id).and_then(|c| c.steps.get(index)).copied() {
+                Some(step) if step.auto_width.is_none_or(|w| w == preferred.width) => {
+                    *self.top_mut() = step.top;
+                    self.item_index = step.item_index;
+                    self.last_rect = step.rect;
+                    self.cache_mode = Some(CacheMode::Replay(id, index + 1));
+                    return step.rect;
+                }
+                // more cells than recorded or different content, computed from here on and recorded again
+                // next frame
+                _ => {
+                    self.caches.remove(&id);
+                    self.cache_mode = None;
+                }
+            }
+        }
+        let auto = self.current_row_widths.get(self.next_item_index()) == Some(&AUTO_WIDTH);
+        let res = self.compute_next(preferred);
+        if let Some(CacheMode::Record(_, _, steps)) = &mut self.cache_mode {
+            let top = *self.stack.last().unwrap();
+            let auto_width = if auto { Some(preferred.width) } else { None };
+            steps.push(LayoutStep { rect: res, top, item_index: self.item_index, auto_width });
+        }
+        res
+    }
+
+    // index in the row widths of the cell `compute_next` returns
+    fn next_item_index(&self) -> usize {
+        if self.item_index == self.current_row_widths.len() {
+            0
+        } else {
+            self.item_index
+        }
+    }
+
+    fn compute_next(&mut self, preferred: Dimensioni) -> Recti {
         let dcell_size = self.style.default_cell_size;
         let padding = self.style.padding;
         let spacing = self.style.spacing;
@@ -214,6 +507,10 @@ impl LayoutManager {
         if self.item_index == row_cells_count {
             self.row_for_layout(lsize_y);
         }
+        if self.item_index == 0 {
+            self.align_line();
+        }
+        let key = self.auto_widths_key();
 
         res.x = self.top().position.x;
         res.y = self.top().position.y;
@@ -224,6 +521,14 @@ impl LayoutManager {
         };
         res.height = self.top().size.height;
 
+        if res.width == AUTO_WIDTH {
+            res.width = max(preferred.width, 0);
+            if self.row_align != RowAlign::Start {
+                let widths = self.auto_widths.entry(key).or_default();
+                widths.resize(max(widths.len(), self.item_index + 1), 0);
+                widths[self.item_index] = if res.width > 0 { res.width } else { dcell_size.width + padding * 2 };
+            }
+        }
         if res.width == 0 {
             res.width = dcell_size.width + padding * 2;
         }
@@ -245,7 +550,7 @@ impl LayoutManager {
         ///////////
         // update the next position/row/body/max/...
         ////////
-        self.top_mut().position.x += res.width + spacing;
+        self.top_mut().position.x += res.width + spacing + self.row_gap;
         self.top_mut().next_row = if self.top().next_row > res.y + res.height + spacing {
             self.top().next_row
         } else {
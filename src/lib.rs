@@ -58,28 +58,149 @@ use std::{
     sync::Arc,
 };
 
+mod about_dialog;
+mod animated_image;
+mod arena;
 mod atlas;
+mod atlas_view;
+mod background;
+mod binding;
+mod calendar;
+mod carousel;
 mod canvas;
+mod clipboard;
+mod columns;
+mod combo;
 mod container;
+#[cfg(feature = "design_tokens")]
+mod design_tokens;
+mod drawer;
+mod editable_label;
+mod extras;
 mod file_dialog;
+mod flash;
+mod form;
+mod gauge;
+mod gesture;
+mod gizmo;
+mod heatmap;
 mod idmngr;
+mod image_compare;
+mod keyboard_nav;
 mod layout;
+mod list_box;
+#[cfg(feature = "lua")]
+mod lua;
+mod meter;
+mod metrics;
+mod mnemonic;
+mod model_version;
+mod presentation;
+mod rect_math;
 mod rect_packer;
+#[cfg(feature = "remote")]
+mod remote;
+mod render_order;
+mod retained;
+mod safe_area;
+mod search;
+mod shared_input;
+mod side_panel;
+mod splash;
+mod spotlight;
+mod style_diff;
+mod style_editor;
+mod style_profile;
+mod svg_export;
+mod telemetry;
+mod text_area;
+mod theme;
+mod text_edit;
+mod text_menu;
+mod tree_table;
+mod undo;
+mod video;
+#[cfg(feature = "ui_desc")]
+mod ui_desc;
+mod ui_path;
+mod widget;
+mod widget_event;
 mod window;
+mod window_anim;
+mod wizard;
 
+pub use about_dialog::*;
+pub use animated_image::*;
+pub use arena::*;
 pub use atlas::*;
+pub(crate) use atlas_view::*;
+pub use background::*;
+pub use binding::*;
+pub use carousel::*;
 pub use canvas::*;
+pub use clipboard::*;
+pub use columns::*;
+pub use combo::*;
 pub use container::*;
+#[cfg(feature = "design_tokens")]
+pub use design_tokens::*;
+pub use drawer::*;
+pub use editable_label::*;
+pub use extras::*;
 pub use idmngr::*;
+pub use image_compare::*;
+pub use keyboard_nav::*;
 pub use layout::*;
+#[cfg(feature = "lua")]
+pub use lua::*;
+pub use meter::*;
+pub use mnemonic::*;
+pub(crate) use metrics::*;
+pub(crate) use presentation::*;
+pub use model_version::*;
+pub use rect_math::*;
 pub use rect_packer::*;
+#[cfg(feature = "remote")]
+pub use remote::*;
+pub use render_order::*;
+pub(crate) use retained::*;
 pub use rs_math3d::*;
+pub(crate) use safe_area::*;
+pub use search::*;
+pub use telemetry::*;
+pub use text_area::*;
+pub use text_edit::*;
+pub use text_menu::*;
+pub use theme::*;
+pub use shared_input::*;
+pub use side_panel::*;
+pub use splash::*;
+pub use spotlight::*;
+pub use style_diff::*;
+pub use style_editor::*;
+pub use style_profile::*;
+pub use tree_table::*;
+pub use undo::*;
+pub use video::*;
+#[cfg(feature = "ui_desc")]
+pub use ui_desc::*;
+pub use ui_path::*;
+pub use widget::*;
+pub use widget_event::*;
 pub use window::*;
+pub(crate) use window_anim::*;
+pub use wizard::*;
 pub use file_dialog::*;
+pub use form::*;
+pub use gauge::*;
+pub use gesture::*;
+pub use gizmo::*;
+pub use heatmap::*;
 
 use bitflags::*;
 use std::cmp::{max, min};
 use std::sync::RwLock;
+use std::time::Instant;
 
 #[derive(Debug, Copy, Clone)]
 pub enum ButtonState {
@@ -98,11 +219,57 @@ pub enum MouseEvent {
     Scroll(f32),
 }
 
+// Rounded rectangle outside of which nothing is drawn, a radius of half the smallest side gives a circle
+#[derive(Copy, Clone)]
+pub struct ClipMask {
+    pub rect: Recti,
+    pub radius: i32,
+}
+
+impl PartialEq for ClipMask {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.rect, other.rect);
+        a.x == b.x && a.y == b.y && a.width == b.width && a.height == b.height && self.radius == other.radius
+    }
+}
+
 pub trait Renderer {
     fn get_atlas(&self) -> AtlasHandle;
     fn begin(&mut self, width: i32, height: i32, clr: Color);
     fn push_quad_vertices(&mut self, v0: &Vertex, v1: &Vertex, v2: &Vertex, v3: &Vertex);
+    // a run of quads, 4 vertices each, backends can override it to copy the whole run at once
+    fn push_quads(&mut self, vertices: &[Vertex]) {
+        for q in vertices.chunks_exact(4) {
+            self.push_quad_vertices(&q[0], &q[1], &q[2], &q[3]);
+        }
+    }
     fn flush(&mut self);
+    // called after a flush, backends without masking ignore it and only the rectangular clip applies
+    fn set_mask(&mut self, _mask: Option<ClipMask>) {}
+    // called after a flush when a slot with a different sampling is drawn, backends ignoring it keep NEAREST
+    fn set_sampler(&mut self, _sampler: Sampler) {}
+    // Converts a video frame on the GPU into the `dst` area of the atlas texture. Returning false lets the
+    // frame be converted on the CPU into the atlas pixels. Backends converting it must keep the area when the
+    // atlas gets uploaded again.
+    fn upload_yuv420(&mut self, _dst: Recti, _frame: &Yuv420Frame) -> bool {
+        false
+    }
+    // Backends returning true clip with `set_scissor`, the canvas then stops clipping the vertices
+    fn hardware_clip(&self) -> bool {
+        false
+    }
+    // Called after a flush when the clip changes (hardware clipping only) and around custom renders,
+    // `None` is the whole target. The rect is in UI coordinates, y going down.
+    fn set_scissor(&mut self, _rect: Option<Recti>) {}
+    // the area drawn into: the whole target when the frame begins, the widget during a custom render
+    fn set_viewport(&mut self, _rect: Recti) {}
+    // Redirects the draws into an offscreen target `scale` times the size of `area`, the vertices keep their
+    // UI coordinates. Returning false draws them directly, without supersampling.
+    fn begin_scaled_layer(&mut self, _area: Recti, _scale: Real) -> bool {
+        false
+    }
+    // filters the offscreen target down over the area given to `begin_scaled_layer`
+    fn end_scaled_layer(&mut self) {}
     fn end(&mut self);
 }
 
@@ -142,7 +309,15 @@ pub enum Clip {
 #[derive(PartialEq, Copy, Clone)]
 #[repr(u32)]
 pub enum ControlColor {
-    Max = 14,
+    Max = 22,
+    GridLine = 21,
+    RowAlt = 20, // drawn over every other row, keep it translucent
+    SelectionBG = 19,
+    Disabled = 18,
+    Error = 17,
+    Warning = 16,
+    Success = 15,
+    Accent = 14,
     ScrollThumb = 13,
     ScrollBase = 12,
     BaseFocus = 11,
@@ -206,6 +381,8 @@ impl ResourceState {
 bitflags! {
         #[derive(Copy, Clone)]
     pub struct ContainerOption : u32 {
+        const MAXIMIZE = 2048;
+        const CLICK_THROUGH = 1024;
         const AUTO_SIZE = 512;
         const NO_TITLE = 128;
         const NO_CLOSE = 64;
@@ -218,6 +395,10 @@ bitflags! {
 
     #[derive(Copy, Clone)]
     pub struct WidgetOption : u32 {
+        const MNEMONIC = 4096; // "&File" in the label is shown as File and pressed with Alt+F
+        const GESTURES = 2048;
+        const ALIGN_DECIMAL = 1024;
+        const ALIGN_NUMERIC = 512;
         const HOLD_FOCUS = 256;
         const NO_SCROLL = 32;
         const NO_INTERACT = 4;
@@ -272,17 +453,31 @@ impl ContainerOption {
     pub fn has_no_frame(&self) -> bool {
         self.intersects(Self::NO_FRAME)
     }
+    pub fn is_click_through(&self) -> bool {
+        self.intersects(Self::CLICK_THROUGH)
+    }
+    pub fn has_maximize(&self) -> bool {
+        self.intersects(Self::MAXIMIZE)
+    }
 }
 
 impl WidgetOption {
     pub fn is_holding_focus(&self) -> bool {
         self.intersects(WidgetOption::HOLD_FOCUS)
     }
+    pub fn has_mnemonic(&self) -> bool {
+        self.intersects(Self::MNEMONIC)
+    }
 
     pub fn has_no_scroll(&self) -> bool {
         self.intersects(WidgetOption::NO_SCROLL)
     }
 
+    // the widget takes the two finger gestures starting over it, see Container::gesture
+    pub fn wants_gestures(&self) -> bool {
+        self.intersects(WidgetOption::GESTURES)
+    }
+
     pub fn is_not_interactive(&self) -> bool {
         self.intersects(WidgetOption::NO_INTERACT)
     }
@@ -292,6 +487,12 @@ impl WidgetOption {
     pub fn is_aligned_center(&self) -> bool {
         self.intersects(WidgetOption::ALIGN_CENTER)
     }
+    pub fn is_aligned_numeric(&self) -> bool {
+        self.intersects(WidgetOption::ALIGN_NUMERIC)
+    }
+    pub fn is_aligned_decimal(&self) -> bool {
+        self.intersects(WidgetOption::ALIGN_DECIMAL)
+    }
     pub fn is_none(&self) -> bool {
         self.bits() == 0
     }
@@ -325,7 +526,18 @@ impl MouseButton {
 bitflags! {
     #[derive(Copy, Clone, Debug)]
     pub struct KeyMode : u32 {
+        const ESCAPE = 16384;
+        const SPACE = 8192;
+        const TAB = 4096;
+        const DOWN = 2048;
+        const UP = 1024;
+        const RIGHT = 512;
+        const LEFT = 256;
+        const END = 128;
+        const HOME = 64;
+        const DELETE = 32;
         const RETURN = 16;
+        const REPEATABLE = Self::BACKSPACE.bits() | Self::DELETE.bits() | Self::LEFT.bits() | Self::RIGHT.bits() | Self::UP.bits() | Self::DOWN.bits();
         const BACKSPACE = 8;
         const ALT = 4;
         const CTRL = 2;
@@ -344,6 +556,27 @@ impl KeyMode {
     pub fn is_backspace(&self) -> bool {
         self.intersects(Self::BACKSPACE)
     }
+    pub fn is_delete(&self) -> bool {
+        self.intersects(Self::DELETE)
+    }
+    pub fn is_home(&self) -> bool {
+        self.intersects(Self::HOME)
+    }
+    pub fn is_end(&self) -> bool {
+        self.intersects(Self::END)
+    }
+    pub fn is_left(&self) -> bool {
+        self.intersects(Self::LEFT)
+    }
+    pub fn is_right(&self) -> bool {
+        self.intersects(Self::RIGHT)
+    }
+    pub fn is_up(&self) -> bool {
+        self.intersects(Self::UP)
+    }
+    pub fn is_down(&self) -> bool {
+        self.intersects(Self::DOWN)
+    }
     pub fn is_alt(&self) -> bool {
         self.intersects(Self::ALT)
     }
@@ -353,6 +586,39 @@ impl KeyMode {
     pub fn is_shift(&self) -> bool {
         self.intersects(Self::SHIFT)
     }
+    pub fn is_escape(&self) -> bool {
+        self.intersects(Self::ESCAPE)
+    }
+}
+
+// what a platform wheel delta counts: notched wheels report lines, touchpads and precise wheels pixels
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScrollUnit {
+    Lines,
+    Pixels,
+}
+
+// How wheel deltas turn into scrolled pixels. The platform convention is kept by default: a positive y
+// (wheel pushed away) scrolls towards the top, a positive x towards the right.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScrollConfig {
+    pub line_height: Real, // pixels per wheel line
+    pub speed: Real,       // multiplier applied to both units
+    pub invert_x: bool,
+    pub invert_y: bool,
+}
+
+impl Default for ScrollConfig {
+    fn default() -> Self {
+        Self { line_height: 30.0, speed: 1.0, invert_x: false, invert_y: false }
+    }
+}
+
+impl ScrollConfig {
+    // "natural" scrolling, the content follows the fingers
+    pub fn natural() -> Self {
+        Self { invert_x: true, invert_y: true, ..Self::default() }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -361,12 +627,35 @@ pub struct Input {
     last_mouse_pos: Vec2i,
     mouse_delta: Vec2i,
     scroll_delta: Vec2i,
+    scroll_lines: Vec2f,  // wheel deltas received since the last frame, converted in the prelude
+    scroll_pixels: Vec2f,
+    scroll_fraction: Vec2f, // sub pixel leftover of the precise deltas
+    scroll_config: ScrollConfig,
     rel_mouse_pos: Vec2i,
     mouse_down: MouseButton,
     mouse_pressed: MouseButton,
     key_down: KeyMode,
     key_pressed: KeyMode,
     input_text: String,
+    last_frame: Option<Instant>,
+    repeat_timer: Real,
+    repeat_delay: Real,
+    repeat_interval: Real,
+    mouse_double_clicked: MouseButton,
+    last_click: Option<(Instant, Vec2i, MouseButton)>,
+    double_click_time: Real,
+    // text menu traffic, see text_menu.rs
+    text_menu_request: Option<(Id, bool)>,
+    text_action: Option<(Id, TextAction)>,
+    text_action_next: Option<(Id, TextAction)>,
+    clipboard_out: Option<String>,
+    events: Vec<WidgetEvent>,
+    frame_dt: Real,
+    touches: Vec<(u64, Vec2i)>, // fingers down, in touch order
+    touch_pair: Option<TouchPair>,
+    gesture: Option<Gesture>,
+    gesture_owner: Option<Id>,
+    mnemonic: Option<char>, // pressed with Alt, until a widget takes it
 }
 
 impl Default for Input {
@@ -377,11 +666,33 @@ impl Default for Input {
             mouse_delta: Vec2i::default(),
             rel_mouse_pos: Vec2i::default(),
             scroll_delta: Vec2i::default(),
+            scroll_lines: Vec2f::default(),
+            scroll_pixels: Vec2f::default(),
+            scroll_fraction: Vec2f::default(),
+            scroll_config: ScrollConfig::default(),
             mouse_down: MouseButton::NONE,
             mouse_pressed: MouseButton::NONE,
             key_down: KeyMode::NONE,
             key_pressed: KeyMode::NONE,
             input_text: String::default(),
+            last_frame: None,
+            repeat_timer: 0.0,
+            repeat_delay: 0.4,
+            repeat_interval: 1.0 / 25.0,
+            mouse_double_clicked: MouseButton::NONE,
+            last_click: None,
+            double_click_time: 0.4,
+            text_menu_request: None,
+            text_action: None,
+            text_action_next: None,
+            clipboard_out: None,
+            events: Vec::new(),
+            frame_dt: 0.0,
+            touches: Vec::new(),
+            touch_pair: None,
+            gesture: None,
+            gesture_owner: None,
+            mnemonic: None,
         }
     }
 }
@@ -391,6 +702,15 @@ impl Input {
         self.rel_mouse_pos
     }
 
+    pub fn mouse_pos(&self) -> Vec2i {
+        self.mouse_pos
+    }
+
+    // movement of the mouse since the previous frame
+    pub fn mouse_delta(&self) -> Vec2i {
+        self.mouse_delta
+    }
+
     pub fn mousemove(&mut self, x: i32, y: i32) {
         self.mouse_pos = vec2(x, y);
     }
@@ -403,6 +723,28 @@ impl Input {
         self.mousemove(x, y);
         self.mouse_down |= btn;
         self.mouse_pressed |= btn;
+
+        // a second press of the same button close in time and space is a double click, a third one starts over
+        let now = Instant::now();
+        let pos = vec2(x, y);
+        match self.last_click {
+            Some((t, p, b))
+                if b.bits() == btn.bits() && (now - t).as_secs_f32() <= self.double_click_time && (p.x - x).abs() <= 4 && (p.y - y).abs() <= 4 =>
+            {
+                self.mouse_double_clicked |= btn;
+                self.last_click = None;
+            }
+            _ => self.last_click = Some((now, pos, btn)),
+        }
+    }
+
+    pub fn mouse_double_clicked(&self) -> MouseButton {
+        self.mouse_double_clicked
+    }
+
+    // maximum delay between the two presses of a double click, in seconds
+    pub fn set_double_click_time(&mut self, time: Real) {
+        self.double_click_time = time;
     }
 
     pub fn mouseup(&mut self, x: i32, y: i32, btn: MouseButton) {
@@ -410,11 +752,44 @@ impl Input {
         self.mouse_down &= !btn;
     }
 
-    pub fn scroll(&mut self, x: i32, y: i32) {
+    // A wheel delta as reported by the platform, see `ScrollConfig` for the direction and the scaling
+    pub fn scroll(&mut self, x: Real, y: Real, unit: ScrollUnit) {
+        let acc = match unit {
+            ScrollUnit::Lines => &mut self.scroll_lines,
+            ScrollUnit::Pixels => &mut self.scroll_pixels,
+        };
+        acc.x += x;
+        acc.y += y;
+    }
+
+    // scrolls the content by an exact amount of pixels, bypassing the scroll configuration
+    pub fn scroll_by(&mut self, x: i32, y: i32) {
         self.scroll_delta.x += x;
         self.scroll_delta.y += y;
     }
 
+    pub fn scroll_config(&self) -> ScrollConfig {
+        self.scroll_config
+    }
+
+    pub fn set_scroll_config(&mut self, config: ScrollConfig) {
+        self.scroll_config = config;
+    }
+
+    fn convert_scroll(&mut self) {
+        let cfg = self.scroll_config;
+        let raw = (self.scroll_lines * cfg.line_height + self.scroll_pixels) * cfg.speed;
+        let x = if cfg.invert_x { -raw.x } else { raw.x };
+        let y = if cfg.invert_y { raw.y } else { -raw.y };
+        let total = self.scroll_fraction + Vec2f::new(x, y);
+        let whole = Vec2f::new(total.x.trunc(), total.y.trunc());
+        self.scroll_fraction = total - whole;
+        self.scroll_delta.x += whole.x as i32;
+        self.scroll_delta.y += whole.y as i32;
+        self.scroll_lines = Vec2f::default();
+        self.scroll_pixels = Vec2f::default();
+    }
+
     pub fn keydown(&mut self, key: KeyMode) {
         self.key_pressed |= key;
         self.key_down |= key;
@@ -430,21 +805,49 @@ impl Input {
         }
     }
 
+    // delay before the first repeat and number of repeats per second for held keys, in seconds
+    pub fn set_key_repeat(&mut self, delay: Real, rate: Real) {
+        self.repeat_delay = delay;
+        self.repeat_interval = if rate > 0.0 { 1.0 / rate } else { Real::MAX };
+    }
+
     fn prelude(&mut self) {
+        self.convert_scroll();
+        self.recognize_gesture();
         self.mouse_delta.x = self.mouse_pos.x - self.last_mouse_pos.x;
         self.mouse_delta.y = self.mouse_pos.y - self.last_mouse_pos.y;
+
+        // key repeat is driven by the wall clock so it doesn't depend on the frame rate
+        let now = Instant::now();
+        let dt = self.last_frame.map(|t| (now - t).as_secs_f32()).unwrap_or(0.0);
+        self.last_frame = Some(now);
+        self.frame_dt = dt;
+        let held = self.key_down & KeyMode::REPEATABLE;
+        if !(self.key_pressed & KeyMode::REPEATABLE).is_none() {
+            self.repeat_timer = self.repeat_delay;
+        } else if !held.is_none() {
+            self.repeat_timer -= dt;
+            if self.repeat_timer <= 0.0 {
+                self.key_pressed |= held;
+                self.repeat_timer = (self.repeat_timer + self.repeat_interval).max(0.0);
+            }
+        }
     }
 
     fn epilogue(&mut self) {
         self.key_pressed = KeyMode::NONE;
         self.input_text.clear();
         self.mouse_pressed = MouseButton::NONE;
+        self.mouse_double_clicked = MouseButton::NONE;
+        // an action picked in the menu reaches its widget during the next frame
+        self.text_action = self.text_action_next.take();
         self.scroll_delta = vec2(0, 0);
+        self.mnemonic = None;
         self.last_mouse_pos = self.mouse_pos;
     }
 }
 
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
 #[repr(C)]
 pub struct Color {
     pub r: u8,
@@ -453,12 +856,95 @@ pub struct Color {
     pub a: u8,
 }
 
+impl Color {
+    // component-wise product, white leaves the color unchanged
+    pub fn modulate(self, tint: Color) -> Color {
+        let m = |a: u8, b: u8| ((a as u16 * b as u16 + 127) / 255) as u8;
+        Color {
+            r: m(self.r, tint.r),
+            g: m(self.g, tint.g),
+            b: m(self.b, tint.b),
+            a: m(self.a, tint.a),
+        }
+    }
+
+    pub fn lerp(self, other: Color, t: Real) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let l = |a: u8, b: u8| (a as Real + (b as Real - a as Real) * t).round() as u8;
+        Color {
+            r: l(self.r, other.r),
+            g: l(self.g, other.g),
+            b: l(self.b, other.b),
+            a: l(self.a, other.a),
+        }
+    }
+
+    // the alpha is linear in both spaces and left as is
+    pub fn to_linear(self) -> Color {
+        Color { r: srgb_to_linear(self.r), g: srgb_to_linear(self.g), b: srgb_to_linear(self.b), a: self.a }
+    }
+
+    pub fn to_srgb(self) -> Color {
+        Color { r: linear_to_srgb(self.r), g: linear_to_srgb(self.g), b: linear_to_srgb(self.b), a: self.a }
+    }
+
+    pub fn grayscale(self) -> Color {
+        let l = ((self.r as u32 * 77 + self.g as u32 * 150 + self.b as u32 * 29) >> 8) as u8;
+        Color { r: l, g: l, b: l, a: self.a }
+    }
+}
+
 pub trait Font {
     fn name(&self) -> &str;
     fn get_size(&self) -> usize;
     fn get_char_size(&self, c: char) -> (usize, usize);
 }
 
+// The space of the vertex colors handed to the renderer. Style colors are sRGB, backends drawing into an
+// sRGB framebuffer (which encodes the shader output) want linear vertex colors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    Srgb,
+    Linear,
+}
+
+fn color_lut(to_linear: bool) -> &'static [u8; 256] {
+    static LUTS: std::sync::OnceLock<([u8; 256], [u8; 256])> = std::sync::OnceLock::new();
+    let luts = LUTS.get_or_init(|| {
+        let mut lin = [0u8; 256];
+        let mut srgb = [0u8; 256];
+        for i in 0..256 {
+            let c = i as f32 / 255.0;
+            let l = if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+            let s = if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+            lin[i] = (l * 255.0).round() as u8;
+            srgb[i] = (s * 255.0).round() as u8;
+        }
+        (lin, srgb)
+    });
+    if to_linear {
+        &luts.0
+    } else {
+        &luts.1
+    }
+}
+
+pub fn srgb_to_linear(c: u8) -> u8 {
+    color_lut(true)[c as usize]
+}
+
+pub fn linear_to_srgb(c: u8) -> u8 {
+    color_lut(false)[c as usize]
+}
+
+// drawn under the text of the controls, helps reading HUD text over busy backgrounds
+#[derive(Copy, Clone)]
+pub enum TextEffect {
+    None,
+    Shadow { offset: Vec2i, color: Color },
+    Outline(Color), // 1px on every side
+}
+
 #[derive(Copy, Clone)]
 pub struct Style {
     pub font: FontId,
@@ -469,7 +955,22 @@ pub struct Style {
     pub title_height: i32,
     pub scrollbar_size: i32,
     pub thumb_size: i32,
-    pub colors: [Color; 14],
+    pub line_spacing: i32, // added to the font height between wrapped lines, can be negative
+    pub text_effect: TextEffect,
+    pub decimal_digits: usize, // digits kept right of the point by ALIGN_DECIMAL
+    pub resize_grip_size: i32, // side of the corner resize zone
+    pub resize_border: i32,    // thickness of the edge resize zones, 0 disables them
+    pub resize_outset: i32,    // how far the resize zones reach outside the frame
+    pub show_resize_grip: bool,
+    pub key_repeat_delay: Real, // seconds before a held key starts repeating
+    pub key_repeat_rate: Real,  // repeats per second
+    pub double_click_time: Real, // seconds between the presses of a double click
+    pub anim_time: Real,         // seconds of the hover and selection fades, 0 switches at once
+    pub item_radius: i32,        // corner radius of the list item highlight
+    pub zebra_rows: bool,        // RowAlt behind the odd rows of lists and tables
+    pub row_lines: bool,         // GridLine under the rows of lists and tables
+    pub column_lines: bool,      // GridLine between the columns of tables
+    pub colors: [Color; ControlColor::Max as usize],
 }
 
 pub type Real = f32;
@@ -492,6 +993,21 @@ impl Default for Style {
             title_height: 24,
             scrollbar_size: 12,
             thumb_size: 8,
+            line_spacing: 0,
+            text_effect: TextEffect::None,
+            decimal_digits: 2,
+            resize_grip_size: 24,
+            resize_border: 4,
+            resize_outset: 4,
+            show_resize_grip: false,
+            key_repeat_delay: 0.4,
+            key_repeat_rate: 25.0,
+            double_click_time: 0.4,
+            anim_time: 0.1,
+            item_radius: 0,
+            zebra_rows: false,
+            row_lines: false,
+            column_lines: false,
             colors: [
                 Color { r: 230, g: 230, b: 230, a: 255 },
                 Color { r: 25, g: 25, b: 25, a: 255 },
@@ -507,11 +1023,33 @@ impl Default for Style {
                 Color { r: 40, g: 40, b: 40, a: 255 },
                 Color { r: 43, g: 43, b: 43, a: 255 },
                 Color { r: 30, g: 30, b: 30, a: 255 },
+                Color { r: 90, g: 140, b: 220, a: 255 },
+                Color { r: 60, g: 180, b: 75, a: 255 },
+                Color { r: 230, g: 180, b: 40, a: 255 },
+                Color { r: 215, g: 60, b: 50, a: 255 },
+                Color { r: 110, g: 110, b: 110, a: 255 },
+                Color { r: 60, g: 90, b: 140, a: 255 },
+                Color { r: 255, g: 255, b: 255, a: 10 },
+                Color { r: 60, g: 60, b: 60, a: 255 },
             ],
         }
     }
 }
 
+// number of colors before the semantic ones were added
+pub const LEGACY_COLOR_COUNT: usize = 14;
+
+impl Style {
+    // loads a palette in the original 14 colors layout, the semantic colors keep their current values
+    pub fn load_legacy_colors(&mut self, colors: &[Color; LEGACY_COLOR_COUNT]) {
+        self.colors[..LEGACY_COLOR_COUNT].copy_from_slice(colors);
+    }
+
+    pub fn color(&self, id: ControlColor) -> Color {
+        self.colors[id as usize]
+    }
+}
+
 pub fn vec2(x: i32, y: i32) -> Vec2i {
     Vec2i { x, y }
 }
@@ -560,14 +1098,29 @@ pub struct Context<R: Renderer> {
     scroll_target: Option<WindowHandle>,
 
     root_list: Vec<WindowHandle>,
+    overlay: Container,
+    shared_input: Option<SharedInput>,
+    appearance: Appearance,
+    auto_theme: Option<(Theme, Theme)>, // light, dark
+    render_passes: Vec<(RenderLayer, RenderPass)>,
+    metrics: Option<MetricsWindow>,
+    atlas_view: Option<AtlasWindow>,
+    telemetry: Option<TelemetryHook>,
+    telemetry_windows: Vec<String>, // open during the last frame
+    keyboard_nav: Option<KeyboardNav>,
+    presentation: Option<Presentation>,
+    safe_insets: Margin,
 
     pub input: Rc<RefCell<Input>>,
 }
 
 impl<R: Renderer> Context<R> {
     pub fn new(renderer: RendererHandle<R>, dim: Dimensioni) -> Self {
+        let canvas = Canvas::from(renderer, dim);
+        let input = Rc::new(RefCell::new(Input::default()));
+        let overlay = Container::new("!overlay", canvas.get_atlas(), &Style::default(), input.clone());
         Self {
-            canvas: Canvas::from(renderer, dim),
+            canvas,
             style: Style::default(),
             last_zindex: 0,
             frame: 0,
@@ -576,8 +1129,20 @@ impl<R: Renderer> Context<R> {
             scroll_target: None,
 
             root_list: Vec::default(),
-
-            input: Rc::new(RefCell::new(Input::default())),
+            overlay,
+            shared_input: None,
+            appearance: Appearance::Dark,
+            auto_theme: None,
+            render_passes: Vec::new(),
+            metrics: None,
+            atlas_view: None,
+            telemetry: None,
+            telemetry_windows: Vec::new(),
+            keyboard_nav: None,
+            presentation: None,
+            safe_insets: Margin::ZERO,
+
+            input,
         }
     }
 }
@@ -588,19 +1153,34 @@ impl<R: Renderer> Context<R> {
     }
 
     pub fn end(&mut self) {
-        for r in &mut self.root_list {
-            r.render(&mut self.canvas);
+        for i in 0..self.root_list.len() {
+            if !self.render_passes.is_empty() {
+                self.run_render_passes(&RenderLayer::Window(self.root_list[i].clone()));
+            }
+            self.root_list[i].render(&mut self.canvas);
         }
-        self.canvas.end()
+        self.run_render_passes(&RenderLayer::Overlay);
+        self.overlay.render(&mut self.canvas);
+        self.canvas.end();
+        // passes of layers that weren't rendered are dropped
+        self.render_passes.clear();
     }
 
     #[inline(never)]
     fn frame_begin(&mut self) {
         self.scroll_target = None;
+        if let Some(shared) = &self.shared_input {
+            shared.swap_into(&mut self.input.borrow_mut());
+        }
+        self.input.borrow_mut().set_key_repeat(self.style.key_repeat_delay, self.style.key_repeat_rate);
+        self.input.borrow_mut().set_double_click_time(self.style.double_click_time);
         self.input.borrow_mut().prelude();
+        self.input.borrow_mut().events.clear();
+        self.keyboard_nav_begin();
         for r in &mut self.root_list {
             r.prepare();
         }
+        self.overlay.prepare();
         self.frame += 1;
         self.root_list.clear();
     }
@@ -610,6 +1190,10 @@ impl<R: Renderer> Context<R> {
         for r in &mut self.root_list {
             r.finish();
         }
+        self.metrics_frame_end();
+        self.dispatch_telemetry();
+        self.keyboard_nav_end();
+        self.presentation_end();
 
         let mouse_pressed = self.input.borrow().mouse_pressed;
         match (mouse_pressed.is_none(), &self.next_hover_root) {
@@ -632,8 +1216,16 @@ impl<R: Renderer> Context<R> {
             _ => (),
         }
 
-        // sort all windows
-        self.root_list.sort_by(|a, b| a.zindex().cmp(&b.zindex()));
+        // sort all windows, the sort is stable so equal z-indices keep the evaluation order
+        self.root_list.sort_by_key(|w| w.zindex());
+
+        // mnemonics go to the hovered window, or the top most one when the mouse is elsewhere
+        for r in &mut self.root_list {
+            r.inner_mut().main.mnemonic_scope = false;
+        }
+        if let Some(mut window) = self.hover_root.clone().or_else(|| self.root_list.last().cloned()) {
+            window.inner_mut().main.mnemonic_scope = true;
+        }
     }
 
     pub fn frame<F: FnOnce(&mut Self)>(&mut self, f: F) {
@@ -668,12 +1260,34 @@ impl<R: Renderer> Context<R> {
         window.inner_mut().main.zindex = self.last_zindex;
     }
 
+    // top most window of the last frame containing `pos`
+    pub fn window_at(&self, pos: Vec2i) -> Option<WindowHandle> {
+        self.root_list.iter().rev().find(|w| w.hit_test(pos).is_some()).cloned()
+    }
+
+    // what a click at `pos` would hit, for hosts implementing OS level dragging or click-through regions
+    pub fn hit_test(&self, pos: Vec2i) -> HitTarget {
+        for w in self.root_list.iter().rev() {
+            if let Some(part) = w.hit_test(pos) {
+                return HitTarget::Window(w.clone(), part);
+            }
+        }
+        HitTarget::None
+    }
+
+    // windows of the last frame from the back to the front, in drawing order
+    pub fn window_order(&self) -> Vec<WindowHandle> {
+        self.root_list.clone()
+    }
+
     #[inline(never)]
-    fn begin_root_container(&mut self, window: &mut WindowHandle) {
+    fn begin_root_container(&mut self, window: &mut WindowHandle, opt: ContainerOption) {
         self.root_list.push(window.clone());
 
-        if window.inner().main.rect.contains(&self.input.borrow().mouse_pos)
-            && (self.next_hover_root.is_none() || window.zindex() > self.next_hover_root.as_ref().unwrap().zindex())
+        // click-through windows are drawn but never hovered, the input goes to what's below them
+        if !opt.is_click_through()
+            && window.inner().hover_rect(opt).contains(&self.input.borrow().mouse_pos)
+            && (self.next_hover_root.is_none() || window.zindex() >= self.next_hover_root.as_ref().unwrap().zindex())
         {
             self.next_hover_root = Some(window.clone());
         }
@@ -684,6 +1298,7 @@ impl<R: Renderer> Context<R> {
     #[inline(never)]
     fn end_root_container(&mut self, window: &mut WindowHandle) {
         let container = &mut window.inner_mut().main;
+        container.draw_flashes();
         container.pop_clip_rect();
 
         let layout = *container.layout.top();
@@ -699,7 +1314,9 @@ impl<R: Renderer> Context<R> {
             return false;
         }
 
-        self.begin_root_container(window);
+        self.begin_root_container(window, opt);
+        window.inner_mut().viewport = self.safe_area();
+        window.inner_mut().keep_in_viewport = self.safe_insets != Margin::ZERO;
         window.begin_window(opt);
 
         true
@@ -727,6 +1344,52 @@ impl<R: Renderer> Context<R> {
         }
     }
 
+    // Lays the window out twice: a first pass without input measures the content, so the scrollbars of the
+    // second pass match this frame's content instead of the previous one. `f` runs twice and should only
+    // change the application state in response to widget results. The measuring pass gets no input and no
+    // time, its events are dropped and the window state it touches is restored. `f` must not open other
+    // windows or popups.
+    pub fn window_exact<F: FnMut(&mut Container) -> WindowState>(&mut self, window: &mut WindowHandle, opt: ContainerOption, mut f: F) {
+        if !window.is_open() {
+            return;
+        }
+
+        let far = vec2(-i32::MAX / 2, -i32::MAX / 2);
+        let input = self.input.replace(Input {
+            mouse_pos: far,
+            last_mouse_pos: far,
+            frame_dt: 0.0,
+            ..Input::default()
+        });
+        let next_hover_root = self.next_hover_root.clone();
+        let root_count = self.root_list.len();
+        let (win_state, animation) = (window.inner().win_state, window.inner().animation);
+        let (focus, hover, updated_focus, activate_next, flashes) = {
+            let c = &window.inner().main;
+            (c.focus, c.hover, c.updated_focus, c.activate_next, c.flashes.clone())
+        };
+
+        self.window(window, opt, &mut f);
+
+        assert!(self.root_list.len() == root_count + 1, "window_exact: the content opened another window");
+        self.root_list.pop();
+        self.next_hover_root = next_hover_root;
+        *self.input.borrow_mut() = input;
+        {
+            let w = &mut window.inner_mut();
+            w.win_state = win_state;
+            w.animation = animation;
+            w.main.focus = focus;
+            w.main.hover = hover;
+            w.main.updated_focus = updated_focus;
+            w.main.activate_next = activate_next;
+            w.main.flashes = flashes;
+            w.main.discard_frame();
+        }
+
+        self.window(window, opt, f);
+    }
+
     pub fn open_dialog(&mut self, window: &mut WindowHandle) {
         window.inner_mut().win_state = WindowState::Open;
     }
@@ -753,14 +1416,118 @@ impl<R: Renderer> Context<R> {
 
     pub fn popup<F: FnOnce(&mut Container) -> WindowState>(&mut self, window: &mut WindowHandle, f: F) {
         let opt = ContainerOption::AUTO_SIZE | ContainerOption::NO_RESIZE | ContainerOption::NO_SCROLL | ContainerOption::NO_TITLE;
+        self.clamp_to_safe_area(window);
         self.window(window, opt, f);
     }
 
+    // Events are then read from the shared buffer at the beginning of each frame, `None` goes back to feeding
+    // `input` directly.
+    pub fn set_shared_input(&mut self, shared: Option<SharedInput>) {
+        self.shared_input = shared;
+    }
+
+    pub fn shared_input(&self) -> Option<SharedInput> {
+        self.shared_input.clone()
+    }
+
     pub fn set_style(&mut self, style: &Style) {
         self.style = style.clone()
     }
 
+    // Draws in screen space above all the windows, without clipping. The layer doesn't take input, it is meant
+    // for FPS counters, crosshairs, drag previews... It can be called several times per frame.
+    pub fn overlay_layer<F: FnOnce(&mut Container)>(&mut self, f: F) {
+        let area = self.safe_area();
+        let overlay = &mut self.overlay;
+        overlay.style = self.style;
+        overlay.rect = area;
+        overlay.in_hover_root = false;
+        overlay.clip_stack.push(UNCLIPPED_RECT);
+        overlay.push_container_body(overlay.rect, ContainerOption::NO_SCROLL);
+        f(overlay);
+        overlay.layout.stack.pop();
+        overlay.pop_clip_rect();
+    }
+
+    // Walks the commands recorded during the last frame (root windows in z order, then their panels). This is
+    // valid after `end()` and until the next frame starts, e.g. to export or forward the frame.
+    pub fn visit_frame_commands<F: FnMut(&Container, &Command)>(&self, mut f: F) {
+        for window in &self.root_list {
+            window.inner().main.visit_commands(&mut f);
+        }
+        self.overlay.visit_commands(&mut f);
+    }
+
+    // Looks up the rect of a widget laid out during the current frame, windows are searched from the top most.
+    // Ids are only unique within a container, pass the window to disambiguate.
+    pub fn widget_rect(&self, id: Id) -> Option<Recti> {
+        let mut windows: Vec<&WindowHandle> = self.root_list.iter().collect();
+        windows.sort_by_key(|w| -w.zindex());
+        windows.iter().find_map(|w| w.inner().main.widget_rect(id))
+    }
+
+    // The regions marked during the frame in every window, from the bottom most window to the top most
+    pub fn marked_regions(&self) -> Vec<(String, Recti)> {
+        let mut windows: Vec<&WindowHandle> = self.root_list.iter().collect();
+        windows.sort_by_key(|w| w.zindex());
+        windows.iter().flat_map(|w| w.inner().main.marked_regions()).collect()
+    }
+
+    pub fn marked_region(&self, name: &str) -> Option<Recti> {
+        self.marked_regions().into_iter().rev().find(|(n, _)| n == name).map(|(_, r)| r)
+    }
+
+    // The clicks, value changes, submits and focus changes reported by the widgets during the last frame,
+    // in evaluation order
+    pub fn take_events(&mut self) -> Vec<WidgetEvent> {
+        std::mem::take(&mut self.input.borrow_mut().events)
+    }
+
+    // Focuses a widget found in the topmost window that laid it out last frame, e.g. the search box of a
+    // palette that just opened. Returns false when no window has it.
+    pub fn focus_widget(&mut self, id: Id) -> bool {
+        let mut windows = self.root_list.clone();
+        windows.sort_by_key(|w| -w.zindex());
+        windows.iter_mut().any(|w| w.inner_mut().main.focus_widget(id))
+    }
+
+    // simulates a click on the widget, it reports it during the next frame
+    pub fn activate_widget(&mut self, id: Id) -> bool {
+        let mut windows = self.root_list.clone();
+        windows.sort_by_key(|w| -w.zindex());
+        windows.iter_mut().any(|w| w.inner_mut().main.activate_widget(id))
+    }
+
+    pub fn blur(&mut self) {
+        for w in &mut self.root_list {
+            w.inner_mut().main.blur();
+        }
+    }
+
+    pub fn window_widget_rect(&self, window: &WindowHandle, id: Id) -> Option<Recti> {
+        window.inner().main.widget_rect(id)
+    }
+
+    // counters of the last frame: containers, commands and per-frame arena usage
+    pub fn frame_stats(&self) -> FrameStats {
+        let mut stats = FrameStats::default();
+        for window in &self.root_list {
+            window.inner().main.collect_stats(&mut stats);
+        }
+        self.overlay.collect_stats(&mut stats);
+        stats
+    }
+
     pub fn canvas(&self) -> &Canvas<R> {
         &self.canvas
     }
+
+    pub fn set_vertex_color_space(&mut self, space: ColorSpace) {
+        self.canvas.set_vertex_color_space(space);
+    }
+
+    // see `Canvas::set_pixel_snap`
+    pub fn set_pixel_snap(&mut self, scale: Option<Real>) {
+        self.canvas.set_pixel_snap(scale);
+    }
 }
@@ -0,0 +1,118 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+// hover and selection amounts of a list item, between 0 and 1, moved towards their target every frame
+#[derive(Copy, Clone, Default)]
+struct ItemFade {
+    hover: Real,
+    selected: Real,
+}
+
+fn approach(v: Real, target: Real, step: Real) -> Real {
+    if v < target {
+        (v + step).min(target)
+    } else {
+        (v - step).max(target)
+    }
+}
+
+impl Container {
+    // RowAlt behind the odd rows and GridLine under every row, as the style asks
+    pub(crate) fn draw_row_background(&mut self, r: Recti, row: usize) {
+        if self.style.zebra_rows && row % 2 == 1 {
+            self.draw_rect(r, self.style.colors[ControlColor::RowAlt as usize]);
+        }
+    }
+
+    pub(crate) fn draw_row_line(&mut self, r: Recti) {
+        if self.style.row_lines {
+            let y = r.y + r.height + self.style.spacing / 2;
+            self.draw_rect(rect(r.x, y, r.width, 1), self.style.colors[ControlColor::GridLine as usize]);
+        }
+    }
+
+    pub fn list_item(&mut self, label: &str, selected: bool) -> ResourceState {
+        self.list_item_row(label, selected, None)
+    }
+
+    #[inline(never)]
+    fn list_item_row(&mut self, label: &str, selected: bool, row: Option<usize>) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let id = self.idmngr.get_id_from_str(label);
+        let r = self.layout.next();
+        self.update_control(id, r, WidgetOption::NONE);
+        if self.is_clicked(id) {
+            res |= ResourceState::SUBMIT;
+            self.emit_click(id, label);
+        }
+
+        let step = if self.style.anim_time > 0.0 { self.frame_dt() / self.style.anim_time } else { 1.0 };
+        let hovered = self.hover == Some(id);
+        let fade = self.widget_extras(id).get_or_insert_with(ItemFade::default);
+        fade.hover = approach(fade.hover, if hovered { 1.0 } else { 0.0 }, step);
+        fade.selected = approach(fade.selected, if selected { 1.0 } else { 0.0 }, step);
+        let fade = *fade;
+
+        if let Some(row) = row {
+            self.draw_row_background(r, row);
+        }
+        if fade.hover > 0.0 || fade.selected > 0.0 {
+            let clear = color(0, 0, 0, 0);
+            let hover = clear.lerp(self.style.colors[ControlColor::ButtonHover as usize], fade.hover);
+            let bg = hover.lerp(self.style.colors[ControlColor::SelectionBG as usize], fade.selected);
+            let radius = self.style.item_radius;
+            if radius > 0 {
+                self.masked(r, radius, |c| c.draw_rect(r, bg));
+            } else {
+                self.draw_rect(r, bg);
+            }
+        }
+        self.draw_control_text(label, r, ControlColor::Text, WidgetOption::NONE);
+        if row.is_some() {
+            self.draw_row_line(r);
+        }
+        res
+    }
+
+    // One list item per entry, clicking one selects it
+    pub fn list_box(&mut self, items: &[&str], selected: &mut Option<usize>) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        for (i, item) in items.iter().enumerate() {
+            self.idmngr.push_id_from_str(&i.to_string());
+            if self.list_item_row(item, *selected == Some(i), Some(i)).is_submitted() && *selected != Some(i) {
+                *selected = Some(i);
+                res |= ResourceState::CHANGE;
+            }
+            self.idmngr.pop_id();
+        }
+        res
+    }
+}
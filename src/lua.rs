@@ -0,0 +1,138 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Lua façade: the host keeps the context, windows and renderer, scripts only fill containers. A script function
+// receives the container as `ui` and may return `false` to close its window:
+//
+//   function settings(ui)
+//       ui:row({80, -1}, 0)
+//       ui:label("Volume")
+//       volume = select(2, ui:slider("volume", volume, 0, 100))
+//       return not ui:button("Close")
+//   end
+//
+// Since script values don't have a stable address, the widgets holding a value are identified by a name.
+use super::*;
+use mlua::{Function, Lua, UserData, UserDataMethods};
+
+impl UserData for Container {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method_mut("row", |_, ui, (widths, height): (Vec<i32>, i32)| {
+            ui.set_row_widths_height(&widths, height);
+            Ok(())
+        });
+        methods.add_method_mut("label", |_, ui, text: String| {
+            ui.label(&text);
+            Ok(())
+        });
+        methods.add_method_mut("text", |_, ui, text: String| {
+            ui.text(&text);
+            Ok(())
+        });
+        methods.add_method_mut("button", |_, ui, label: String| Ok(ui.button_ex(&label, None, WidgetOption::ALIGN_CENTER).is_submitted()));
+        methods.add_method_mut("checkbox", |_, ui, (label, mut state): (String, bool)| {
+            let id = ui.idmngr.get_id_from_str(&label);
+            let r = ui.next_cell();
+            let res = ui.checkbox_raw(&label, &mut state, id, r);
+            Ok((res.is_changed(), state))
+        });
+        methods.add_method_mut(
+            "slider",
+            |_, ui, (name, mut value, low, high, step, precision): (String, Real, Real, Real, Option<Real>, Option<usize>)| {
+                let id = ui.idmngr.get_id_from_str(&name);
+                let r = ui.next_cell();
+                let res = ui.slider_raw(&mut value, id, r, low, high, step.unwrap_or(0.0), precision.unwrap_or(0), WidgetOption::ALIGN_CENTER);
+                Ok((res.is_changed(), value))
+            },
+        );
+        methods.add_method_mut("number", |_, ui, (name, mut value, step, precision): (String, Real, Option<Real>, Option<usize>)| {
+            let id = ui.idmngr.get_id_from_str(&name);
+            let r = ui.next_cell();
+            let res = ui.number_raw(&mut value, id, r, step.unwrap_or(1.0), precision.unwrap_or(2), WidgetOption::ALIGN_CENTER);
+            Ok((res.is_changed(), value))
+        });
+        methods.add_method_mut("textbox", |_, ui, (name, mut text): (String, String)| {
+            let id = ui.idmngr.get_id_from_str(&name);
+            let r = ui.next_cell();
+            let res = ui.textbox_raw(&mut text, id, r, WidgetOption::NONE);
+            Ok((res.is_submitted(), text))
+        });
+        methods.add_method_mut("header", |_, ui, (label, expanded): (String, bool)| {
+            let state = if expanded { NodeState::Expanded } else { NodeState::Closed };
+            Ok(ui.header(&label, state, |_| ()).is_expanded())
+        });
+    }
+}
+
+pub struct LuaUi {
+    lua: Lua,
+}
+
+impl Default for LuaUi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LuaUi {
+    pub fn new() -> Self {
+        Self { lua: Lua::new() }
+    }
+
+    // to register host functions (actions) or globals shared with the scripts
+    pub fn lua(&self) -> &Lua {
+        &self.lua
+    }
+
+    pub fn load(&self, source: &str) -> mlua::Result<()> {
+        self.lua.load(source).exec()
+    }
+
+    pub fn call(&self, function: &str, container: &mut Container) -> mlua::Result<WindowState> {
+        let f: Function = self.lua.globals().get(function)?;
+        self.lua.scope(|scope| {
+            let ui = scope.create_userdata_ref_mut(container)?;
+            let open: Option<bool> = f.call(ui)?;
+            Ok(if open == Some(false) { WindowState::Closed } else { WindowState::Open })
+        })
+    }
+
+    // runs `function` as the content of `window`, errors are returned and close nothing
+    pub fn window<R: Renderer>(&self, ctx: &mut Context<R>, window: &mut WindowHandle, opt: ContainerOption, function: &str) -> mlua::Result<()> {
+        let mut res = Ok(());
+        ctx.window(window, opt, |container| match self.call(function, container) {
+            Ok(state) => state,
+            Err(e) => {
+                res = Err(e);
+                WindowState::Open
+            }
+        });
+        res
+    }
+}
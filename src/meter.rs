@@ -0,0 +1,179 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MeterScale {
+    Linear,
+    // value is a linear amplitude mapped in decibels over [-range, 0] dB
+    Decibel(Real),
+}
+
+#[derive(Clone)]
+pub struct LevelMeterState {
+    pub orientation: Orientation,
+    pub scale: MeterScale,
+    pub decay: Real,             // fraction of the level lost per frame when the input drops
+    pub peak_hold_frames: usize, // number of frames the peak marker is held before falling
+    pub zones: [Real; 2],        // normalized thresholds for the yellow and red zones
+    pub zone_colors: Option<[Color; 3]>, // the style's Success, Warning and Error colors when None
+    level: Real,
+    peak: Real,
+    peak_age: usize,
+    pub extras: Extras,
+}
+
+impl LevelMeterState {
+    pub fn new(orientation: Orientation) -> Self {
+        Self {
+            orientation,
+            scale: MeterScale::Linear,
+            decay: 0.05,
+            peak_hold_frames: 60,
+            zones: [0.7, 0.9],
+            zone_colors: None,
+            level: 0.0,
+            peak: 0.0,
+            peak_age: 0,
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn level(&self) -> Real {
+        self.level
+    }
+
+    pub fn peak(&self) -> Real {
+        self.peak
+    }
+
+    fn normalize(&self, value: Real) -> Real {
+        let v = match self.scale {
+            MeterScale::Linear => value,
+            MeterScale::Decibel(range) => {
+                if value <= 0.0 {
+                    0.0
+                } else {
+                    (20.0 * value.log10() + range) / range
+                }
+            }
+        };
+        v.clamp(0.0, 1.0)
+    }
+
+    // fast attack, slow release
+    fn update(&mut self, value: Real) {
+        let v = self.normalize(value);
+        if v >= self.level {
+            self.level = v;
+        } else {
+            self.level = (self.level - self.decay).max(v);
+        }
+
+        if self.level >= self.peak {
+            self.peak = self.level;
+            self.peak_age = 0;
+        } else if self.peak_age < self.peak_hold_frames {
+            self.peak_age += 1;
+        } else {
+            self.peak = (self.peak - self.decay).max(self.level);
+        }
+    }
+
+    fn colors(&self, style: &Style) -> [Color; 3] {
+        self.zone_colors
+            .unwrap_or([style.color(ControlColor::Success), style.color(ControlColor::Warning), style.color(ControlColor::Error)])
+    }
+
+    fn zone_color(&self, v: Real, style: &Style) -> Color {
+        let colors = self.colors(style);
+        if v >= self.zones[1] {
+            colors[2]
+        } else if v >= self.zones[0] {
+            colors[1]
+        } else {
+            colors[0]
+        }
+    }
+}
+
+impl Container {
+    // map [from, to] (normalized) onto the meter rect, growing up for vertical meters
+    fn meter_span(r: Recti, orientation: Orientation, from: Real, to: Real) -> Recti {
+        match orientation {
+            Orientation::Horizontal => {
+                let x0 = (from * r.width as Real) as i32;
+                let x1 = (to * r.width as Real) as i32;
+                rect(r.x + x0, r.y, x1 - x0, r.height)
+            }
+            Orientation::Vertical => {
+                let y0 = (from * r.height as Real) as i32;
+                let y1 = (to * r.height as Real) as i32;
+                rect(r.x, r.y + r.height - y1, r.width, y1 - y0)
+            }
+        }
+    }
+
+    #[inline(never)]
+    pub fn level_meter(&mut self, state: &mut LevelMeterState, value: Real) {
+        state.update(value);
+        let r = self.layout.next();
+        self.draw_frame(r, ControlColor::Base);
+
+        // draw the level zone by zone so each part gets its own color
+        let level = state.level;
+        let colors = state.colors(&self.style);
+        let mut from = 0.0;
+        for (i, to) in [state.zones[0], state.zones[1], 1.0].iter().enumerate() {
+            let to = to.min(level);
+            if to > from {
+                let span = Self::meter_span(r, state.orientation, from, to);
+                self.draw_rect(span, colors[i]);
+            }
+            from = from.max(to);
+        }
+
+        if state.peak > 0.0 {
+            let marker = Self::meter_span(r, state.orientation, state.peak, state.peak);
+            let marker = match state.orientation {
+                Orientation::Horizontal => rect(marker.x - 1, marker.y, 2, marker.height),
+                Orientation::Vertical => rect(marker.x, marker.y - 1, marker.width, 2),
+            };
+            let color = state.zone_color(state.peak, &self.style);
+            self.draw_rect(marker, color);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+const FRAME_HISTORY: usize = 120;
+
+pub(crate) struct MetricsWindow {
+    win: WindowHandle,
+    frame_times: Vec<Real>, // ring buffer of the last frame durations in seconds
+    head: usize,
+    stats: FrameStats, // snapshot taken when the previous frame ended, the current one is still being built
+}
+
+impl MetricsWindow {
+    fn push(&mut self, dt: Real) {
+        if self.frame_times.len() < FRAME_HISTORY {
+            self.frame_times.push(dt);
+        } else {
+            self.frame_times[self.head] = dt;
+            self.head = (self.head + 1) % FRAME_HISTORY;
+        }
+    }
+
+    // oldest first
+    fn history(&self) -> impl Iterator<Item = Real> + '_ {
+        self.frame_times[self.head..].iter().chain(self.frame_times[..self.head].iter()).copied()
+    }
+
+    fn average(&self) -> Real {
+        if self.frame_times.is_empty() {
+            0.0
+        } else {
+            self.frame_times.iter().sum::<Real>() / self.frame_times.len() as Real
+        }
+    }
+}
+
+impl Container {
+    fn frame_time_graph(&mut self, times: &[Real]) {
+        let r = self.layout.next();
+        self.draw_frame(r, ControlColor::Base);
+        if times.is_empty() || r.width <= 0 {
+            return;
+        }
+
+        // bars scaled to the slowest frame, at least 1/30s so a steady 60fps stays at half height
+        let top = times.iter().copied().fold(1.0 / 30.0, Real::max);
+        let target = 1.0 / 60.0;
+        let bar_w = (r.width / FRAME_HISTORY as i32).max(1);
+        let x0 = r.x + r.width - bar_w * times.len() as i32;
+        for (i, t) in times.iter().enumerate() {
+            let h = ((t / top) * r.height as Real) as i32;
+            let color = if *t > target * 1.5 { self.style.color(ControlColor::Warning) } else { self.style.color(ControlColor::Success) };
+            self.draw_rect(rect(x0 + i as i32 * bar_w, r.y + r.height - h, bar_w, h), color);
+        }
+        let y = r.y + r.height - ((target / top) * r.height as Real) as i32;
+        self.draw_rect(rect(r.x, y, r.width, 1), self.style.color(ControlColor::Text));
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // A built-in window with the frame rate, a frame time graph, the command, vertex and state counters
+    // and the atlas occupancy. Call it every frame while it should be visible.
+    pub fn show_metrics_window(&mut self) {
+        let dt = self.input.borrow().frame_dt;
+        let mut metrics = match self.metrics.take() {
+            Some(m) => m,
+            None => MetricsWindow {
+                win: self.new_window("Metrics", rect(10, 10, 260, 260)),
+                frame_times: Vec::with_capacity(FRAME_HISTORY),
+                head: 0,
+                stats: self.frame_stats(),
+            },
+        };
+        if dt > 0.0 {
+            metrics.push(dt);
+        }
+
+        // the canvas still holds the vertices of the previous frame, rendered after it ended
+        let stats = metrics.stats;
+        let vertices = self.canvas.vertex_count();
+        let atlas = self.canvas.get_atlas();
+        let atlas_dim = atlas.get_texture_dimension();
+        let atlas_area = (atlas_dim.width * atlas_dim.height).max(1) as usize;
+        let occupancy = atlas.used_area() as Real * 100.0 / atlas_area as Real;
+
+        let avg = metrics.average();
+        let fps = if avg > 0.0 { 1.0 / avg } else { 0.0 };
+        let times: Vec<Real> = metrics.history().collect();
+        self.window(&mut metrics.win.clone(), ContainerOption::NONE, |cont| {
+            cont.set_row_widths_height(&[110, -1], 0);
+            for (name, value) in [
+                ("FPS", format!("{:.1}", fps)),
+                ("Frame time", format!("{:.2} ms", avg * 1000.0)),
+                ("Containers", format!("{}", stats.containers)),
+                ("Commands", format!("{}", stats.commands)),
+                ("Vertices", format!("{}", vertices)),
+                ("Atlas", format!("{:.1}% of {}x{}", occupancy, atlas_dim.width, atlas_dim.height)),
+                ("State entries", format!("{}", stats.state_entries)),
+                ("Arena", format!("{} / {} bytes", stats.arena_used, stats.arena_capacity)),
+            ] {
+                cont.label(name);
+                cont.label(&value);
+            }
+            cont.set_row_widths_height(&[-1], 60);
+            cont.frame_time_graph(&times);
+            WindowState::Open
+        });
+        self.metrics = Some(metrics);
+    }
+
+    pub(crate) fn metrics_frame_end(&mut self) {
+        if let Some(mut metrics) = self.metrics.take() {
+            metrics.stats = self.frame_stats();
+            self.metrics = Some(metrics);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::borrow::Cow;
+
+// Splits a label marked for a keyboard mnemonic: "&File" shows "File" and is activated by Alt+F, "&&" is a
+// literal '&', as is a '&' not followed by a letter or digit. The mnemonic is the byte offset of its character
+// in the shown text, and the lowercase key.
+pub fn parse_mnemonic(label: &str) -> (Cow<'_, str>, Option<(usize, char)>) {
+    if !label.contains('&') {
+        return (Cow::Borrowed(label), None);
+    }
+    let mut text = String::with_capacity(label.len());
+    let mut mnemonic = None;
+    let mut chars = label.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            text.push(c);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('&') => {
+                chars.next();
+                text.push('&');
+            }
+            // only the first marker counts
+            Some(k) if k.is_alphanumeric() && mnemonic.is_none() => {
+                chars.next();
+                mnemonic = Some((text.len(), k.to_lowercase().next().unwrap_or(k)));
+                text.push(k);
+            }
+            _ => text.push('&'),
+        }
+    }
+    (Cow::Owned(text), mnemonic)
+}
+
+// the label as shown, only parsed for widgets given `WidgetOption::MNEMONIC`
+pub(crate) fn mnemonic_label(label: &str, opt: WidgetOption) -> (Cow<'_, str>, Option<(usize, char)>) {
+    if opt.has_mnemonic() {
+        parse_mnemonic(label)
+    } else {
+        (Cow::Borrowed(label), None)
+    }
+}
+
+impl Input {
+    // Alt+`key` was pressed. The first widget with that mnemonic in the hovered window (the top most one when
+    // the mouse is elsewhere) takes it during the next frame.
+    pub fn mnemonic(&mut self, key: char) {
+        self.mnemonic = Some(key.to_lowercase().next().unwrap_or(key));
+    }
+
+    pub fn is_alt_down(&self) -> bool {
+        self.key_down.is_alt()
+    }
+}
+
+impl Container {
+    // whether the mnemonic of the widget was pressed, taking it from the input
+    pub(crate) fn mnemonic_pressed(&mut self, mnemonic: Option<(usize, char)>) -> bool {
+        let Some((_, key)) = mnemonic else {
+            return false;
+        };
+        let mut input = self.input.borrow_mut();
+        if self.disabled > 0 || !self.mnemonic_scope || input.mnemonic != Some(key) {
+            return false;
+        }
+        input.mnemonic = None;
+        true
+    }
+
+    // underlines the mnemonic of `text`, drawn by `draw_control_text`, while Alt is held
+    pub(crate) fn draw_mnemonic(&mut self, text: &str, mnemonic: Option<(usize, char)>, r: Recti, colorid: ControlColor, opt: WidgetOption) {
+        let Some((at, _)) = mnemonic else {
+            return;
+        };
+        if !self.input.borrow().is_alt_down() || opt.is_aligned_numeric() || opt.is_aligned_decimal() {
+            return;
+        }
+        let font = self.style.font;
+        let pos = self.control_text_pos(text, r, opt);
+        let end = text[at..].chars().next().map(|c| at + c.len_utf8()).unwrap_or(at);
+        let x0 = pos.x + self.atlas.get_text_size(font, &text[..at]).width;
+        let x1 = pos.x + self.atlas.get_text_size(font, &text[..end]).width;
+        let y = pos.y + self.atlas.get_font_height(font) as i32 - 1;
+        let color = self.style.colors[colorid as usize];
+        self.push_clip_rect(r);
+        self.draw_rect(rect(x0, y, x1 - x0, 1), color);
+        self.pop_clip_rect();
+    }
+}
@@ -0,0 +1,107 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+use std::any::Any;
+
+// Change counter of an application data model. The model bumps it on each mutation, views compare it
+// with the version they were built from and skip the rebuild while it's the same.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct ModelVersion(u64);
+
+impl ModelVersion {
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    pub fn bump(&mut self) {
+        self.0 = self.0.wrapping_add(1);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+// A value derived from a model (filtered rows, a flattened tree...), rebuilt only when the version moves
+pub struct Versioned<T> {
+    version: Option<ModelVersion>,
+    value: Option<Rc<T>>,
+}
+
+impl<T> Default for Versioned<T> {
+    fn default() -> Self {
+        Self { version: None, value: None }
+    }
+}
+
+impl<T> Versioned<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_stale(&self, version: ModelVersion) -> bool {
+        self.version != Some(version)
+    }
+
+    pub fn get_or_build<F: FnOnce() -> T>(&mut self, version: ModelVersion, build: F) -> Rc<T> {
+        if self.is_stale(version) || self.value.is_none() {
+            self.value = Some(Rc::new(build()));
+            self.version = Some(version);
+        }
+        self.value.clone().unwrap()
+    }
+
+    pub fn invalidate(&mut self) {
+        self.version = None;
+        self.value = None;
+    }
+}
+
+impl<T> Clone for Versioned<T> {
+    fn clone(&self) -> Self {
+        Self { version: self.version, value: self.value.clone() }
+    }
+}
+
+impl Container {
+    // Content keyed by `key` in this container, kept across frames and rebuilt by `build` only when
+    // `version` differs from the one it was built with
+    pub fn cached_model<T: Any, F: FnOnce() -> T>(&mut self, key: &str, version: ModelVersion, build: F) -> Rc<T> {
+        let id = self.idmngr.get_id_from_str(key);
+        self.widget_extras(id).get_or_insert_with(Versioned::<T>::new).get_or_build(version, build)
+    }
+
+    pub fn invalidate_model<T: Any>(&mut self, key: &str) {
+        let id = self.idmngr.get_id_from_str(key);
+        if let Some(cache) = self.widget_extras(id).get_mut::<Versioned<T>>() {
+            cache.invalidate();
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+const HIGHLIGHT_RADIUS: f32 = 22.0;
+const RIPPLE_TIME: Real = 0.5;
+const RIPPLE_RADIUS: f32 = 48.0;
+
+pub(crate) struct Presentation {
+    ripples: Vec<(Vec2i, Real)>, // click position, time left
+}
+
+impl<R: Renderer> Context<R> {
+    // Presentation mode: a highlight follows the cursor and every click leaves a ripple, drawn over the UI so
+    // that demos and recorded tutorials are easy to follow.
+    pub fn set_presentation_mode(&mut self, enabled: bool) {
+        self.presentation = if enabled { Some(Presentation { ripples: Vec::new() }) } else { None };
+    }
+
+    pub fn is_presentation_mode(&self) -> bool {
+        self.presentation.is_some()
+    }
+
+    pub(crate) fn presentation_end(&mut self) {
+        let Some(mut p) = self.presentation.take() else {
+            return;
+        };
+
+        let (mouse, pressed, dt) = {
+            let input = self.input.borrow();
+            (input.mouse_pos, input.mouse_pressed, input.frame_dt)
+        };
+        for (_, t) in &mut p.ripples {
+            *t -= dt;
+        }
+        p.ripples.retain(|(_, t)| *t > 0.0);
+        if !pressed.is_none() {
+            p.ripples.push((mouse, RIPPLE_TIME));
+        }
+
+        let ripples = &p.ripples;
+        self.overlay_layer(|o| {
+            let accent = o.style.color(ControlColor::Accent);
+            let faded = |alpha: Real| color(accent.r, accent.g, accent.b, (accent.a as Real * alpha) as u8);
+            let full = 2.0 * f32::consts::PI;
+            for (pos, t) in ripples {
+                let k = 1.0 - *t / RIPPLE_TIME;
+                let center = Vec2f::new(pos.x as f32, pos.y as f32);
+                o.draw_arc(center, HIGHLIGHT_RADIUS + (RIPPLE_RADIUS - HIGHLIGHT_RADIUS) * k, 3.0, 0.0, full, faded(1.0 - k));
+            }
+            let center = Vec2f::new(mouse.x as f32, mouse.y as f32);
+            o.draw_circle(center, HIGHLIGHT_RADIUS, faded(0.25));
+            o.draw_arc(center, HIGHLIGHT_RADIUS, 2.0, 0.0, full, faded(0.8));
+        });
+        self.presentation = Some(p);
+    }
+}
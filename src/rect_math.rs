@@ -0,0 +1,86 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Branch-light rect helpers for the clipping hot paths: min/max and non short-circuit boolean operators compile
+// to conditional moves and vectorize when applied over slices.
+use super::*;
+
+#[inline(always)]
+fn right(r: &Recti) -> i32 {
+    r.x + r.width
+}
+
+#[inline(always)]
+fn bottom(r: &Recti) -> i32 {
+    r.y + r.height
+}
+
+// intersection of two rects, empty rects have a zero width or height
+#[inline]
+pub fn rect_intersect(a: &Recti, b: &Recti) -> Recti {
+    let x = max(a.x, b.x);
+    let y = max(a.y, b.y);
+    let w = max(min(right(a), right(b)) - x, 0);
+    let h = max(min(bottom(a), bottom(b)) - y, 0);
+    Recti { x, y, width: w, height: h }
+}
+
+#[inline]
+pub fn rect_is_empty(r: &Recti) -> bool {
+    (r.width <= 0) | (r.height <= 0)
+}
+
+// true if the rects overlap or touch
+#[inline]
+pub fn rect_touches(a: &Recti, b: &Recti) -> bool {
+    (a.x <= right(b)) & (right(a) >= b.x) & (a.y <= bottom(b)) & (bottom(a) >= b.y)
+}
+
+#[inline]
+pub fn rect_contains_rect(outer: &Recti, inner: &Recti) -> bool {
+    (inner.x >= outer.x) & (inner.y >= outer.y) & (right(inner) <= right(outer)) & (bottom(inner) <= bottom(outer))
+}
+
+#[inline]
+pub fn rect_contains_point(r: &Recti, p: &Vec2i) -> bool {
+    (p.x >= r.x) & (p.y >= r.y) & (p.x < right(r)) & (p.y < bottom(r))
+}
+
+// Short-circuiting is kept here on purpose: most widgets are either fully inside or fully outside, and the early
+// exits measured faster than the branch-free form (see benches/rect_math.rs)
+#[inline]
+pub fn rect_clip_state(r: &Recti, clip: &Recti) -> Clip {
+    if r.x > right(clip) || right(r) < clip.x || r.y > bottom(clip) || bottom(r) < clip.y {
+        Clip::All
+    } else if r.x >= clip.x && r.y >= clip.y && right(r) <= right(clip) && bottom(r) <= bottom(clip) {
+        Clip::None
+    } else {
+        Clip::Part
+    }
+}
@@ -67,6 +67,19 @@ pub struct Config {
     pub border_padding: i32,
     /// Minimum spacing between rectangles.
     pub rectangle_padding: i32,
+
+    /// Placement strategy.
+    pub algorithm: PackingAlgorithm,
+}
+
+/// Placement strategies of `Packer`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum PackingAlgorithm {
+    /// Bottom-left placement over a skyline, fast and good for rectangles of similar heights (glyphs).
+    #[default]
+    Skyline,
+    /// Best short side fit over a list of free rectangles, wastes less space with mixed sizes.
+    Guillotine,
 }
 
 pub trait RectTrait {
@@ -127,11 +140,20 @@ impl RectTrait for Recti {
     }
 }
 
+#[derive(Clone)]
+enum Backend {
+    Skyline(DensePacker),
+    Guillotine(GuillotinePacker),
+}
+
 /// `Packer` is the main structure in this crate. It holds packing context.
 #[derive(Clone)]
 pub struct Packer {
     config: Config,
-    packer: DensePacker,
+    packer: Backend,
+    released: GuillotinePacker, // space given back to a skyline, which can't reclaim it itself
+    used_area: i64,
+    count: usize,
 }
 
 impl Packer {
@@ -146,9 +168,73 @@ impl Packer {
             config.height + config.rectangle_padding - 2 * config.border_padding,
         );
 
+        let packer = match config.algorithm {
+            PackingAlgorithm::Skyline => Backend::Skyline(DensePacker::new(width, height)),
+            PackingAlgorithm::Guillotine => Backend::Guillotine(GuillotinePacker::new(width, height)),
+        };
         Packer {
             config: config,
-            packer: DensePacker::new(width, height),
+            packer,
+            released: GuillotinePacker::empty(width, height),
+            used_area: 0,
+            count: 0,
+        }
+    }
+
+    /// Forget every packed rectangle.
+    pub fn reset(&mut self) {
+        *self = Packer::new(self.config);
+    }
+
+    /// Reset and pack `sizes` again, largest first, to defragment the free space.
+    /// The results are in the order of `sizes`, `None` for the rectangles that no longer fit.
+    pub fn repack(&mut self, sizes: &[(i32, i32)], allow_rotation: bool) -> Vec<Option<Recti>> {
+        self.reset();
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|i| {
+            let (w, h) = sizes[*i];
+            (std::cmp::Reverse(h), std::cmp::Reverse(w))
+        });
+        let mut res = vec![None; sizes.len()];
+        for i in order {
+            res[i] = self.pack(sizes[i].0, sizes[i].1, allow_rotation);
+        }
+        res
+    }
+
+    /// Give back a rectangle returned by `pack` so its space can be reused. Adjacent free space is coalesced.
+    pub fn free(&mut self, rect: Recti) {
+        let r = Rect::new(
+            rect.x - self.config.border_padding,
+            rect.y - self.config.border_padding,
+            rect.width + self.config.rectangle_padding,
+            rect.height + self.config.rectangle_padding,
+        );
+        match &mut self.packer {
+            Backend::Skyline(_) => self.released.free(r),
+            Backend::Guillotine(p) => p.free(r),
+        }
+        self.used_area -= rect.width as i64 * rect.height as i64;
+        self.count = self.count.saturating_sub(1);
+    }
+
+    /// Area covered by the packed rectangles, padding excluded.
+    pub fn used_area(&self) -> i64 {
+        self.used_area
+    }
+
+    /// Number of packed rectangles.
+    pub fn packed_count(&self) -> usize {
+        self.count
+    }
+
+    /// Fraction of the encompassing rectangle covered by the packed rectangles.
+    pub fn occupancy(&self) -> f32 {
+        let total = self.config.width as i64 * self.config.height as i64;
+        if total <= 0 {
+            0.0
+        } else {
+            self.used_area as f32 / total as f32
         }
     }
 
@@ -167,15 +253,18 @@ impl Packer {
             return None;
         }
 
-        if let Some(mut rect) = self.packer.pack(
-            width + self.config.rectangle_padding,
-            height + self.config.rectangle_padding,
-            allow_rotation,
-        ) {
+        let (w, h) = (width + self.config.rectangle_padding, height + self.config.rectangle_padding);
+        let packed = match &mut self.packer {
+            Backend::Skyline(p) => self.released.pack(w, h, allow_rotation).or_else(|| p.pack(w, h, allow_rotation)),
+            Backend::Guillotine(p) => p.pack(w, h, allow_rotation),
+        };
+        if let Some(mut rect) = packed {
             rect.width -= self.config.rectangle_padding;
             rect.height -= self.config.rectangle_padding;
             rect.x += self.config.border_padding;
             rect.y += self.config.border_padding;
+            self.used_area += rect.width as i64 * rect.height as i64;
+            self.count += 1;
 
             Some(rect)
         } else {
@@ -185,11 +274,11 @@ impl Packer {
 
     /// Check if rectangle with the specified size can be added.
     pub fn can_pack(&self, width: i32, height: i32, allow_rotation: bool) -> bool {
-        self.packer.can_pack(
-            width + self.config.rectangle_padding,
-            height + self.config.rectangle_padding,
-            allow_rotation,
-        )
+        let (w, h) = (width + self.config.rectangle_padding, height + self.config.rectangle_padding);
+        match &self.packer {
+            Backend::Skyline(p) => self.released.can_pack(w, h, allow_rotation) || p.can_pack(w, h, allow_rotation),
+            Backend::Guillotine(p) => p.can_pack(w, h, allow_rotation),
+        }
     }
 }
 
@@ -384,3 +473,131 @@ impl DensePacker {
         }
     }
 }
+
+/// Guillotine packer without padding: keeps a list of free rectangles, places each rectangle in the free one
+/// leaving the shortest leftover side and splits the remainder along the shorter axis.
+#[derive(Clone)]
+pub struct GuillotinePacker {
+    width: i32,
+    height: i32,
+    free: Vec<Recti>,
+}
+
+impl GuillotinePacker {
+    /// Create new empty `GuillotinePacker` with the provided parameters.
+    pub fn new(width: i32, height: i32) -> GuillotinePacker {
+        let width = std::cmp::max(0, width);
+        let height = std::cmp::max(0, height);
+        GuillotinePacker {
+            width,
+            height,
+            free: vec![Rect::new(0, 0, width, height)],
+        }
+    }
+
+    // a packer without free space, filled by `free`
+    fn empty(width: i32, height: i32) -> GuillotinePacker {
+        GuillotinePacker { width, height, free: Vec::new() }
+    }
+
+    /// Get size that this packer was created with.
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// Give back a packed rectangle, merging it with the adjacent free rectangles.
+    pub fn free(&mut self, rect: Recti) {
+        if rect.width > 0 && rect.height > 0 {
+            self.free.push(rect);
+            self.merge();
+        }
+    }
+
+    /// Pack new rectangle. Returns position of the newly added rectangle. If there is not enough space returns `None`.
+    pub fn pack(&mut self, width: i32, height: i32, allow_rotation: bool) -> Option<Recti> {
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+
+        let (i, rect) = self.find_free(width, height, allow_rotation)?;
+        let free = self.free.swap_remove(i);
+        self.split(&free, &rect);
+        self.merge();
+        Some(rect)
+    }
+
+    /// Check if rectangle with the specified size can be added.
+    pub fn can_pack(&self, width: i32, height: i32, allow_rotation: bool) -> bool {
+        self.find_free(width, height, allow_rotation).is_some()
+    }
+
+    fn find_free(&self, w: i32, h: i32, allow_rotation: bool) -> Option<(usize, Recti)> {
+        let mut best: Option<(i32, usize, Recti)> = None;
+        for (i, f) in self.free.iter().enumerate() {
+            let mut candidates = vec![(w, h)];
+            if allow_rotation && w != h {
+                candidates.push((h, w));
+            }
+            for (cw, ch) in candidates {
+                if cw <= f.width && ch <= f.height {
+                    let short_side = std::cmp::min(f.width - cw, f.height - ch);
+                    if best.is_none_or(|(s, _, _)| short_side < s) {
+                        best = Some((short_side, i, Rect::new(f.x, f.y, cw, ch)));
+                    }
+                }
+            }
+        }
+        best.map(|(_, i, r)| (i, r))
+    }
+
+    fn split(&mut self, free: &Recti, rect: &Recti) {
+        let right_w = free.width - rect.width;
+        let bottom_h = free.height - rect.height;
+        // the longer leftover keeps the full extent of the free rectangle
+        let (right, bottom) = if right_w < bottom_h {
+            (
+                Rect::new(rect.right(), free.y, right_w, rect.height),
+                Rect::new(free.x, rect.bottom(), free.width, bottom_h),
+            )
+        } else {
+            (
+                Rect::new(rect.right(), free.y, right_w, free.height),
+                Rect::new(free.x, rect.bottom(), rect.width, bottom_h),
+            )
+        };
+        for r in [right, bottom] {
+            if r.width > 0 && r.height > 0 {
+                self.free.push(r);
+            }
+        }
+    }
+
+    // join free rectangles sharing a full edge
+    fn merge(&mut self) {
+        let mut i = 0;
+        while i < self.free.len() {
+            let mut j = i + 1;
+            let mut merged = false;
+            while j < self.free.len() {
+                let (a, b) = (self.free[i], self.free[j]);
+                let joined = if a.y == b.y && a.height == b.height && (a.right() == b.left() || b.right() == a.left()) {
+                    Some(Rect::new(std::cmp::min(a.x, b.x), a.y, a.width + b.width, a.height))
+                } else if a.x == b.x && a.width == b.width && (a.bottom() == b.top() || b.bottom() == a.top()) {
+                    Some(Rect::new(a.x, std::cmp::min(a.y, b.y), a.width, a.height + b.height))
+                } else {
+                    None
+                };
+                if let Some(r) = joined {
+                    self.free[i] = r;
+                    self.free.swap_remove(j);
+                    merged = true;
+                } else {
+                    j += 1;
+                }
+            }
+            if !merged {
+                i += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Line based mirroring protocol. The server streams each frame as:
+//   frame <width> <height>
+//   clip <x> <y> <w> <h>
+//   rect <x> <y> <w> <h> <r> <g> <b> <a>
+//   text <font> <x> <y> <r> <g> <b> <a> <text, \n and \\ escaped>
+//   icon|slot <id> <x> <y> <w> <h> <r> <g> <b> <a>
+//   tri <x0> <y0> <x1> <y1> <x2> <y2> <r> <g> <b> <a>
+//   custom <x> <y> <w> <h>
+//   end
+// and the viewer sends back its input, one event per line:
+//   move <x> <y> | down <x> <y> <buttons> | up <x> <y> <buttons> | scroll <x> <y>
+//   keydown <keys> | keyup <keys> | text <text>
+use super::*;
+use std::fmt::Write as FmtWrite;
+use std::io::{BufRead, BufReader, ErrorKind, Result, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => (),
+            },
+            (c, false) => res.push(c),
+        }
+    }
+    res
+}
+
+fn write_color(out: &mut String, c: &Color) {
+    let _ = write!(out, " {} {} {} {}", c.r, c.g, c.b, c.a);
+}
+
+pub fn encode_frame<R: Renderer>(ctx: &Context<R>) -> String {
+    let mut out = String::new();
+    let dim = ctx.canvas().current_dimension();
+    let _ = writeln!(out, "frame {} {}", dim.width, dim.height);
+    ctx.visit_frame_commands(|container, command| {
+        match command {
+            Command::Clip { rect } => {
+                let _ = write!(out, "clip {} {} {} {}", rect.x, rect.y, rect.width, rect.height);
+            }
+            Command::Recti { rect, color } => {
+                let _ = write!(out, "rect {} {} {} {}", rect.x, rect.y, rect.width, rect.height);
+                write_color(&mut out, color);
+            }
+            Command::Text { font, pos, color, text } => {
+                let _ = write!(out, "text {} {} {}", Into::<u32>::into(*font), pos.x, pos.y);
+                write_color(&mut out, color);
+                let _ = write!(out, " {}", escape(container.command_text(*text)));
+            }
+            Command::Icon { rect, id, color } => {
+                let _ = write!(out, "icon {} {} {} {} {}", Into::<u32>::into(*id), rect.x, rect.y, rect.width, rect.height);
+                write_color(&mut out, color);
+            }
+            Command::Slot { rect, id, color } | Command::SlotRedraw { rect, id, color, .. } => {
+                let _ = write!(out, "slot {} {} {} {} {}", Into::<u32>::into(*id), rect.x, rect.y, rect.width, rect.height);
+                write_color(&mut out, color);
+            }
+            Command::Atlas { rect, src, color } => {
+                let _ = write!(out, "atlas {} {} {} {} {} {} {} {}", src.x, src.y, src.width, src.height, rect.x, rect.y, rect.width, rect.height);
+                write_color(&mut out, color);
+            }
+            Command::Triangle { points, color } => {
+                let _ = write!(out, "tri {} {} {} {} {} {}", points[0].x, points[0].y, points[1].x, points[1].y, points[2].x, points[2].y);
+                write_color(&mut out, color);
+            }
+            Command::Mask { mask: Some(m) } => {
+                let _ = write!(out, "mask {} {} {} {} {}", m.rect.x, m.rect.y, m.rect.width, m.rect.height, m.radius);
+            }
+            Command::Mask { mask: None } => out.push_str("mask none"),
+            Command::Sampler { sampler } => {
+                let filter = if sampler.filter == TextureFilter::Linear { "linear" } else { "nearest" };
+                let _ = write!(out, "sampler {} {}", filter, sampler.mipmaps as u8);
+            }
+            Command::CustomRender(cra, _) => {
+                let r = cra.content_area;
+                let _ = write!(out, "custom {} {} {} {}", r.x, r.y, r.width, r.height);
+            }
+            Command::None => return,
+        }
+        out.push('\n');
+    });
+    out.push_str("end\n");
+    out
+}
+
+// Applies one input line sent by the viewer, unknown or malformed lines are ignored
+pub fn apply_input_line(input: &mut Input, line: &str) {
+    let (cmd, args) = line.split_once(' ').unwrap_or((line, ""));
+    if cmd == "text" {
+        input.text(unescape(args).as_str());
+        return;
+    }
+
+    let v: Vec<i32> = args.split_whitespace().filter_map(|a| a.parse().ok()).collect();
+    match (cmd, v.as_slice()) {
+        ("move", [x, y]) => input.mousemove(*x, *y),
+        ("down", [x, y, b]) => input.mousedown(*x, *y, MouseButton::from_bits_truncate(*b as u32)),
+        ("up", [x, y, b]) => input.mouseup(*x, *y, MouseButton::from_bits_truncate(*b as u32)),
+        ("scroll", [x, y]) => input.scroll_by(*x, *y),
+        ("keydown", [k]) => input.keydown(KeyMode::from_bits_truncate(*k as u32)),
+        ("keyup", [k]) => input.keyup(KeyMode::from_bits_truncate(*k as u32)),
+        _ => (),
+    }
+}
+
+// Serves a single viewer at a time, all the calls are non blocking so they can be made from the frame loop.
+// A frame the socket can't take right away stays pending, new frames are dropped until it is flushed
+pub struct RemoteServer {
+    listener: TcpListener,
+    client: Option<(TcpStream, BufReader<TcpStream>)>,
+    pending: Vec<u8>,
+}
+
+impl RemoteServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, client: None, pending: Vec::new() })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.client.is_some()
+    }
+
+    fn accept(&mut self) {
+        if self.client.is_some() {
+            return;
+        }
+        if let Ok((stream, _)) = self.listener.accept() {
+            let reader = stream.try_clone().and_then(|s| {
+                s.set_nonblocking(true)?;
+                Ok(BufReader::new(s))
+            });
+            if let Ok(reader) = reader {
+                let _ = stream.set_nodelay(true);
+                self.pending.clear();
+                self.client = Some((stream, reader));
+            }
+        }
+    }
+
+    // feeds the pending viewer input into the context, call it before `Context::frame`
+    pub fn poll_input<R: Renderer>(&mut self, ctx: &mut Context<R>) {
+        self.accept();
+        let mut disconnect = false;
+        if let Some((_, reader)) = &mut self.client {
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match reader.read_line(&mut line) {
+                    Ok(0) => {
+                        disconnect = true;
+                        break;
+                    }
+                    Ok(_) => apply_input_line(&mut ctx.input.borrow_mut(), line.trim_end_matches(['\r', '\n'])),
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(_) => {
+                        disconnect = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if disconnect {
+            self.client = None;
+        }
+    }
+
+    // writes as much of the pending frame as the socket takes, returns false when the viewer is gone
+    fn flush(&mut self) -> bool {
+        let Some((stream, _)) = &mut self.client else { return true };
+        while !self.pending.is_empty() {
+            match stream.write(&self.pending) {
+                Ok(0) => return false,
+                Ok(n) => {
+                    self.pending.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(_) => return false,
+            }
+        }
+        true
+    }
+
+    // sends the last frame, call it after `Context::end`. The frame is skipped while the previous one is still pending
+    pub fn send_frame<R: Renderer>(&mut self, ctx: &Context<R>) {
+        self.accept();
+        if self.client.is_none() {
+            return;
+        }
+        let mut alive = self.flush();
+        if alive && self.pending.is_empty() {
+            self.pending.extend_from_slice(encode_frame(ctx).as_bytes());
+            alive = self.flush();
+        }
+        if !alive {
+            self.client = None;
+            self.pending.clear();
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+// A layer of the rendered UI, windows are drawn from the back to the front and the overlay last
+#[derive(Clone)]
+pub enum RenderLayer {
+    Window(WindowHandle),
+    Overlay,
+}
+
+impl RenderLayer {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Window(w) => w.name(),
+            Self::Overlay => "!overlay".to_string(),
+        }
+    }
+
+    fn is(&self, other: &RenderLayer) -> bool {
+        match (self, other) {
+            (Self::Window(a), Self::Window(b)) => a.ptr_eq(b),
+            (Self::Overlay, Self::Overlay) => true,
+            _ => false,
+        }
+    }
+}
+
+pub(crate) type RenderPass = Box<dyn FnOnce(Dimensioni)>;
+
+impl<R: Renderer> Context<R> {
+    // The order `end` renders the layers of the last frame in. Windows follow their z-index, change it
+    // with `bring_to_front` or `WindowHandle::set_zindex` to reorder them.
+    pub fn render_order(&self) -> Vec<RenderLayer> {
+        let mut order: Vec<RenderLayer> = self.root_list.iter().map(|w| RenderLayer::Window(w.clone())).collect();
+        order.push(RenderLayer::Overlay);
+        order
+    }
+
+    // Runs `pass` during the next `end`, right before `layer` is rendered, e.g. to draw a 3D viewport
+    // between two windows. The UI batch is flushed first, the pass gets the canvas size.
+    pub fn insert_render_pass<F: FnOnce(Dimensioni) + 'static>(&mut self, layer: &RenderLayer, pass: F) {
+        self.render_passes.push((layer.clone(), Box::new(pass)));
+    }
+
+    pub(crate) fn run_render_passes(&mut self, layer: &RenderLayer) {
+        let mut i = 0;
+        while i < self.render_passes.len() {
+            if self.render_passes[i].0.is(layer) {
+                let (_, pass) = self.render_passes.remove(i);
+                self.canvas.end();
+                pass(self.canvas.current_dimension());
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
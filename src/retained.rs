@@ -0,0 +1,176 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
+
+// a recorded command, the text of `Command::Text` is kept here since the arena is reset every frame
+enum RetainedCommand {
+    Draw(Command),
+    Text { font: FontId, pos: Vec2i, color: Color, text: String },
+}
+
+pub(crate) struct RetainedTree {
+    key: u64,
+    commands: Vec<RetainedCommand>,
+    widgets: Vec<(Id, Recti)>,
+    focus_order: Vec<(Id, Recti)>, // the keyboard navigation walks them
+    bounds: Option<Recti>,
+    layout: (Layout, usize, Recti, Vec<i32>),
+    live: bool, // recorded with a hovered or focused widget, its highlight mustn't be replayed
+}
+
+// custom renders own their closure, they can't be copied
+fn copy_command(cmd: &Command) -> Option<Command> {
+    Some(match cmd {
+        Command::Clip { rect } => Command::Clip { rect: *rect },
+        Command::Recti { rect, color } => Command::Recti { rect: *rect, color: *color },
+        Command::Text { font, pos, color, text } => Command::Text { font: *font, pos: *pos, color: *color, text: *text },
+        Command::Icon { rect, id, color } => Command::Icon { rect: *rect, id: *id, color: *color },
+        Command::Triangle { points, color } => Command::Triangle { points: *points, color: *color },
+        Command::Slot { rect, id, color } => Command::Slot { rect: *rect, id: *id, color: *color },
+        Command::SlotRedraw { rect, id, color, payload } => Command::SlotRedraw { rect: *rect, id: *id, color: *color, payload: payload.clone() },
+        Command::Atlas { rect, src, color } => Command::Atlas { rect: *rect, src: *src, color: *color },
+        Command::Mask { mask } => Command::Mask { mask: *mask },
+        Command::Sampler { sampler } => Command::Sampler { sampler: *sampler },
+        Command::CustomRender(..) => return None,
+        Command::None => Command::None,
+    })
+}
+
+fn retain_command(cmd: &Command, arena: &FrameArena) -> Option<RetainedCommand> {
+    match cmd {
+        Command::Text { font, pos, color, text } => Some(RetainedCommand::Text { font: *font, pos: *pos, color: *color, text: arena.get(*text).to_string() }),
+        _ => copy_command(cmd).map(RetainedCommand::Draw),
+    }
+}
+
+impl Container {
+    fn retained_key<K: Hash>(&mut self, inputs: &K) -> u64 {
+        let mut h = DefaultHasher::new();
+        inputs.hash(&mut h);
+        self.layout.cache_key().hash(&mut h);
+        let clip = self.get_clip_rect();
+        [clip.x, clip.y, clip.width, clip.height].hash(&mut h);
+        if let Some(tint) = self.tint_stack.last() {
+            [tint.color.r, tint.color.g, tint.color.b, tint.color.a, tint.grayscale as u8].hash(&mut h);
+        }
+        if let Some(mask) = self.mask_stack.last() {
+            [mask.rect.x, mask.rect.y, mask.rect.width, mask.rect.height, mask.radius].hash(&mut h);
+        }
+        self.disabled.hash(&mut h);
+        h.finish()
+    }
+
+    // the widgets of the subtree need to run while the mouse is over them, one of them is being edited or
+    // is about to be activated
+    fn retained_is_live(&self, bounds: Option<Recti>, widgets: &[(Id, Recti)]) -> bool {
+        let mouse = self.input.borrow().mouse_pos;
+        let over = bounds.map(|b| b.contains(&mouse)).unwrap_or(false);
+        over || widgets.iter().any(|(id, _)| {
+            let id = Some(*id);
+            self.hover == id || self.focus == id || self.text_edit == id || self.number_edit == id || self.activated == id || self.activate_next == id
+        })
+    }
+
+    // Runs `build` only when `inputs`, the layout position, the clip or the tint changed since the last
+    // frame, otherwise replays the commands it produced. The subtree is rebuilt while the mouse is over
+    // its widgets so they stay interactive. Subtrees with panels or custom renders are always built.
+    // Returns true when the commands were replayed.
+    pub fn retained<K: Hash, F: FnOnce(&mut Self)>(&mut self, name: &str, inputs: &K, build: F) -> bool {
+        let id = self.idmngr.get_id_from_str(name);
+        let key = self.retained_key(inputs);
+        if let Some(tree) = self.retained.remove(&id) {
+            // a tree recorded while live is rebuilt once more after the mouse left, dropping the highlight
+            if tree.key == key && !tree.live && !self.retained_is_live(tree.bounds, &tree.widgets) {
+                self.replay_retained(&tree);
+                self.retained.insert(id, tree);
+                return true;
+            }
+        }
+
+        let cmd_start = self.command_list.len();
+        let panel_count = self.panels.len();
+        let focus_start = self.focus_order.len();
+        let known: HashSet<Id> = self.widget_rects.keys().copied().collect();
+        self.idmngr.push_id(id);
+        build(self);
+        self.idmngr.pop_id();
+        if self.panels.len() != panel_count {
+            return false;
+        }
+        let mut commands = Vec::with_capacity(self.command_list.len() - cmd_start);
+        for cmd in &self.command_list[cmd_start..] {
+            match retain_command(cmd, &self.arena) {
+                Some(cmd) => commands.push(cmd),
+                None => return false,
+            }
+        }
+        let widgets: Vec<(Id, Recti)> = self.widget_rects.iter().filter(|(id, _)| !known.contains(id)).map(|(id, r)| (*id, *r)).collect();
+        let bounds = widgets.iter().map(|(_, r)| *r).reduce(|a, b| {
+            let x = min(a.x, b.x);
+            let y = min(a.y, b.y);
+            rect(x, y, max(a.x + a.width, b.x + b.width) - x, max(a.y + a.height, b.y + b.height) - y)
+        });
+        let focus_order = self.focus_order[focus_start..].to_vec();
+        let layout = (*self.layout.top(), self.layout.item_index, self.layout.last_rect, self.layout.current_row_widths.clone());
+        let live = self.retained_is_live(bounds, &widgets);
+        self.retained.insert(id, RetainedTree { key, commands, widgets, focus_order, bounds, layout, live });
+        false
+    }
+
+    fn replay_retained(&mut self, tree: &RetainedTree) {
+        for cmd in &tree.commands {
+            let cmd = match cmd {
+                RetainedCommand::Text { font, pos, color, text } => Command::Text { font: *font, pos: *pos, color: *color, text: self.arena.alloc_str(text) },
+                RetainedCommand::Draw(cmd) => copy_command(cmd).unwrap_or_default(),
+            };
+            // already tinted when recorded
+            self.command_list.push(cmd);
+        }
+        for (id, r) in &tree.widgets {
+            self.widget_rects.insert(*id, *r);
+        }
+        self.focus_order.extend_from_slice(&tree.focus_order);
+        let (top, item_index, last_rect, widths) = &tree.layout;
+        *self.layout.top_mut() = *top;
+        self.layout.item_index = *item_index;
+        self.layout.last_rect = *last_rect;
+        self.layout.current_row_widths.clone_from(widths);
+    }
+
+    pub fn forget_retained(&mut self, name: &str) {
+        let id = self.idmngr.get_id_from_str(name);
+        self.retained.remove(&id);
+    }
+}
+
+pub(crate) type RetainedTrees = HashMap<Id, RetainedTree>;
@@ -0,0 +1,67 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// moves `r` inside `area`, keeping its size, the top left corner wins when it doesn't fit
+pub(crate) fn clamp_rect_into(r: Recti, area: Recti) -> Recti {
+    let x = max(area.x, min(r.x, area.x + area.width - r.width));
+    let y = max(area.y, min(r.y, area.y + area.height - r.height));
+    rect(x, y, r.width, r.height)
+}
+
+impl<R: Renderer> Context<R> {
+    // Reserves the edges of the screen covered by notches, rounded corners or system bars. While the insets
+    // aren't zero the popups open inside the remaining area and the windows can't be dragged out of it,
+    // maximized windows, side panels and the overlay layer fill it.
+    pub fn set_safe_area(&mut self, insets: Margin) {
+        self.safe_insets = insets;
+    }
+
+    pub fn safe_insets(&self) -> Margin {
+        self.safe_insets
+    }
+
+    // the screen minus the insets
+    pub fn safe_area(&self) -> Recti {
+        let dim = self.canvas.current_dimension();
+        let m = self.safe_insets;
+        rect(m.left, m.top, max(dim.width - m.left - m.right, 0), max(dim.height - m.top - m.bottom, 0))
+    }
+
+    // moves a window placed by the library (popups) inside the safe area
+    pub(crate) fn clamp_to_safe_area(&self, window: &mut WindowHandle) {
+        if self.safe_insets == Margin::ZERO {
+            return;
+        }
+        let area = self.safe_area();
+        let mut w = window.inner_mut();
+        w.main.rect = clamp_rect_into(w.main.rect, area);
+    }
+}
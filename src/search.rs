@@ -0,0 +1,136 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::ops::Range;
+
+// Splits `text` into consecutive segments, flagging the ones matching `query` (ascii case insensitive)
+pub fn highlight_matches(text: &str, query: &str) -> Vec<(Range<usize>, bool)> {
+    let mut res = Vec::new();
+    if query.is_empty() {
+        if !text.is_empty() {
+            res.push((0..text.len(), false));
+        }
+        return res;
+    }
+
+    let hay = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    let mut last = 0;
+    for (start, m) in hay.match_indices(needle.as_str()) {
+        if start > last {
+            res.push((last..start, false));
+        }
+        res.push((start..start + m.len(), true));
+        last = start + m.len();
+    }
+    if last < text.len() {
+        res.push((last..text.len(), false));
+    }
+    res
+}
+
+pub fn matches_query(text: &str, query: &str) -> bool {
+    query.is_empty() || text.to_ascii_lowercase().contains(query.to_ascii_lowercase().as_str())
+}
+
+impl Container {
+    pub fn draw_highlighted_text(&mut self, text: &str, query: &str, r: Recti, accent: Color, opt: WidgetOption) {
+        let font = self.style.font;
+        let tsize = self.atlas.get_text_size(font, text);
+        let padding = self.style.padding;
+        let color = self.style.colors[ControlColor::Text as usize];
+
+        self.push_clip_rect(r);
+        let y = r.y + (r.height - tsize.height) / 2;
+        let mut x = if opt.is_aligned_center() {
+            r.x + (r.width - tsize.width) / 2
+        } else if opt.is_aligned_right() {
+            r.x + r.width - tsize.width - padding
+        } else {
+            r.x + padding
+        };
+        for (range, matched) in highlight_matches(text, query) {
+            let segment = &text[range];
+            let w = self.atlas.get_text_size(font, segment).width;
+            if matched {
+                self.draw_rect(rect(x, y, w, tsize.height), accent);
+            }
+            self.draw_text(font, segment, vec2(x, y), color);
+            x += w;
+        }
+        self.pop_clip_rect();
+    }
+
+    pub fn label_highlighted(&mut self, text: &str, query: &str, accent: Color) {
+        let r = self.layout.next();
+        self.draw_highlighted_text(text, query, r, accent, WidgetOption::NONE);
+    }
+
+    #[inline(never)]
+    pub fn searchbox(&mut self, query: &mut String, placeholder: &str) -> ResourceState {
+        let id: Id = self.idmngr.get_id_from_ptr(query);
+        let mut r: Recti = self.layout.next();
+
+        // clear button on the right side of the box
+        let clear = rect(r.x + r.width - r.height, r.y, r.height, r.height);
+        self.idmngr.push_id(id);
+        let clear_id = self.idmngr.get_id_from_str("!searchclear");
+        self.idmngr.pop_id();
+        let mut res = ResourceState::NONE;
+        if !query.is_empty() {
+            r.width -= clear.width;
+        }
+
+        res |= self.textbox_raw(query, id, r, WidgetOption::NONE);
+        if query.is_empty() && self.focus != Some(id) {
+            // placeholder is drawn with a faded text color
+            let font = self.style.font;
+            let mut color = self.style.colors[ControlColor::Text as usize];
+            color.a /= 2;
+            let tsize = self.atlas.get_text_size(font, placeholder);
+            let pos = vec2(r.x + self.style.padding, r.y + (r.height - tsize.height) / 2);
+            self.push_clip_rect(r);
+            self.draw_text(font, placeholder, pos, color);
+            self.pop_clip_rect();
+        }
+
+        if !query.is_empty() {
+            self.update_control(clear_id, clear, WidgetOption::NONE);
+            self.draw_widget_frame(clear_id, clear, ControlColor::Button, WidgetOption::NONE);
+            let color = self.style.colors[ControlColor::Text as usize];
+            self.draw_icon(CLOSE_ICON, clear, color);
+            if self.is_clicked(clear_id) {
+                query.clear();
+                res |= ResourceState::CHANGE;
+            }
+        }
+        res
+    }
+}
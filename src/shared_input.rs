@@ -0,0 +1,119 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Thread safe back buffer for the input: an event thread feeds it while the UI thread works on its own snapshot.
+// Once attached to the context, the events gathered since the last frame are moved into the snapshot when the
+// frame begins.
+use super::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+pub struct SharedInput(Arc<Mutex<Input>>);
+
+impl SharedInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<Res, F: FnOnce(&mut Input) -> Res>(&self, f: F) -> Res {
+        f(&mut self.0.lock().unwrap())
+    }
+
+    pub fn mousemove(&self, x: i32, y: i32) {
+        self.with(|i| i.mousemove(x, y))
+    }
+
+    pub fn mousedown(&self, x: i32, y: i32, btn: MouseButton) {
+        self.with(|i| i.mousedown(x, y, btn))
+    }
+
+    pub fn mouseup(&self, x: i32, y: i32, btn: MouseButton) {
+        self.with(|i| i.mouseup(x, y, btn))
+    }
+
+    pub fn scroll(&self, x: Real, y: Real, unit: ScrollUnit) {
+        self.with(|i| i.scroll(x, y, unit))
+    }
+
+    pub fn scroll_by(&self, x: i32, y: i32) {
+        self.with(|i| i.scroll_by(x, y))
+    }
+
+    pub fn touch_down(&self, finger: u64, x: i32, y: i32) {
+        self.with(|i| i.touch_down(finger, x, y))
+    }
+
+    pub fn touch_move(&self, finger: u64, x: i32, y: i32) {
+        self.with(|i| i.touch_move(finger, x, y))
+    }
+
+    pub fn touch_up(&self, finger: u64) {
+        self.with(|i| i.touch_up(finger))
+    }
+
+    pub fn keydown(&self, key: KeyMode) {
+        self.with(|i| i.keydown(key))
+    }
+
+    pub fn keyup(&self, key: KeyMode) {
+        self.with(|i| i.keyup(key))
+    }
+
+    pub fn text(&self, text: &str) {
+        self.with(|i| i.text(text))
+    }
+
+    pub fn mnemonic(&self, key: char) {
+        self.with(|i| i.mnemonic(key))
+    }
+
+    // held state is copied, transient events (presses, scroll, text) are accumulated into `front` and consumed
+    pub(crate) fn swap_into(&self, front: &mut Input) {
+        let mut back = self.0.lock().unwrap();
+        front.mouse_pos = back.mouse_pos;
+        front.mouse_down = back.mouse_down;
+        front.key_down = back.key_down;
+        front.touches.clone_from(&back.touches);
+        front.mouse_pressed |= back.mouse_pressed;
+        front.key_pressed |= back.key_pressed;
+        front.scroll_delta.x += back.scroll_delta.x;
+        front.scroll_delta.y += back.scroll_delta.y;
+        front.scroll_lines = front.scroll_lines + back.scroll_lines;
+        front.scroll_pixels = front.scroll_pixels + back.scroll_pixels;
+        front.input_text.push_str(&back.input_text);
+        front.mnemonic = front.mnemonic.or(back.mnemonic.take());
+
+        back.mouse_pressed = MouseButton::NONE;
+        back.key_pressed = KeyMode::NONE;
+        back.scroll_delta = vec2(0, 0);
+        back.scroll_lines = Vec2f::default();
+        back.scroll_pixels = Vec2f::default();
+        back.input_text.clear();
+    }
+}
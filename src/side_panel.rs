@@ -0,0 +1,154 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// A tool panel docked to an edge of the screen. Hidden, it leaves a thin tab strip along the edge, hovering
+// the strip slides the panel out and leaving the panel hides it again. Clicking the strip pins the panel open
+// until the strip is clicked again.
+pub struct SidePanel {
+    tab: WindowHandle,
+    panel: WindowHandle,
+    pub edge: DrawerEdge,
+    pub size: i32,     // width or height of the fully opened panel
+    pub tab_size: i32, // thickness of the strip
+    pub speed: Real,   // fraction of the slide done per second
+    pub open_on_hover: bool,
+    pinned: bool,
+    slide: Slide,
+}
+
+impl SidePanel {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str, edge: DrawerEdge, size: i32) -> Self {
+        Self {
+            tab: ctx.new_window(&format!("!{}_tab", name), Recti::default()),
+            panel: ctx.new_window(name, Recti::default()),
+            edge,
+            size,
+            tab_size: 8,
+            speed: 8.0,
+            open_on_hover: true,
+            pinned: false,
+            slide: Slide::new(),
+        }
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    pub fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+        self.slide.open = pinned;
+    }
+
+    // true while the panel is (at least partially) visible
+    pub fn is_visible(&self) -> bool {
+        self.slide.progress > 0.0
+    }
+
+    fn tab_rect(&self, screen: Recti) -> Recti {
+        let t = self.tab_size;
+        match self.edge {
+            DrawerEdge::Left => rect(screen.x, screen.y, t, screen.height),
+            DrawerEdge::Right => rect(screen.x + screen.width - t, screen.y, t, screen.height),
+            DrawerEdge::Top => rect(screen.x, screen.y, screen.width, t),
+            DrawerEdge::Bottom => rect(screen.x, screen.y + screen.height - t, screen.width, t),
+        }
+    }
+
+    // the panel slides out next to the strip, which stays clickable to pin and unpin it
+    fn panel_rect(&self, screen: Recti) -> Recti {
+        let t = self.tab_size;
+        let screen = match self.edge {
+            DrawerEdge::Left => rect(screen.x + t, screen.y, screen.width - t, screen.height),
+            DrawerEdge::Right => rect(screen.x, screen.y, screen.width - t, screen.height),
+            DrawerEdge::Top => rect(screen.x, screen.y + t, screen.width, screen.height - t),
+            DrawerEdge::Bottom => rect(screen.x, screen.y, screen.width, screen.height - t),
+        };
+        self.slide.rect(self.edge, self.size, screen)
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Shows the strip of `state` and, while it's slid out, the panel with `f` as its content. The panel
+    // keeps the screen edge, moving and resizing it is disabled.
+    pub fn side_panel<F: FnOnce(&mut Container)>(&mut self, state: &mut SidePanel, opt: ContainerOption, f: F) {
+        let screen = self.safe_area();
+
+        let mut tab_hovered = false;
+        let mut tab_clicked = false;
+        let tab_r = state.tab_rect(screen);
+        state.tab.inner_mut().main.rect = tab_r;
+        let tab_opt = ContainerOption::NO_TITLE | ContainerOption::NO_FRAME | ContainerOption::NO_SCROLL | ContainerOption::NO_RESIZE;
+        let pinned = state.pinned;
+        self.window(&mut state.tab.clone(), tab_opt, |cont| {
+            let id = cont.idmngr.get_id_from_str("!side_tab");
+            let r = cont.rect;
+            cont.update_control(id, r, WidgetOption::NONE);
+            tab_hovered = cont.hover == Some(id);
+            tab_clicked = cont.is_clicked(id);
+            let colorid = if pinned {
+                ControlColor::Accent
+            } else if tab_hovered {
+                ControlColor::ButtonHover
+            } else {
+                ControlColor::Button
+            };
+            cont.draw_rect(r, cont.style.colors[colorid as usize]);
+            WindowState::Open
+        });
+
+        if tab_clicked {
+            state.pinned = !state.pinned;
+            state.slide.open = state.pinned;
+        } else if tab_hovered && state.open_on_hover {
+            state.slide.open = true;
+        } else if state.slide.open && !state.pinned && state.slide.progress >= 1.0 {
+            // hidden once the mouse leaves the panel, unless a widget of the panel is being dragged
+            let input = self.input.borrow();
+            let inside = state.panel_rect(screen).contains(&input.mouse_pos) || tab_r.contains(&input.mouse_pos);
+            if !inside && input.mouse_down.is_none() {
+                state.slide.open = false;
+            }
+        }
+
+        state.slide.animate(state.speed * self.input.borrow().frame_dt);
+        if !state.is_visible() {
+            return;
+        }
+        let mut panel = state.panel.clone();
+        panel.inner_mut().main.rect = state.panel_rect(screen);
+        self.bring_to_front(&mut panel);
+        self.window(&mut panel, opt | ContainerOption::NO_RESIZE | ContainerOption::NO_CLOSE, |cont| {
+            f(cont);
+            WindowState::Open
+        });
+    }
+}
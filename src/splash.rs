@@ -0,0 +1,190 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+
+// Sent by the loading thread through a `SplashHandle`
+#[derive(Clone, Debug)]
+pub enum SplashMessage {
+    Phase(usize),    // starts the phase, the status text becomes its name
+    Progress(Real),  // 0..1 within the current phase
+    Status(String),  // replaces the status text until the next phase
+    Finished,
+}
+
+// The sending side, it can be cloned and moved to other threads. Dropping every handle finishes the splash, so
+// a loader that fails doesn't leave the screen up forever.
+#[derive(Clone)]
+pub struct SplashHandle {
+    tx: Sender<SplashMessage>,
+}
+
+impl SplashHandle {
+    // the splash may be gone already, the updates are then ignored
+    pub fn send(&self, msg: SplashMessage) {
+        let _ = self.tx.send(msg);
+    }
+
+    pub fn phase(&self, index: usize) {
+        self.send(SplashMessage::Phase(index))
+    }
+
+    pub fn progress(&self, fraction: Real) {
+        self.send(SplashMessage::Progress(fraction))
+    }
+
+    pub fn status(&self, text: &str) {
+        self.send(SplashMessage::Status(text.to_string()))
+    }
+
+    pub fn finish(&self) {
+        self.send(SplashMessage::Finished)
+    }
+}
+
+// A startup screen covering the whole (safe) screen: an optional image, the status text and a progress bar.
+// Loading is split in weighted phases, the bar fills with the weight of the completed ones. Without phases
+// the loading is a single unnamed one.
+pub struct Splash {
+    win: WindowHandle,
+    image: Option<SlotId>,
+    phases: Vec<(String, Real)>, // name, weight
+    phase: usize,
+    fraction: Real,
+    status: String,
+    finished: bool,
+    rx: Receiver<SplashMessage>,
+}
+
+impl Splash {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str, phases: &[(&str, Real)]) -> (Self, SplashHandle) {
+        let mut phases: Vec<(String, Real)> = phases.iter().map(|(n, w)| (n.to_string(), w.max(0.0))).collect();
+        if phases.is_empty() {
+            phases.push((String::new(), 1.0));
+        }
+        let (tx, rx) = channel();
+        let mut win = ctx.new_dialog(name, ctx.safe_area());
+        ctx.open_dialog(&mut win);
+        let splash = Self {
+            win,
+            image: None,
+            status: phases[0].0.clone(),
+            phases,
+            phase: 0,
+            fraction: 0.0,
+            finished: false,
+            rx,
+        };
+        (splash, SplashHandle { tx })
+    }
+
+    pub fn set_image(&mut self, image: Option<SlotId>) {
+        self.image = image;
+    }
+
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    // overall progress, 0..1
+    pub fn progress(&self) -> Real {
+        let total: Real = self.phases.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return if self.finished { 1.0 } else { 0.0 };
+        }
+        let done: Real = self.phases[..self.phase].iter().map(|(_, w)| w).sum();
+        ((done + self.phases[self.phase].1 * self.fraction) / total).clamp(0.0, 1.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn receive(&mut self) {
+        loop {
+            match self.rx.try_recv() {
+                Ok(SplashMessage::Phase(i)) => {
+                    self.phase = i.min(self.phases.len() - 1);
+                    self.fraction = 0.0;
+                    self.status = self.phases[self.phase].0.clone();
+                }
+                Ok(SplashMessage::Progress(f)) => self.fraction = f.clamp(0.0, 1.0),
+                Ok(SplashMessage::Status(s)) => self.status = s,
+                Ok(SplashMessage::Finished) | Err(TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+            }
+        }
+    }
+
+    // Applies the pending updates and shows the splash over everything else, returns false once the loading
+    // finished and the splash closed.
+    pub fn eval<R: Renderer>(&mut self, ctx: &mut Context<R>) -> bool {
+        self.receive();
+        if self.finished {
+            return false;
+        }
+
+        self.win.inner_mut().main.rect = ctx.safe_area();
+        let progress = self.progress();
+        let opt = ContainerOption::NO_TITLE | ContainerOption::NO_RESIZE | ContainerOption::NO_SCROLL | ContainerOption::NO_CLOSE;
+        ctx.dialog(&mut self.win.clone(), opt, |cont| {
+            let body = cont.body;
+            let font = cont.style.font;
+            let text_size = cont.atlas.get_text_size(font, &self.status);
+            let image_size = self.image.map(|s| cont.atlas.get_slot_size(s)).unwrap_or_default();
+            let bar_w = (body.width * 3 / 5).min(400);
+            let spacing = cont.style.spacing * 2;
+
+            // image, status and bar stacked around the middle of the screen
+            let height = image_size.height + spacing + text_size.height + spacing + 6;
+            let mut y = body.y + (body.height - height) / 2;
+            if let Some(slot) = self.image {
+                cont.draw_slot(slot, rect(body.x + (body.width - image_size.width) / 2, y, image_size.width, image_size.height), color(255, 255, 255, 255));
+            }
+            y += image_size.height + spacing;
+            let text_color = cont.style.color(ControlColor::Text);
+            cont.draw_text(font, &self.status, vec2(body.x + (body.width - text_size.width) / 2, y), text_color);
+            y += text_size.height + spacing;
+
+            let bar = rect(body.x + (body.width - bar_w) / 2, y, bar_w, 6);
+            cont.draw_rect(bar, cont.style.color(ControlColor::Base));
+            cont.draw_rect(rect(bar.x, bar.y, (bar.width as Real * progress) as i32, bar.height), cont.style.color(ControlColor::Accent));
+            WindowState::Open
+        });
+        true
+    }
+}
@@ -0,0 +1,205 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Coach marks: walks the user through a list of widgets, dimming the rest of the screen and showing an
+// instruction bubble next to the highlighted widget. Evaluate it after the windows it points into.
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SpotlightResult {
+    None,
+    Finished,
+    Skipped,
+}
+
+#[derive(Clone)]
+pub struct SpotlightStep {
+    pub target: Id,
+    pub text: String,
+}
+
+pub struct SpotlightState {
+    win: WindowHandle,
+    steps: Vec<SpotlightStep>,
+    current: usize,
+    frames: usize, // frames since the current step started, drives the ring animation
+    result: SpotlightResult,
+    pub dim_color: Color,
+    pub ring_color: Option<Color>, // the style's accent color when None
+    pub bubble_size: Dimensioni,
+    pub extras: Extras,
+}
+
+impl SpotlightState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str) -> Self {
+        Self {
+            win: ctx.new_dialog(name, rect(0, 0, 1, 1)),
+            steps: Vec::new(),
+            current: 0,
+            frames: 0,
+            result: SpotlightResult::None,
+            dim_color: color(0, 0, 0, 160),
+            ring_color: None,
+            bubble_size: Dimensioni::new(260, 110),
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn push_step(&mut self, target: Id, text: &str) {
+        self.steps.push(SpotlightStep { target, text: text.to_string() });
+    }
+
+    pub fn clear_steps(&mut self) {
+        self.steps.clear();
+    }
+
+    pub fn current_step(&self) -> usize {
+        self.current
+    }
+
+    pub fn result(&self) -> SpotlightResult {
+        self.result
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.win.is_open()
+    }
+
+    pub fn start<R: Renderer>(&mut self, ctx: &mut Context<R>) {
+        if self.steps.is_empty() {
+            return;
+        }
+        self.current = 0;
+        self.frames = 0;
+        self.result = SpotlightResult::None;
+        ctx.open_dialog(&mut self.win);
+    }
+
+    pub fn skip(&mut self) {
+        self.result = SpotlightResult::Skipped;
+        self.win.inner_mut().win_state = WindowState::Closed;
+    }
+
+    fn bubble_rect(target: Option<Recti>, size: Dimensioni, screen: Dimensioni) -> Recti {
+        let (w, h) = (size.width, size.height);
+        let (x, y) = match target {
+            Some(t) => {
+                let x = t.x + t.width / 2 - w / 2;
+                // below the widget if it fits, above otherwise
+                let y = if t.y + t.height + 12 + h <= screen.height { t.y + t.height + 12 } else { t.y - 12 - h };
+                (x, y)
+            }
+            None => ((screen.width - w) / 2, (screen.height - h) / 2),
+        };
+        rect(x.clamp(0, max(screen.width - w, 0)), y.clamp(0, max(screen.height - h, 0)), w, h)
+    }
+
+    pub fn eval<R: Renderer>(&mut self, ctx: &mut Context<R>) {
+        if !self.win.is_open() || self.current >= self.steps.len() {
+            return;
+        }
+
+        let screen = ctx.canvas().current_dimension();
+        let target = ctx.widget_rect(self.steps[self.current].target);
+        self.win.inner_mut().main.rect = rect(0, 0, screen.width, screen.height);
+        self.frames += 1;
+
+        let step_count = self.steps.len();
+        let text = self.steps[self.current].text.clone();
+        let mut next = false;
+        let mut skip = false;
+        let dim = self.dim_color;
+        let ring_color = self.ring_color;
+        let frames = self.frames;
+        let bubble = Self::bubble_rect(target, self.bubble_size, screen);
+        let index = self.current;
+        let last = index + 1 == step_count;
+        let opt = ContainerOption::NO_TITLE | ContainerOption::NO_RESIZE | ContainerOption::NO_SCROLL | ContainerOption::NO_FRAME;
+        ctx.dialog(&mut self.win, opt, |c| {
+            let body = c.body;
+            let ring = ring_color.unwrap_or(c.style.color(ControlColor::Accent));
+            match target {
+                Some(t) => {
+                    // dim around the widget
+                    c.draw_rect(rect(body.x, body.y, body.width, t.y - body.y), dim);
+                    c.draw_rect(rect(body.x, t.y + t.height, body.width, body.y + body.height - t.y - t.height), dim);
+                    c.draw_rect(rect(body.x, t.y, t.x - body.x, t.height), dim);
+                    c.draw_rect(rect(t.x + t.width, t.y, body.x + body.width - t.x - t.width, t.height), dim);
+
+                    // pulsing ring
+                    let phase = (frames % 60) as Real / 60.0;
+                    let grow = 2 + (phase * 6.0) as i32;
+                    let faded = color(ring.r, ring.g, ring.b, (ring.a as Real * (1.0 - phase)) as u8);
+                    c.draw_box(expand_rect(t, 2), ring);
+                    c.draw_box(expand_rect(t, 3), ring);
+                    c.draw_box(expand_rect(t, 2 + grow), faded);
+                }
+                None => c.draw_rect(body, dim),
+            }
+
+            // instruction bubble
+            c.draw_frame(bubble, ControlColor::WindowBG);
+            c.draw_box(bubble, ring);
+            c.layout.begin_region(expand_rect(bubble, -c.style.padding));
+            c.set_row_widths_height(&[-1], 0);
+            c.label(&format!("{} / {}", index + 1, step_count));
+            c.text(&text);
+            c.layout.end_region();
+            let button_h = c.style.title_height;
+            let button_w = (bubble.width - c.style.padding * 3) / 2;
+            let by = bubble.y + bubble.height - button_h - c.style.padding;
+            c.layout.begin_region(rect(bubble.x + c.style.padding, by, bubble.width - c.style.padding * 2, button_h));
+            c.set_row_widths_height(&[button_w, -1], button_h);
+            if c.button_ex("Skip", None, WidgetOption::ALIGN_CENTER).is_submitted() {
+                skip = true;
+            }
+            if c.button_ex(if last { "Finish" } else { "Next" }, None, WidgetOption::ALIGN_CENTER).is_submitted() {
+                next = true;
+            }
+            c.layout.end_region();
+
+            if skip || (next && last) {
+                WindowState::Closed
+            } else {
+                WindowState::Open
+            }
+        });
+
+        if skip {
+            self.result = SpotlightResult::Skipped;
+        } else if next {
+            if last {
+                self.result = SpotlightResult::Finished;
+            } else {
+                self.current += 1;
+                self.frames = 0;
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// the value of a single style field
+#[derive(Copy, Clone, PartialEq)]
+pub enum StyleValue {
+    Int(i32),
+    Real(Real),
+    Bool(bool),
+    Color(Color),
+    Font(FontId),
+    Effect(TextEffect),
+}
+
+// A field that differs between two styles, named like in the text format of the style editor. Keeping the
+// old value lets an editor revert the change.
+#[derive(Copy, Clone, PartialEq)]
+pub struct StyleChange {
+    pub name: &'static str,
+    pub old: StyleValue,
+    pub new: StyleValue,
+}
+
+impl StyleChange {
+    pub fn reversed(&self) -> Self {
+        Self { name: self.name, old: self.new, new: self.old }
+    }
+}
+
+impl PartialEq for TextEffect {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) => true,
+            (Self::Shadow { offset: a, color: ca }, Self::Shadow { offset: b, color: cb }) => a.x == b.x && a.y == b.y && ca == cb,
+            (Self::Outline(a), Self::Outline(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+fn style_fields(style: &Style) -> Vec<(&'static str, StyleValue)> {
+    use StyleValue::*;
+    let mut fields = vec![
+        ("font", Font(style.font)),
+        ("cell_width", Int(style.default_cell_size.width)),
+        ("cell_height", Int(style.default_cell_size.height)),
+        ("padding", Int(style.padding)),
+        ("spacing", Int(style.spacing)),
+        ("indent", Int(style.indent)),
+        ("title_height", Int(style.title_height)),
+        ("scrollbar_size", Int(style.scrollbar_size)),
+        ("thumb_size", Int(style.thumb_size)),
+        ("line_spacing", Int(style.line_spacing)),
+        ("text_effect", Effect(style.text_effect)),
+        ("decimal_digits", Int(style.decimal_digits as i32)),
+        ("resize_grip_size", Int(style.resize_grip_size)),
+        ("resize_border", Int(style.resize_border)),
+        ("resize_outset", Int(style.resize_outset)),
+        ("show_resize_grip", Bool(style.show_resize_grip)),
+        ("key_repeat_delay", Real(style.key_repeat_delay)),
+        ("key_repeat_rate", Real(style.key_repeat_rate)),
+        ("double_click_time", Real(style.double_click_time)),
+        ("anim_time", Real(style.anim_time)),
+        ("item_radius", Int(style.item_radius)),
+        ("zebra_rows", Bool(style.zebra_rows)),
+        ("row_lines", Bool(style.row_lines)),
+        ("column_lines", Bool(style.column_lines)),
+    ];
+    fields.extend(COLOR_NAMES.iter().zip(style.colors.iter()).map(|(n, c)| (*n, Color(*c))));
+    fields
+}
+
+// false when the name is unknown or the value has the wrong kind
+fn set_style_field(style: &mut Style, name: &str, value: StyleValue) -> bool {
+    use StyleValue::*;
+    match (name, value) {
+        ("font", Font(f)) => style.font = f,
+        ("cell_width", Int(v)) => style.default_cell_size.width = v,
+        ("cell_height", Int(v)) => style.default_cell_size.height = v,
+        ("padding", Int(v)) => style.padding = v,
+        ("spacing", Int(v)) => style.spacing = v,
+        ("indent", Int(v)) => style.indent = v,
+        ("title_height", Int(v)) => style.title_height = v,
+        ("scrollbar_size", Int(v)) => style.scrollbar_size = v,
+        ("thumb_size", Int(v)) => style.thumb_size = v,
+        ("line_spacing", Int(v)) => style.line_spacing = v,
+        ("text_effect", Effect(e)) => style.text_effect = e,
+        ("decimal_digits", Int(v)) => style.decimal_digits = v.max(0) as usize,
+        ("resize_grip_size", Int(v)) => style.resize_grip_size = v,
+        ("resize_border", Int(v)) => style.resize_border = v,
+        ("resize_outset", Int(v)) => style.resize_outset = v,
+        ("show_resize_grip", Bool(v)) => style.show_resize_grip = v,
+        ("key_repeat_delay", Real(v)) => style.key_repeat_delay = v,
+        ("key_repeat_rate", Real(v)) => style.key_repeat_rate = v,
+        ("double_click_time", Real(v)) => style.double_click_time = v,
+        ("anim_time", Real(v)) => style.anim_time = v,
+        ("item_radius", Int(v)) => style.item_radius = v,
+        ("zebra_rows", Bool(v)) => style.zebra_rows = v,
+        ("row_lines", Bool(v)) => style.row_lines = v,
+        ("column_lines", Bool(v)) => style.column_lines = v,
+        (name, Color(c)) => match COLOR_NAMES.iter().position(|n| *n == name) {
+            Some(i) => style.colors[i] = c,
+            None => return false,
+        },
+        _ => return false,
+    }
+    true
+}
+
+impl Style {
+    // the fields of `other` that differ from this style, in declaration order
+    pub fn diff(&self, other: &Style) -> Vec<StyleChange> {
+        style_fields(self)
+            .into_iter()
+            .zip(style_fields(other))
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((name, old), (_, new))| StyleChange { name, old, new })
+            .collect()
+    }
+
+    // Sets the new value of every change, `a.apply(&a.diff(&b))` turns `a` into `b`. Returns the names of
+    // the changes that couldn't be applied.
+    pub fn apply(&mut self, changes: &[StyleChange]) -> Vec<&'static str> {
+        changes.iter().filter(|c| !set_style_field(self, c.name, c.new)).map(|c| c.name).collect()
+    }
+
+    // undoes `apply`, the changes are reverted in reverse order
+    pub fn revert(&mut self, changes: &[StyleChange]) -> Vec<&'static str> {
+        changes.iter().rev().filter(|c| !set_style_field(self, c.name, c.old)).map(|c| c.name).collect()
+    }
+}
@@ -0,0 +1,323 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+pub(crate) const COLOR_NAMES: [&str; ControlColor::Max as usize] = [
+    "text",
+    "border",
+    "window_bg",
+    "title_bg",
+    "title_text",
+    "panel_bg",
+    "button",
+    "button_hover",
+    "button_focus",
+    "base",
+    "base_hover",
+    "base_focus",
+    "scroll_base",
+    "scroll_thumb",
+    "accent",
+    "success",
+    "warning",
+    "error",
+    "disabled",
+    "selection_bg",
+    "row_alt",
+    "grid_line",
+];
+
+// name, low, high, getter, setter
+type Metric = (&'static str, i32, i32, fn(&Style) -> i32, fn(&mut Style, i32));
+
+const METRICS: [Metric; 13] = [
+    ("padding", 0, 16, |s| s.padding, |s, v| s.padding = v),
+    ("spacing", 0, 16, |s| s.spacing, |s, v| s.spacing = v),
+    ("indent", 0, 64, |s| s.indent, |s, v| s.indent = v),
+    ("title_height", 8, 64, |s| s.title_height, |s, v| s.title_height = v),
+    ("scrollbar_size", 4, 32, |s| s.scrollbar_size, |s, v| s.scrollbar_size = v),
+    ("thumb_size", 4, 32, |s| s.thumb_size, |s, v| s.thumb_size = v),
+    ("line_spacing", -8, 16, |s| s.line_spacing, |s, v| s.line_spacing = v),
+    ("decimal_digits", 0, 6, |s| s.decimal_digits as i32, |s, v| s.decimal_digits = v.max(0) as usize),
+    ("resize_grip_size", 4, 48, |s| s.resize_grip_size, |s, v| s.resize_grip_size = v),
+    ("resize_border", 0, 16, |s| s.resize_border, |s, v| s.resize_border = v),
+    ("resize_outset", 0, 16, |s| s.resize_outset, |s, v| s.resize_outset = v),
+    ("item_radius", 0, 16, |s| s.item_radius, |s, v| s.item_radius = v),
+    ("cell_height", 4, 48, |s| s.default_cell_size.height, |s, v| s.default_cell_size.height = v),
+];
+
+// name, low, high
+const TIMINGS: [(&str, Real, Real); 4] = [("key_repeat_delay", 0.1, 1.0), ("key_repeat_rate", 1.0, 60.0), ("double_click_time", 0.1, 1.0), ("anim_time", 0.0, 0.5)];
+
+fn timing(style: &mut Style, i: usize) -> &mut Real {
+    match i {
+        0 => &mut style.key_repeat_delay,
+        1 => &mut style.key_repeat_rate,
+        2 => &mut style.double_click_time,
+        _ => &mut style.anim_time,
+    }
+}
+
+// `name=value` pairs separated by `;`, colors as #rrggbbaa. Fonts and text effects aren't included.
+pub fn style_to_string(style: &Style) -> String {
+    let mut s = *style;
+    let mut out = String::new();
+    for (i, name) in COLOR_NAMES.iter().enumerate() {
+        let c = s.colors[i];
+        out.push_str(&format!("{}=#{:02x}{:02x}{:02x}{:02x};", name, c.r, c.g, c.b, c.a));
+    }
+    for (name, _, _, get, _) in METRICS.iter() {
+        out.push_str(&format!("{}={};", name, get(&s)));
+    }
+    for (i, (name, _, _)) in TIMINGS.iter().enumerate() {
+        out.push_str(&format!("{}={};", name, timing(&mut s, i)));
+    }
+    out
+}
+
+// Applies the pairs found in `text` over `base`, see `style_to_string`
+pub fn style_from_str(text: &str, base: &Style) -> Result<Style, String> {
+    let mut style = *base;
+    for pair in text.split([';', '\n']).map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, value) = pair.split_once('=').ok_or_else(|| format!("expected name=value: {}", pair))?;
+        let (name, value) = (name.trim(), value.trim());
+        let bad = || format!("bad value for {}: {}", name, value);
+        if let Some(i) = COLOR_NAMES.iter().position(|n| *n == name) {
+            let hex = value.strip_prefix('#').filter(|h| h.len() == 8).ok_or_else(bad)?;
+            let v = u32::from_str_radix(hex, 16).map_err(|_| bad())?;
+            style.colors[i] = color((v >> 24) as u8, (v >> 16) as u8, (v >> 8) as u8, v as u8);
+        } else if let Some((_, _, _, _, set)) = METRICS.iter().find(|(n, ..)| *n == name) {
+            set(&mut style, value.parse().map_err(|_| bad())?);
+        } else if let Some(i) = TIMINGS.iter().position(|(n, _, _)| *n == name) {
+            *timing(&mut style, i) = value.parse().map_err(|_| bad())?;
+        } else {
+            return Err(format!("unknown style entry: {}", name));
+        }
+    }
+    Ok(style)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum StyleEditorPage {
+    Colors,
+    Metrics,
+    Text,
+}
+
+// A window editing a style: color swatches with an RGBA picker, sliders for the metrics and the timings,
+// import/export as text, undo and redo
+pub struct StyleEditorState {
+    win: WindowHandle,
+    pub style: Style,
+    page: StyleEditorPage,
+    selected: usize,
+    undo: Vec<Style>,
+    redo: Vec<Style>,
+    editing: bool,
+    io_text: String,
+    io_error: Option<String>,
+    pub extras: Extras,
+}
+
+impl StyleEditorState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str, r: Recti) -> Self {
+        Self {
+            win: ctx.new_window(name, r),
+            style: ctx.style,
+            page: StyleEditorPage::Colors,
+            selected: 0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            editing: false,
+            io_text: String::new(),
+            io_error: None,
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn window(&self) -> WindowHandle {
+        self.win.clone()
+    }
+
+    pub fn page(&self) -> StyleEditorPage {
+        self.page
+    }
+
+    pub fn set_page(&mut self, page: StyleEditorPage) {
+        self.page = page;
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(style) => {
+                self.redo.push(std::mem::replace(&mut self.style, style));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(style) => {
+                self.undo.push(std::mem::replace(&mut self.style, style));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn int_slider(cont: &mut Container, i: usize, value: &mut i32, low: i32, high: i32) -> bool {
+        let mut v = *value as Real;
+        cont.idmngr.push_id_u32(i as u32);
+        let res = cont.slider_ex(&mut v, low as Real, high as Real, 1.0, 0, WidgetOption::ALIGN_CENTER);
+        cont.idmngr.pop_id();
+        *value = v.round() as i32;
+        res.is_changed()
+    }
+
+    fn colors_page(&mut self, cont: &mut Container) -> bool {
+        let mut changed = false;
+        cont.set_row_widths_height(&[-30, -1], 0);
+        for (i, name) in COLOR_NAMES.iter().enumerate() {
+            let label = if i == self.selected { format!("> {}", name) } else { name.to_string() };
+            if cont.button_ex(&label, None, WidgetOption::NONE).is_submitted() {
+                self.selected = i;
+            }
+            let swatch = cont.next_cell();
+            cont.draw_rect(swatch, self.style.colors[i]);
+            cont.draw_box(swatch, self.style.colors[ControlColor::Border as usize]);
+        }
+
+        let c = &mut self.style.colors[self.selected];
+        cont.set_row_widths_height(&[-1], 24);
+        let preview = cont.next_cell();
+        cont.draw_rect(preview, *c);
+        cont.set_row_widths_height(&[20, -1], 0);
+        for (i, (name, channel)) in [("R", &mut c.r), ("G", &mut c.g), ("B", &mut c.b), ("A", &mut c.a)].into_iter().enumerate() {
+            cont.label(name);
+            let mut v = *channel as i32;
+            changed |= Self::int_slider(cont, 100 + i, &mut v, 0, 255);
+            *channel = v as u8;
+        }
+        changed
+    }
+
+    fn metrics_page(&mut self, cont: &mut Container) -> bool {
+        let mut changed = false;
+        cont.set_row_widths_height(&[110, -1], 0);
+        for (i, (name, low, high, get, set)) in METRICS.iter().enumerate() {
+            cont.label(name);
+            let mut v = get(&self.style);
+            changed |= Self::int_slider(cont, i, &mut v, *low, *high);
+            set(&mut self.style, v);
+        }
+        for (i, (name, low, high)) in TIMINGS.iter().enumerate() {
+            cont.label(name);
+            cont.idmngr.push_id_u32(200 + i as u32);
+            changed |= cont.slider_ex(timing(&mut self.style, i), *low, *high, 0.0, 2, WidgetOption::ALIGN_CENTER).is_changed();
+            cont.idmngr.pop_id();
+        }
+        changed
+    }
+
+    // an import is pushed on the undo stack right away
+    fn text_page(&mut self, cont: &mut Container) -> bool {
+        let mut imported = false;
+        cont.set_row_widths_height(&[-1], 0);
+        cont.textbox_ex(&mut self.io_text, WidgetOption::NONE);
+        cont.set_row_widths_height(&[80, 80, -1], 0);
+        if cont.button_ex("Export", None, WidgetOption::NONE).is_submitted() {
+            self.io_text = style_to_string(&self.style);
+            cont.copy_to_clipboard(&self.io_text);
+            self.io_error = None;
+        }
+        if cont.button_ex("Import", None, WidgetOption::NONE).is_submitted() {
+            match style_from_str(&self.io_text, &self.style) {
+                Ok(style) => {
+                    self.undo.push(std::mem::replace(&mut self.style, style));
+                    self.redo.clear();
+                    self.io_error = None;
+                    imported = true;
+                }
+                Err(e) => self.io_error = Some(e),
+            }
+        }
+        cont.label("");
+        if let Some(e) = &self.io_error {
+            cont.set_row_widths_height(&[-1], 0);
+            cont.text(e);
+        }
+        imported
+    }
+
+    // Shows the editor, returns true when the style changed. The style is also set on the context.
+    pub fn eval<R: Renderer>(&mut self, ctx: &mut Context<R>) -> bool {
+        let before = self.style;
+        let (mut edited, mut history) = (false, false);
+        let (mut undo, mut redo) = (false, false);
+        ctx.window(&mut self.win.clone(), ContainerOption::NONE, |cont| {
+            cont.set_row_widths_height(&[70, 70, 90, -1], 0);
+            for (page, name) in [(StyleEditorPage::Colors, "Colors"), (StyleEditorPage::Metrics, "Metrics"), (StyleEditorPage::Text, "Import/Export")] {
+                let name = if self.page == page { format!("[{}]", name) } else { name.to_string() };
+                if cont.button_ex(&name, None, WidgetOption::NONE).is_submitted() {
+                    self.page = page;
+                }
+            }
+            cont.label("");
+            cont.set_row_widths_height(&[70, 70, -1], 0);
+            cont.disabled(self.undo.is_empty(), |c| undo = c.button_ex("Undo", None, WidgetOption::NONE).is_submitted());
+            cont.disabled(self.redo.is_empty(), |c| redo = c.button_ex("Redo", None, WidgetOption::NONE).is_submitted());
+            cont.label("");
+            match self.page {
+                StyleEditorPage::Colors => edited = self.colors_page(cont),
+                StyleEditorPage::Metrics => edited = self.metrics_page(cont),
+                StyleEditorPage::Text => history = self.text_page(cont),
+            }
+            WindowState::Open
+        });
+        history |= (undo && self.undo()) || (redo && self.redo());
+
+        // a drag over several frames is a single undo step
+        if edited && !history && !self.editing {
+            self.undo.push(before);
+            self.redo.clear();
+            self.editing = true;
+        }
+        if ctx.input.borrow().mouse_down.is_none() {
+            self.editing = false;
+        }
+        let changed = edited || history;
+        if changed {
+            ctx.set_style(&self.style);
+        }
+        changed
+    }
+}
@@ -0,0 +1,147 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Named scale profiles (compact, normal, large...) applied on top of a base style. Metrics are scaled
+// together and each profile can pick its own font, since fonts are baked in the atlas at a fixed size.
+use super::*;
+
+#[derive(Clone)]
+pub struct StyleProfile {
+    pub name: String,
+    pub scale: Real,
+    pub font: Option<FontId>,
+}
+
+impl StyleProfile {
+    pub fn new(name: &str, scale: Real, font: Option<FontId>) -> Self {
+        Self { name: name.to_string(), scale, font }
+    }
+
+    pub fn apply(&self, base: &Style) -> Style {
+        let s = |v: i32| max((v as Real * self.scale).round() as i32, 1);
+        let mut style = *base;
+        style.default_cell_size = Dimensioni::new(s(base.default_cell_size.width), s(base.default_cell_size.height));
+        style.padding = s(base.padding);
+        style.spacing = s(base.spacing);
+        style.indent = s(base.indent);
+        style.title_height = s(base.title_height);
+        style.scrollbar_size = s(base.scrollbar_size);
+        style.thumb_size = s(base.thumb_size);
+        style.resize_grip_size = s(base.resize_grip_size);
+        style.line_spacing = (base.line_spacing as Real * self.scale).round() as i32;
+        style.resize_border = (base.resize_border as Real * self.scale).round() as i32;
+        style.resize_outset = (base.resize_outset as Real * self.scale).round() as i32;
+        style.item_radius = (base.item_radius as Real * self.scale).round() as i32;
+        if let Some(font) = self.font {
+            style.font = font;
+        }
+        style
+    }
+}
+
+pub struct StyleProfiles {
+    base: Style,
+    profiles: Vec<StyleProfile>,
+    current: usize,
+}
+
+impl StyleProfiles {
+    // starts with the compact, normal and large profiles, all using the base font, normal selected
+    pub fn new(base: Style) -> Self {
+        Self {
+            base,
+            profiles: vec![
+                StyleProfile::new("compact", 0.75, None),
+                StyleProfile::new("normal", 1.0, None),
+                StyleProfile::new("large", 1.5, None),
+            ],
+            current: 1,
+        }
+    }
+
+    pub fn base(&self) -> &Style {
+        &self.base
+    }
+
+    pub fn set_base(&mut self, base: Style) {
+        self.base = base;
+    }
+
+    // replaces the profile with the same name if any
+    pub fn add(&mut self, profile: StyleProfile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(p) => *p = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut StyleProfile> {
+        self.profiles.iter_mut().find(|p| p.name == name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    pub fn select(&mut self, name: &str) -> bool {
+        match self.profiles.iter().position(|p| p.name == name) {
+            Some(i) => {
+                self.current = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // picks the profile with the scale closest to the monitor's content scale (1.0 at 96 dpi)
+    pub fn select_for_scale(&mut self, content_scale: Real) -> &StyleProfile {
+        let mut best = self.current;
+        for (i, p) in self.profiles.iter().enumerate() {
+            if (p.scale - content_scale).abs() < (self.profiles[best].scale - content_scale).abs() {
+                best = i;
+            }
+        }
+        self.current = best;
+        &self.profiles[best]
+    }
+
+    pub fn current(&self) -> &StyleProfile {
+        &self.profiles[self.current]
+    }
+
+    pub fn style(&self) -> Style {
+        self.current().apply(&self.base)
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    pub fn apply_style_profile(&mut self, profiles: &StyleProfiles) {
+        self.set_style(&profiles.style())
+    }
+}
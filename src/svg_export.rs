@@ -0,0 +1,216 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::Write;
+
+fn svg_fill(c: &Color) -> String {
+    format!("fill=\"rgb({},{},{})\" fill-opacity=\"{:.3}\"", c.r, c.g, c.b, c.a as f32 / 255.0)
+}
+
+fn svg_escape(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+#[cfg(feature = "builder")]
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut res = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                res.push(TABLE[((n >> (18 - i * 6)) & 0x3F) as usize] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+    res
+}
+
+// the whole atlas is embedded once, icons and slots reference the part they use through a viewBox
+fn atlas_defs(atlas: &AtlasHandle) -> Option<String> {
+    #[cfg(feature = "builder")]
+    {
+        let bytes = builder::Builder::png_image_bytes(atlas.clone()).ok()?;
+        Some(format!(
+            "<defs><image id=\"atlas\" width=\"{}\" height=\"{}\" href=\"data:image/png;base64,{}\"/></defs>\n",
+            atlas.width(),
+            atlas.height(),
+            base64(bytes.as_slice())
+        ))
+    }
+    #[cfg(not(feature = "builder"))]
+    {
+        let _ = atlas;
+        None
+    }
+}
+
+fn svg_image(out: &mut String, has_atlas: bool, src: Recti, r: Recti, color: &Color) {
+    let x = r.x + (r.width - src.width) / 2;
+    let y = r.y + (r.height - src.height) / 2;
+    svg_region(out, has_atlas, src, rect(x, y, src.width, src.height), color);
+}
+
+// the atlas region `src` stretched over `dst`
+fn svg_region(out: &mut String, has_atlas: bool, src: Recti, dst: Recti, color: &Color) {
+    if has_atlas {
+        let _ = writeln!(
+            out,
+            "<svg x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\" preserveAspectRatio=\"none\" opacity=\"{:.3}\"><use href=\"#atlas\"/></svg>",
+            dst.x,
+            dst.y,
+            dst.width,
+            dst.height,
+            src.x,
+            src.y,
+            src.width,
+            src.height,
+            color.a as f32 / 255.0
+        );
+    } else {
+        let _ = writeln!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>", dst.x, dst.y, dst.width, dst.height, svg_fill(color));
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Converts the commands of the last frame to an SVG document, independently of the renderer
+    pub fn frame_to_svg(&self) -> String {
+        let dim = self.canvas.current_dimension();
+        let atlas = self.canvas.get_atlas();
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+            dim.width, dim.height, dim.width, dim.height
+        );
+        let defs = atlas_defs(&atlas);
+        let has_atlas = defs.is_some();
+        if let Some(defs) = defs {
+            out.push_str(defs.as_str());
+        }
+
+        let mut clip_count = 0;
+        let mut groups = 0;
+        let mut clip: Option<Recti> = None;
+        let mut mask: Option<ClipMask> = None;
+        // the clip and the mask are nested groups, both are reopened whenever one of them changes
+        let mut reopen = |out: &mut String, groups: &mut usize, clip: Option<Recti>, mask: Option<ClipMask>| {
+            for _ in 0..*groups {
+                out.push_str("</g>\n");
+            }
+            *groups = 0;
+            let shapes = clip.map(|r| (r, 0)).into_iter().chain(mask.map(|m| (m.rect, m.radius)));
+            for (r, radius) in shapes {
+                let _ = writeln!(
+                    out,
+                    "<clipPath id=\"clip{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/></clipPath><g clip-path=\"url(#clip{})\">",
+                    clip_count, r.x, r.y, r.width, r.height, radius, clip_count
+                );
+                clip_count += 1;
+                *groups += 1;
+            }
+        };
+        self.visit_frame_commands(|container, command| match command {
+            Command::Clip { rect } => {
+                clip = if rect.width != UNCLIPPED_RECT.width || rect.height != UNCLIPPED_RECT.height { Some(*rect) } else { None };
+                reopen(&mut out, &mut groups, clip, mask);
+            }
+            Command::Mask { mask: m } => {
+                mask = *m;
+                reopen(&mut out, &mut groups, clip, mask);
+            }
+            Command::Recti { rect, color } => {
+                let _ = writeln!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>", rect.x, rect.y, rect.width, rect.height, svg_fill(color));
+            }
+            Command::Text { font, pos, color, text } => {
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" dominant-baseline=\"hanging\" xml:space=\"preserve\" {}>{}</text>",
+                    pos.x,
+                    pos.y,
+                    atlas.get_font_height(*font),
+                    svg_fill(color),
+                    svg_escape(container.command_text(*text))
+                );
+            }
+            Command::Icon { rect, id, color } => svg_image(&mut out, has_atlas, atlas.get_icon_rect(*id), *rect, color),
+            Command::Slot { rect, id, color } | Command::SlotRedraw { rect, id, color, .. } => svg_image(&mut out, has_atlas, atlas.get_slot_rect(*id), *rect, color),
+            Command::Atlas { rect, src, color } => svg_region(&mut out, has_atlas, *src, *rect, color),
+            Command::Triangle { points, color } => {
+                let _ = writeln!(
+                    out,
+                    "<polygon points=\"{},{} {},{} {},{}\" {}/>",
+                    points[0].x,
+                    points[0].y,
+                    points[1].x,
+                    points[1].y,
+                    points[2].x,
+                    points[2].y,
+                    svg_fill(color)
+                );
+            }
+            Command::CustomRender(cra, _) => {
+                // the content is rendered by the application, only its area is known
+                let r = cra.content_area;
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"gray\" stroke-dasharray=\"4\"/>",
+                    r.x, r.y, r.width, r.height
+                );
+            }
+            Command::Sampler { .. } | Command::None => (),
+        });
+        for _ in 0..groups {
+            out.push_str("</g>\n");
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    pub fn export_frame_svg(&self, path: &str) -> std::io::Result<()> {
+        let mut f = File::create(path)?;
+        f.write_all(self.frame_to_svg().as_bytes())
+    }
+}
@@ -0,0 +1,98 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// What the telemetry hook receives. Windows are identified by their name, widgets by their label (empty for
+// icon only ones), the path of the window or panel they belong to and their id.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TelemetryEvent {
+    WindowOpened { name: String },
+    WindowClosed { name: String },
+    WidgetClicked { window: String, path: UiPath, label: String, id: Id },
+    DialogConfirmed { name: String },
+    DialogCancelled { name: String },
+}
+
+pub type TelemetryHook = Box<dyn FnMut(&TelemetryEvent)>;
+
+impl Container {
+    // the click event, also kept aside so the telemetry gets it even when the events were taken during the frame
+    pub(crate) fn emit_click(&mut self, id: Id, label: &str) {
+        self.emit_event(id, WidgetEventKind::Clicked, EventValue::None);
+        self.clicks.push((id, label.to_string()));
+    }
+
+    // the clicks of this container, then of its panels
+    fn collect_clicks(&self, out: &mut Vec<(UiPath, String, Id)>) {
+        out.extend(self.clicks.iter().map(|(id, label)| (self.path.clone(), label.clone(), *id)));
+        for p in &self.panels {
+            p.inner().collect_clicks(out);
+        }
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Installs a callback receiving the window and click events at the end of every frame, `None` removes it.
+    // Windows whose name starts with '!' are internal and not reported.
+    pub fn set_telemetry_hook(&mut self, hook: Option<TelemetryHook>) {
+        self.telemetry = hook;
+        self.telemetry_windows.clear();
+    }
+
+    // reports an event through the hook, the built-in dialogs use it for their confirmation
+    pub fn report_telemetry(&mut self, event: TelemetryEvent) {
+        if let Some(hook) = &mut self.telemetry {
+            hook(&event);
+        }
+    }
+
+    pub(crate) fn dispatch_telemetry(&mut self) {
+        let Some(hook) = &mut self.telemetry else {
+            return;
+        };
+
+        let windows: Vec<String> = self.root_list.iter().map(|w| w.name()).filter(|n| !n.starts_with('!')).collect();
+        for name in windows.iter().filter(|n| !self.telemetry_windows.contains(n)) {
+            hook(&TelemetryEvent::WindowOpened { name: name.clone() });
+        }
+        for name in self.telemetry_windows.iter().filter(|n| !windows.contains(n)) {
+            hook(&TelemetryEvent::WindowClosed { name: name.clone() });
+        }
+        self.telemetry_windows = windows;
+
+        for w in self.root_list.iter().filter(|w| !w.name().starts_with('!')) {
+            let mut clicks = Vec::new();
+            w.inner().main.collect_clicks(&mut clicks);
+            for (path, label, id) in clicks {
+                hook(&TelemetryEvent::WidgetClicked { window: w.name(), path, label, id });
+            }
+        }
+    }
+}
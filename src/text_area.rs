@@ -0,0 +1,493 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Multi-line text editor with mouse selection and its own scrolling. Dragging a selection past the edges scrolls
+// the text, and with `middle_click_autoscroll` a middle click toggles scrolling towards the mouse. Alt+drag
+// selects a block (column) instead, typing then edits every line of the block.
+use super::*;
+use std::ops::Range;
+
+// lines are inclusive, x is in pixels from the left of the text, an empty block is a caret on every line
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BlockSelection {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub x0: i32,
+    pub x1: i32,
+}
+
+pub struct TextArea {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>, // other end of the selection
+    block: Option<BlockSelection>,
+    block_anchor: Option<(usize, i32)>,
+    scroll: Vec2i,
+    autoscroll_origin: Option<Vec2i>,
+    read_only: bool,
+    lines: Vec<Range<usize>>, // cached on every change of the text
+    words: usize,
+    pub middle_click_autoscroll: bool,
+    pub extras: Extras,
+}
+
+impl TextArea {
+    pub fn new(text: &str) -> Self {
+        let mut area = Self {
+            text: text.to_string(),
+            cursor: 0,
+            anchor: None,
+            block: None,
+            block_anchor: None,
+            scroll: Vec2i::default(),
+            autoscroll_origin: None,
+            read_only: false,
+            lines: Vec::new(),
+            words: 0,
+            middle_click_autoscroll: true,
+            extras: Extras::default(),
+        };
+        area.update_metrics();
+        area
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.cursor = clamp_cursor(&self.text, self.cursor);
+        self.anchor = None;
+        self.block = None;
+        self.update_metrics();
+    }
+
+    pub fn block_selection(&self) -> Option<BlockSelection> {
+        self.block
+    }
+
+    // the corners can be given in any order, the lines are clamped to the text
+    pub fn set_block_selection(&mut self, block: Option<BlockSelection>) {
+        let last = self.lines.len() - 1;
+        self.block = block.map(|b| BlockSelection {
+            first_line: min(min(b.first_line, b.last_line), last),
+            last_line: min(max(b.first_line, b.last_line), last),
+            x0: min(b.x0, b.x1),
+            x1: max(b.x0, b.x1),
+        });
+        self.anchor = None;
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words
+    }
+
+    // zero based line and column (in chars) of the cursor
+    pub fn cursor_line_col(&self) -> (usize, usize) {
+        let line = Self::line_of(&self.lines, self.cursor);
+        (line, self.text[self.lines[line].start..self.cursor].chars().count())
+    }
+
+    // number of chars selected
+    pub fn selection_len(&self) -> usize {
+        self.selection().map(|r| self.text[r].chars().count()).unwrap_or(0)
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn set_cursor(&mut self, pos: usize) {
+        self.cursor = clamp_cursor(&self.text, pos);
+        self.anchor = None;
+        self.block = None;
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        match self.anchor {
+            Some(a) if a != self.cursor => Some(min(a, self.cursor)..max(a, self.cursor)),
+            _ => None,
+        }
+    }
+
+    pub fn select(&mut self, range: Range<usize>) {
+        self.block = None;
+        self.anchor = Some(clamp_cursor(&self.text, range.start));
+        self.cursor = clamp_cursor(&self.text, range.end);
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.selection().map(|r| &self.text[r]).unwrap_or("")
+    }
+
+    // text of a block selection, one line of the block per line
+    pub fn selected_block_text(&self, atlas: &AtlasHandle, font: FontId) -> String {
+        match &self.block {
+            Some(b) => self.block_ranges(atlas, font, b).into_iter().map(|r| &self.text[r]).collect::<Vec<_>>().join("\n"),
+            None => String::new(),
+        }
+    }
+
+    // returns whether the text changed and the text to put in the clipboard
+    pub fn apply_text_action(&mut self, action: TextAction) -> (bool, Option<String>) {
+        let selected = self.selection().map(|r| self.text[r].to_string());
+        match action {
+            TextAction::Copy => (false, selected),
+            TextAction::Cut if !self.read_only => {
+                let changed = self.delete_selection();
+                if changed {
+                    self.update_metrics();
+                }
+                (changed, selected)
+            }
+            TextAction::Paste(text) if !self.read_only => {
+                self.delete_selection();
+                self.block = None;
+                self.cursor = clamp_cursor(&self.text, self.cursor);
+                self.text.insert_str(self.cursor, &text);
+                self.cursor += text.len();
+                self.update_metrics();
+                (true, None)
+            }
+            TextAction::SelectAll => {
+                self.select(0..self.text.len());
+                (false, None)
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(r) => {
+                self.text.replace_range(r.clone(), "");
+                self.cursor = r.start;
+                self.anchor = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update_metrics(&mut self) {
+        self.lines.clear();
+        let mut start = 0;
+        for (i, c) in self.text.char_indices() {
+            if c == '\n' {
+                self.lines.push(start..i);
+                start = i + 1;
+            }
+        }
+        self.lines.push(start..self.text.len());
+        self.words = self.text.split_whitespace().count();
+    }
+
+    fn line_of(lines: &[Range<usize>], pos: usize) -> usize {
+        min(lines.partition_point(|l| l.end < pos), lines.len() - 1)
+    }
+
+    // byte ranges covered by the block on each of its lines
+    fn block_ranges(&self, atlas: &AtlasHandle, font: FontId, block: &BlockSelection) -> Vec<Range<usize>> {
+        self.lines[block.first_line..=block.last_line]
+            .iter()
+            .map(|l| self.offset_at_x(atlas, font, l, block.x0)..self.offset_at_x(atlas, font, l, block.x1))
+            .collect()
+    }
+
+    // applies the typed text, backspace and delete on every line of the block, bottom up so the offsets of
+    // the lines above stay valid
+    fn edit_block(&mut self, atlas: &AtlasHandle, font: FontId, block: BlockSelection, input: &Input) -> bool {
+        let keys = input.key_pressed;
+        let ranges = self.block_ranges(atlas, font, &block);
+        let mut changed = false;
+        let mut first_caret = 0;
+        let mut last_caret = None;
+        let mut shift: isize = 0; // bytes added by the edits above the last line
+        for (i, r) in ranges.iter().enumerate().rev() {
+            let line = &self.lines[block.first_line + i];
+            let mut r = r.clone();
+            if r.is_empty() && keys.is_backspace() && r.start > line.start {
+                r.start = prev_char_boundary(&self.text, r.start);
+            } else if r.is_empty() && keys.is_delete() && r.end < line.end {
+                r.end = next_char_boundary(&self.text, r.end);
+            }
+            if !r.is_empty() || !input.input_text.is_empty() {
+                self.text.replace_range(r.clone(), &input.input_text);
+                changed = true;
+                if last_caret.is_some() {
+                    shift += input.input_text.len() as isize - r.len() as isize;
+                }
+            }
+            first_caret = r.start + input.input_text.len();
+            if last_caret.is_none() {
+                last_caret = Some(first_caret);
+            }
+        }
+        if let Some(caret) = last_caret {
+            self.cursor = (caret as isize + shift) as usize;
+        }
+        let x = atlas.get_text_size(font, &self.text[self.lines[block.first_line].start..first_caret]).width;
+        self.block = Some(BlockSelection { x0: x, x1: x, ..block });
+        self.update_metrics();
+        changed
+    }
+
+    // byte offset in `line` closest to the horizontal position `x`
+    fn offset_at_x(&self, atlas: &AtlasHandle, font: FontId, line: &Range<usize>, x: i32) -> usize {
+        let text = &self.text[line.clone()];
+        let mut prev_w = 0;
+        for (i, c) in text.char_indices() {
+            let w = atlas.get_text_size(font, &text[..i + c.len_utf8()]).width;
+            if x < (prev_w + w) / 2 {
+                return line.start + i;
+            }
+            prev_w = w;
+        }
+        line.end
+    }
+}
+
+impl Container {
+    #[inline(never)]
+    pub fn textarea(&mut self, area: &mut TextArea) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let id = self.idmngr.get_id_from_ptr(area);
+        let r = self.layout.next();
+        self.update_control(id, r, WidgetOption::HOLD_FOCUS);
+        self.request_text_menu(id, r, !area.read_only);
+        if let Some(action) = self.take_text_action(id) {
+            let (changed, copied) = area.apply_text_action(action);
+            if let Some(text) = copied {
+                self.copy_to_clipboard(&text);
+            }
+            if changed {
+                res |= ResourceState::CHANGE;
+            }
+        }
+
+        let font = self.style.font;
+        let atlas = self.atlas.clone();
+        let line_h = max(atlas.get_font_height(font) as i32 + self.style.line_spacing, 1);
+        let view = expand_rect(r, -self.style.padding);
+        let input = self.input.borrow().clone();
+        let mouse = input.mouse_pos;
+        let focused = self.focus == Some(id);
+        let hovered = self.mouse_over(r, self.in_hover_root);
+        let text_w = |s: &str| atlas.get_text_size(font, s).width;
+
+        let mut lines = area.lines.clone();
+        let mut follow_cursor = false;
+        let hit = |area: &TextArea, lines: &[Range<usize>], p: Vec2i| {
+            let line = ((p.y - view.y + area.scroll.y).div_euclid(max(line_h, 1))).clamp(0, lines.len() as i32 - 1) as usize;
+            area.offset_at_x(&atlas, font, &lines[line], p.x - view.x + area.scroll.x)
+        };
+
+        if focused {
+            // middle click toggles autoscroll, any other click ends it
+            if area.middle_click_autoscroll && input.mouse_pressed.is_middle() && hovered {
+                area.autoscroll_origin = match area.autoscroll_origin {
+                    Some(_) => None,
+                    None => Some(mouse),
+                };
+            } else if !input.mouse_pressed.is_none() {
+                area.autoscroll_origin = None;
+            }
+
+            let text_point = |area: &TextArea, p: Vec2i| {
+                let line = ((p.y - view.y + area.scroll.y).div_euclid(max(line_h, 1))).clamp(0, lines.len() as i32 - 1) as usize;
+                (line, max(p.x - view.x + area.scroll.x, 0))
+            };
+            if input.mouse_pressed.is_left() && hovered && input.key_down.is_alt() {
+                let (line, x) = text_point(area, mouse);
+                area.block_anchor = Some((line, x));
+                area.block = Some(BlockSelection { first_line: line, last_line: line, x0: x, x1: x });
+                area.anchor = None;
+                area.cursor = hit(area, &lines, mouse);
+            } else if input.mouse_pressed.is_left() && hovered {
+                area.block = None;
+                area.block_anchor = None;
+                let pos = hit(area, &lines, mouse);
+                if input.key_down.is_shift() {
+                    area.anchor.get_or_insert(area.cursor);
+                } else {
+                    area.anchor = Some(pos);
+                }
+                area.cursor = pos;
+            } else if input.mouse_down.is_left() {
+                // drag selection, scroll when the mouse leaves the view
+                let outside = |p: i32, lo: i32, len: i32| if p < lo { p - lo } else if p > lo + len { p - lo - len } else { 0 };
+                area.scroll.x += outside(mouse.x, view.x, view.width) / 2;
+                area.scroll.y += outside(mouse.y, view.y, view.height) / 2;
+                area.cursor = hit(area, &lines, mouse);
+                if let Some((line, x)) = area.block_anchor {
+                    let (l, mx) = text_point(area, mouse);
+                    area.block = Some(BlockSelection {
+                        first_line: min(line, l),
+                        last_line: max(line, l),
+                        x0: min(x, mx),
+                        x1: max(x, mx),
+                    });
+                }
+            } else {
+                area.block_anchor = None;
+            }
+
+            let keys = input.key_pressed;
+            let moves = keys & (KeyMode::LEFT | KeyMode::RIGHT | KeyMode::HOME | KeyMode::END | KeyMode::UP | KeyMode::DOWN);
+            if !moves.is_none() || keys.is_return() {
+                area.block = None;
+            }
+            if !moves.is_none() {
+                if input.key_down.is_shift() {
+                    area.anchor.get_or_insert(area.cursor);
+                } else {
+                    area.anchor = None;
+                }
+                follow_cursor = true;
+            }
+
+            // typing replaces the selection, which also absorbs a backspace or delete
+            let mut edit = input.clone();
+            if keys.is_return() {
+                edit.input_text.push('\n');
+            }
+            let edits = !area.read_only && (!edit.input_text.is_empty() || keys.is_backspace() || keys.is_delete());
+            if area.read_only {
+                edit.input_text.clear();
+                edit.key_pressed &= !(KeyMode::BACKSPACE | KeyMode::DELETE);
+            }
+            if let (true, Some(block)) = (edits, area.block) {
+                if area.edit_block(&atlas, font, block, &edit) {
+                    res |= ResourceState::CHANGE;
+                }
+            } else {
+                if edits && area.delete_selection() {
+                    edit.key_pressed &= !(KeyMode::BACKSPACE | KeyMode::DELETE);
+                    res |= ResourceState::CHANGE;
+                }
+                if edit_text(&mut area.text, &mut area.cursor, &edit) {
+                    res |= ResourceState::CHANGE;
+                }
+            }
+            if edits {
+                area.anchor = None;
+                area.block_anchor = None;
+                follow_cursor = true;
+                area.update_metrics();
+                lines = area.lines.clone();
+            }
+
+            if keys.is_up() || keys.is_down() {
+                let line = TextArea::line_of(&lines, area.cursor);
+                let x = text_w(&area.text[lines[line].start..area.cursor]);
+                let target = if keys.is_up() { line.saturating_sub(1) } else { min(line + 1, lines.len() - 1) };
+                area.cursor = area.offset_at_x(&atlas, font, &lines[target], x);
+            }
+        } else {
+            area.autoscroll_origin = None;
+        }
+
+        if let Some(origin) = area.autoscroll_origin {
+            area.scroll.x += (mouse.x - origin.x) / 8;
+            area.scroll.y += (mouse.y - origin.y) / 8;
+        }
+        if hovered {
+            area.scroll.x += input.scroll_delta.x;
+            area.scroll.y += input.scroll_delta.y;
+        }
+
+        let cursor_line = TextArea::line_of(&lines, area.cursor);
+        let cursor_x = text_w(&area.text[lines[cursor_line].start..area.cursor]);
+        let cursor_y = cursor_line as i32 * line_h;
+        if follow_cursor {
+            area.scroll.x = area.scroll.x.clamp(min(cursor_x + 1 - view.width, cursor_x), cursor_x);
+            area.scroll.y = area.scroll.y.clamp(min(cursor_y + line_h - view.height, cursor_y), cursor_y);
+        }
+        let content_w = lines.iter().map(|l| text_w(&area.text[l.clone()])).max().unwrap_or(0) + 1;
+        let content_h = lines.len() as i32 * line_h;
+        area.scroll.x = area.scroll.x.clamp(0, max(content_w - view.width, 0));
+        area.scroll.y = area.scroll.y.clamp(0, max(content_h - view.height, 0));
+
+        // draw
+        self.draw_widget_frame(id, r, ControlColor::Base, WidgetOption::NONE);
+        self.push_clip_rect(view);
+        let text_color = self.style.colors[ControlColor::Text as usize];
+        let select_color = self.style.colors[ControlColor::SelectionBG as usize];
+        let selection = area.selection();
+        let block = area.block.map(|b| (b, area.block_ranges(&atlas, font, &b)));
+        let first = max(area.scroll.y / max(line_h, 1), 0) as usize;
+        let last = min(((area.scroll.y + view.height) / max(line_h, 1)) as usize + 1, lines.len());
+        for (i, line) in lines.iter().enumerate().take(last).skip(first) {
+            let x = view.x - area.scroll.x;
+            let y = view.y + i as i32 * line_h - area.scroll.y;
+            if let Some(sel) = &selection {
+                let (a, b) = (max(sel.start, line.start), min(sel.end, line.end));
+                if a <= b && sel.start <= line.end && sel.end >= line.start {
+                    let x0 = text_w(&area.text[line.start..a]);
+                    // a selected line break is shown as a small block past the end of the line
+                    let x1 = text_w(&area.text[line.start..b]) + if sel.end > line.end { 4 } else { 0 };
+                    self.draw_rect(rect(x + x0, y, x1 - x0, line_h), select_color);
+                }
+            }
+            if let Some((b, ranges)) = &block {
+                if (b.first_line..=b.last_line).contains(&i) {
+                    let sel = &ranges[i - b.first_line];
+                    let x0 = text_w(&area.text[line.start..sel.start]);
+                    let x1 = text_w(&area.text[line.start..sel.end]);
+                    self.draw_rect(rect(x + x0, y, max(x1 - x0, 1), line_h), if sel.is_empty() { text_color } else { select_color });
+                }
+            }
+            self.draw_text(font, &area.text[line.clone()], vec2(x, y), text_color);
+        }
+        if focused && block.is_none() {
+            self.draw_rect(rect(view.x - area.scroll.x + cursor_x, view.y - area.scroll.y + cursor_y, 1, line_h), text_color);
+        }
+        self.pop_clip_rect();
+
+        if let Some(origin) = area.autoscroll_origin {
+            let center = Vec2f::new(origin.x as f32, origin.y as f32);
+            self.draw_circle(center, 6.0, select_color);
+            self.draw_circle(center, 2.0, text_color);
+        }
+        res
+    }
+}
@@ -0,0 +1,128 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Cursor based editing shared by the text widgets. Cursors are byte offsets into the buffer, always on a char
+// boundary.
+use super::*;
+
+pub fn prev_char_boundary(text: &str, pos: usize) -> usize {
+    text[..pos].char_indices().next_back().map(|(i, _)| i).unwrap_or(0)
+}
+
+pub fn next_char_boundary(text: &str, pos: usize) -> usize {
+    text[pos..].chars().next().map(|c| pos + c.len_utf8()).unwrap_or(pos)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// start of the word before `pos`, skipping the separators right before it
+pub fn prev_word_boundary(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos].char_indices().rev().skip_while(|(_, c)| !is_word_char(*c)).peekable();
+    let mut start = chars.peek().map(|(i, _)| *i).unwrap_or(0);
+    for (i, c) in chars {
+        if !is_word_char(c) {
+            break;
+        }
+        start = i;
+    }
+    start
+}
+
+// end of the word after `pos`, skipping the separators right after it
+pub fn next_word_boundary(text: &str, pos: usize) -> usize {
+    let mut end = pos;
+    let mut in_word = false;
+    for (i, c) in text[pos..].char_indices() {
+        if is_word_char(c) {
+            in_word = true;
+        } else if in_word {
+            return pos + i;
+        }
+        end = pos + i + c.len_utf8();
+    }
+    end
+}
+
+pub fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+pub fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map(|i| pos + i).unwrap_or(text.len())
+}
+
+pub fn clamp_cursor(text: &str, pos: usize) -> usize {
+    let mut pos = min(pos, text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+// Applies the text typed and the editing keys pressed this frame, returns true if the text changed
+pub fn edit_text(buf: &mut String, cursor: &mut usize, input: &Input) -> bool {
+    let mut changed = false;
+    let keys = input.key_pressed;
+    let ctrl = input.key_down.is_ctrl();
+    *cursor = clamp_cursor(buf, *cursor);
+
+    if !input.input_text.is_empty() {
+        buf.insert_str(*cursor, &input.input_text);
+        *cursor += input.input_text.len();
+        changed = true;
+    }
+
+    if keys.is_backspace() && *cursor > 0 {
+        let start = if ctrl { prev_word_boundary(buf, *cursor) } else { prev_char_boundary(buf, *cursor) };
+        buf.replace_range(start..*cursor, "");
+        *cursor = start;
+        changed = true;
+    }
+    if keys.is_delete() && *cursor < buf.len() {
+        let end = if ctrl { next_word_boundary(buf, *cursor) } else { next_char_boundary(buf, *cursor) };
+        buf.replace_range(*cursor..end, "");
+        changed = true;
+    }
+
+    if keys.is_left() {
+        *cursor = if ctrl { prev_word_boundary(buf, *cursor) } else { prev_char_boundary(buf, *cursor) };
+    }
+    if keys.is_right() {
+        *cursor = if ctrl { next_word_boundary(buf, *cursor) } else { next_char_boundary(buf, *cursor) };
+    }
+    if keys.is_home() {
+        *cursor = if ctrl { 0 } else { line_start(buf, *cursor) };
+    }
+    if keys.is_end() {
+        *cursor = if ctrl { buf.len() } else { line_end(buf, *cursor) };
+    }
+    changed
+}
@@ -0,0 +1,142 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Right-click menu of the text widgets. The widgets post their right clicks to the input, the menu applies
+// the chosen action to the widget on the next frame and moves the copied text to the clipboard.
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextAction {
+    Cut,
+    Copy,
+    Paste(String),
+    SelectAll,
+}
+
+pub struct TextMenuState {
+    popup: WindowHandle,
+    target: Option<(Id, bool)>, // widget and whether it can be edited
+    entries: Vec<String>,
+    pub extras: Extras,
+}
+
+impl TextMenuState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>) -> Self {
+        Self { popup: ctx.new_popup("!text_menu"), target: None, entries: Vec::new(), extras: Extras::default() }
+    }
+
+    // app specific entries, shown under the clipboard ones
+    pub fn push_entry(&mut self, label: &str) {
+        self.entries.push(label.to_string());
+    }
+
+    pub fn clear_entries(&mut self) {
+        self.entries.clear();
+    }
+
+    // the widget the menu was opened on
+    pub fn target(&self) -> Option<Id> {
+        self.target.map(|(id, _)| id)
+    }
+
+    // Call once per frame after the windows, returns the index of the app entry picked
+    pub fn eval<R: Renderer>(&mut self, ctx: &mut Context<R>, clipboard: &mut dyn Clipboard) -> Option<usize> {
+        let (copied, request) = {
+            let mut input = ctx.input.borrow_mut();
+            (input.clipboard_out.take(), input.text_menu_request.take())
+        };
+        if let Some(text) = copied {
+            clipboard.set_text(&text);
+        }
+        if let Some(target) = request {
+            self.target = Some(target);
+            ctx.open_popup(&mut self.popup);
+        }
+
+        let editable = self.target.map(|(_, e)| e).unwrap_or(false);
+        let mut action = None;
+        let mut picked = None;
+        let entries = &self.entries;
+        ctx.popup(&mut self.popup, |c| {
+            c.set_row_widths_height(&[100], 0);
+            if c.disabled(!editable, |c| c.button_ex("Cut", None, WidgetOption::NONE)).is_submitted() {
+                action = Some(TextAction::Cut);
+            }
+            if c.button_ex("Copy", None, WidgetOption::NONE).is_submitted() {
+                action = Some(TextAction::Copy);
+            }
+            if c.disabled(!editable, |c| c.button_ex("Paste", None, WidgetOption::NONE)).is_submitted() {
+                action = Some(TextAction::Paste(String::new()));
+            }
+            if c.button_ex("Select All", None, WidgetOption::NONE).is_submitted() {
+                action = Some(TextAction::SelectAll);
+            }
+            for (i, label) in entries.iter().enumerate() {
+                if c.button_ex(label.as_str(), None, WidgetOption::NONE).is_submitted() {
+                    picked = Some(i);
+                }
+            }
+            if action.is_some() || picked.is_some() {
+                WindowState::Closed
+            } else {
+                WindowState::Open
+            }
+        });
+
+        let action = match action {
+            Some(TextAction::Paste(_)) => clipboard.get_text().map(TextAction::Paste),
+            a => a,
+        };
+        if let (Some(action), Some((id, _))) = (action, self.target) {
+            ctx.input.borrow_mut().text_action_next = Some((id, action));
+        }
+        picked
+    }
+}
+
+impl Container {
+    // opens the text menu on a right click over `r`
+    pub(crate) fn request_text_menu(&mut self, id: Id, r: Recti, editable: bool) {
+        if self.input.borrow().mouse_pressed.is_right() && self.mouse_over(r, self.in_hover_root) {
+            self.input.borrow_mut().text_menu_request = Some((id, editable));
+        }
+    }
+
+    pub(crate) fn take_text_action(&mut self, id: Id) -> Option<TextAction> {
+        let mut input = self.input.borrow_mut();
+        match &input.text_action {
+            Some((target, _)) if *target == id => input.text_action.take().map(|(_, a)| a),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn copy_to_clipboard(&mut self, text: &str) {
+        self.input.borrow_mut().clipboard_out = Some(text.to_string());
+    }
+}
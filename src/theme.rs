@@ -0,0 +1,175 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Built-in color palettes, they only replace the colors of a style and keep its metrics and font.
+use super::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+#[derive(Copy, Clone)]
+pub struct Theme {
+    pub colors: [Color; ControlColor::Max as usize],
+}
+
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color { r, g, b, a: 255 }
+}
+
+const CLEAR: Color = Color { r: 0, g: 0, b: 0, a: 0 };
+
+impl Theme {
+    pub const BUILTIN: [&'static str; 4] = ["dark", "light", "high-contrast", "deuteranopia"];
+
+    pub fn builtin(name: &str) -> Option<Theme> {
+        // Text, Border, WindowBG, TitleBG, TitleText, PanelBG, Button, ButtonHover, ButtonFocus, Base, BaseHover,
+        // BaseFocus, ScrollBase, ScrollThumb, Accent, Success, Warning, Error, Disabled, SelectionBG, RowAlt, GridLine
+        let colors = match name {
+            "dark" => Style::default().colors,
+            "light" => [
+                rgb(20, 20, 20),
+                rgb(160, 160, 160),
+                rgb(235, 235, 235),
+                rgb(200, 200, 200),
+                rgb(20, 20, 20),
+                CLEAR,
+                rgb(205, 205, 205),
+                rgb(185, 185, 185),
+                rgb(165, 165, 165),
+                rgb(250, 250, 250),
+                rgb(242, 242, 242),
+                rgb(230, 230, 230),
+                rgb(215, 215, 215),
+                rgb(170, 170, 170),
+                rgb(30, 100, 200),
+                rgb(30, 140, 50),
+                rgb(200, 130, 0),
+                rgb(190, 30, 30),
+                rgb(150, 150, 150),
+                rgb(170, 200, 240),
+                Color { r: 0, g: 0, b: 0, a: 12 },
+                rgb(210, 210, 210),
+            ],
+            "high-contrast" => [
+                rgb(255, 255, 255),
+                rgb(255, 255, 0),
+                rgb(0, 0, 0),
+                rgb(0, 0, 0),
+                rgb(255, 255, 0),
+                CLEAR,
+                rgb(0, 0, 0),
+                rgb(0, 90, 160),
+                rgb(0, 140, 255),
+                rgb(0, 0, 0),
+                rgb(40, 40, 40),
+                rgb(0, 60, 120),
+                rgb(0, 0, 0),
+                rgb(255, 255, 0),
+                rgb(0, 255, 255),
+                rgb(0, 255, 0),
+                rgb(255, 255, 0),
+                rgb(255, 64, 64),
+                rgb(160, 160, 160),
+                rgb(0, 90, 200),
+                Color { r: 255, g: 255, b: 255, a: 30 },
+                rgb(255, 255, 0),
+            ],
+            // blue/orange accents (Okabe-Ito), no meaning carried by red against green
+            "deuteranopia" => [
+                rgb(235, 235, 235),
+                rgb(20, 20, 20),
+                rgb(45, 45, 50),
+                rgb(25, 35, 60),
+                rgb(240, 240, 240),
+                CLEAR,
+                rgb(60, 80, 120),
+                rgb(80, 110, 170),
+                rgb(230, 159, 0),
+                rgb(30, 30, 35),
+                rgb(40, 45, 60),
+                rgb(50, 60, 90),
+                rgb(35, 35, 40),
+                rgb(86, 180, 233),
+                rgb(86, 180, 233),
+                rgb(0, 114, 178),
+                rgb(230, 159, 0),
+                rgb(213, 94, 0),
+                rgb(110, 110, 110),
+                rgb(50, 80, 130),
+                Color { r: 255, g: 255, b: 255, a: 10 },
+                rgb(60, 60, 70),
+            ],
+            _ => return None,
+        };
+        Some(Theme { colors })
+    }
+
+    pub fn apply(&self, style: &mut Style) {
+        style.colors = self.colors;
+    }
+
+    pub fn style(&self) -> Style {
+        let mut style = Style::default();
+        self.apply(&mut style);
+        style
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Backends forward the OS light/dark setting here, at startup and whenever it changes
+    pub fn set_system_appearance(&mut self, appearance: Appearance) {
+        self.appearance = appearance;
+        if let Some((light, dark)) = self.auto_theme {
+            let theme = if appearance == Appearance::Light { light } else { dark };
+            theme.apply(&mut self.style);
+        }
+    }
+
+    pub fn system_appearance(&self) -> Appearance {
+        self.appearance
+    }
+
+    // Follows the system appearance with the given themes, the current one is applied right away
+    pub fn set_auto_theme(&mut self, light: Theme, dark: Theme) {
+        self.auto_theme = Some((light, dark));
+        self.set_system_appearance(self.appearance);
+    }
+
+    // the style keeps the colors of the last applied theme
+    pub fn clear_auto_theme(&mut self) {
+        self.auto_theme = None;
+    }
+
+    pub fn is_auto_theme(&self) -> bool {
+        self.auto_theme.is_some()
+    }
+}
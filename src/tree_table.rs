@@ -0,0 +1,210 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone)]
+pub struct TableColumn {
+    pub title: String,
+    pub width: i32,
+    pub align: WidgetOption, // alignment of the cells, the first column is always left aligned
+}
+
+// Column widths and sorting are owned by the state; the application sorts its rows according to `sort()`
+#[derive(Clone)]
+pub struct TreeTableState {
+    columns: Vec<TableColumn>,
+    sort: Option<(usize, SortOrder)>,
+    pub extras: Extras,
+}
+
+impl TreeTableState {
+    pub fn new(columns: &[(&str, i32)]) -> Self {
+        Self {
+            columns: columns.iter().map(|(title, width)| TableColumn { title: title.to_string(), width: *width, align: WidgetOption::NONE }).collect(),
+            sort: None,
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn columns(&self) -> &[TableColumn] {
+        &self.columns
+    }
+
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    pub fn set_sort(&mut self, sort: Option<(usize, SortOrder)>) {
+        self.sort = sort;
+    }
+
+    pub fn set_column_align(&mut self, column: usize, align: WidgetOption) {
+        self.columns[column].align = align;
+    }
+}
+
+pub struct TreeTable<'a> {
+    container: &'a mut Container,
+    widths: Vec<i32>,
+    aligns: Vec<WidgetOption>,
+    depth: i32,
+    row: usize, // rows shown so far, for the striping
+}
+
+impl<'a> TreeTable<'a> {
+    pub fn container(&mut self) -> &mut Container {
+        self.container
+    }
+
+    fn cells(&mut self, cells: &[&str]) {
+        for i in 1..self.widths.len() {
+            let text = cells.get(i - 1).copied().unwrap_or("");
+            let r = self.container.next_cell();
+            if self.container.style.column_lines {
+                let x = r.x - (self.container.style.spacing + 1) / 2;
+                let color = self.container.style.colors[ControlColor::GridLine as usize];
+                self.container.draw_rect(rect(x, r.y, 1, r.height + self.container.style.spacing), color);
+            }
+            self.container.draw_control_text(text, r, ControlColor::Text, self.aligns[i]);
+        }
+    }
+
+    fn first_cell(&mut self, label: &str, id: Id, expander: Option<bool>) -> Recti {
+        let r = self.container.next_cell();
+        let indent = self.depth * self.container.style.indent;
+        let row = rect(r.x, r.y, self.container.layout.top().body.width, r.height);
+        self.container.update_control(id, row, WidgetOption::NONE);
+        self.container.draw_row_background(row, self.row);
+        self.container.draw_row_line(row);
+        self.row += 1;
+        if self.container.hover == Some(id) {
+            self.container.draw_frame(row, ControlColor::ButtonHover);
+        }
+
+        let icon_r = rect(r.x + indent, r.y, r.height, r.height);
+        if let Some(expanded) = expander {
+            let color = self.container.style.colors[ControlColor::Text as usize];
+            self.container.draw_icon(if expanded { COLLAPSE_ICON } else { EXPAND_ICON }, icon_r, color);
+        }
+        let text_r = rect(icon_r.x + icon_r.width - self.container.style.padding, r.y, r.width - indent - icon_r.width, r.height);
+        self.container.draw_control_text(label, text_r, ControlColor::Text, WidgetOption::NONE);
+        row
+    }
+
+    pub fn leaf(&mut self, label: &str, cells: &[&str]) -> ResourceState {
+        let id = self.container.idmngr.get_id_from_str(label);
+        self.first_cell(label, id, None);
+        self.cells(cells);
+        if self.container.is_clicked(id) {
+            ResourceState::SUBMIT
+        } else {
+            ResourceState::NONE
+        }
+    }
+
+    #[must_use]
+    pub fn node<F: FnOnce(&mut Self)>(&mut self, label: &str, state: NodeState, f: F) -> NodeState {
+        self.node_ex(label, &[], state, f)
+    }
+
+    #[must_use]
+    pub fn node_ex<F: FnOnce(&mut Self)>(&mut self, label: &str, cells: &[&str], state: NodeState, f: F) -> NodeState {
+        let id = self.container.idmngr.get_id_from_str(label);
+        self.first_cell(label, id, Some(state.is_expanded()));
+        self.cells(cells);
+
+        let toggled = self.container.is_clicked(id);
+        let res = if state.is_expanded() ^ toggled { NodeState::Expanded } else { NodeState::Closed };
+        if res.is_expanded() {
+            self.container.idmngr.push_id(id);
+            self.depth += 1;
+            f(self);
+            self.depth -= 1;
+            self.container.idmngr.pop_id();
+        }
+        res
+    }
+}
+
+impl Container {
+    #[inline(never)]
+    fn tree_table_header(&mut self, state: &mut TreeTableState) -> Vec<i32> {
+        let widths: Vec<i32> = state.columns.iter().map(|c| c.width).collect();
+        self.set_row_widths_height(&widths, 0);
+        let pressed = self.input.borrow().mouse_pressed.is_left();
+        let mouse_down = self.input.borrow().mouse_down.is_left();
+        let delta = self.input.borrow().mouse_delta.x;
+
+        for i in 0..state.columns.len() {
+            let r = self.next_cell();
+            let id = self.idmngr.get_id_from_str(state.columns[i].title.as_str());
+            let grip_id = self.idmngr.get_id_u32(i as u32);
+
+            // the rightmost 4 pixels of a column are the resize grip
+            let grip = rect(r.x + r.width - 2, r.y, 4 + self.style.spacing, r.height);
+            self.update_control(grip_id, grip, WidgetOption::NONE);
+            self.update_control(id, rect(r.x, r.y, r.width - 2, r.height), WidgetOption::NONE);
+            if self.focus == Some(grip_id) && mouse_down && state.columns[i].width > 0 {
+                state.columns[i].width = max(state.columns[i].width + delta, 16);
+            }
+            if self.focus == Some(id) && pressed {
+                state.sort = match state.sort {
+                    Some((col, SortOrder::Ascending)) if col == i => Some((i, SortOrder::Descending)),
+                    _ => Some((i, SortOrder::Ascending)),
+                };
+            }
+
+            self.draw_widget_frame(id, r, ControlColor::Button, WidgetOption::NONE);
+            let title = match state.sort {
+                Some((col, SortOrder::Ascending)) if col == i => format!("{} ^", state.columns[i].title),
+                Some((col, SortOrder::Descending)) if col == i => format!("{} v", state.columns[i].title),
+                _ => state.columns[i].title.clone(),
+            };
+            self.draw_control_text(title.as_str(), r, ControlColor::Text, WidgetOption::NONE);
+        }
+        widths
+    }
+
+    pub fn tree_table<F: FnOnce(&mut TreeTable)>(&mut self, state: &mut TreeTableState, f: F) {
+        self.idmngr.push_id_from_ptr(state);
+        let widths = self.tree_table_header(state);
+        self.set_row_widths_height(&widths, 0);
+        let aligns = state.columns.iter().map(|c| c.align).collect();
+        let mut table = TreeTable { container: self, widths, aligns, depth: 0, row: 0 };
+        f(&mut table);
+        self.idmngr.pop_id();
+    }
+}
@@ -0,0 +1,248 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+// Data driven UI: windows and their widgets are described by a serde deserializable tree (from JSON, RON, ...).
+// Editable widgets store their value under a key in `UiDescState`, buttons emit their action id.
+use super::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WidgetDesc {
+    Row {
+        widths: Vec<i32>,
+        #[serde(default)]
+        height: i32,
+    },
+    Label {
+        text: String,
+    },
+    Text {
+        text: String,
+    },
+    Button {
+        label: String,
+        action: String,
+    },
+    Checkbox {
+        label: String,
+        key: String,
+    },
+    Slider {
+        key: String,
+        low: Real,
+        high: Real,
+        #[serde(default)]
+        step: Real,
+        #[serde(default)]
+        precision: usize,
+    },
+    Number {
+        key: String,
+        #[serde(default = "default_number_step")]
+        step: Real,
+        #[serde(default)]
+        precision: usize,
+    },
+    Textbox {
+        key: String,
+    },
+    Header {
+        label: String,
+        #[serde(default)]
+        expanded: bool,
+        children: Vec<WidgetDesc>,
+    },
+    Column {
+        children: Vec<WidgetDesc>,
+    },
+}
+
+fn default_number_step() -> Real {
+    1.0
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct WindowDesc {
+    pub title: String,
+    pub rect: [i32; 4],
+    #[serde(default)]
+    pub widgets: Vec<WidgetDesc>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct UiDesc {
+    pub windows: Vec<WindowDesc>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiValue {
+    Bool(bool),
+    Real(Real),
+    Text(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiEvent {
+    Action(String),
+    Changed(String),
+}
+
+pub struct UiDescState {
+    desc: UiDesc,
+    windows: Vec<WindowHandle>,
+    values: HashMap<String, UiValue>,
+    headers: HashMap<String, NodeState>,
+    pub extras: Extras,
+}
+
+impl UiDescState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, desc: UiDesc) -> Self {
+        let windows = desc.windows.iter().map(|w| ctx.new_window(&w.title, rect(w.rect[0], w.rect[1], w.rect[2], w.rect[3]))).collect();
+        Self {
+            desc,
+            windows,
+            values: HashMap::new(),
+            headers: HashMap::new(),
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn value(&self, key: &str) -> Option<&UiValue> {
+        self.values.get(key)
+    }
+
+    pub fn set_value(&mut self, key: &str, value: UiValue) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    fn bool_value(values: &HashMap<String, UiValue>, key: &str) -> bool {
+        match values.get(key) {
+            Some(UiValue::Bool(b)) => *b,
+            _ => false,
+        }
+    }
+
+    fn real_value(values: &HashMap<String, UiValue>, key: &str) -> Real {
+        match values.get(key) {
+            Some(UiValue::Real(v)) => *v,
+            _ => 0.0,
+        }
+    }
+
+    fn text_value(values: &HashMap<String, UiValue>, key: &str) -> String {
+        match values.get(key) {
+            Some(UiValue::Text(t)) => t.clone(),
+            _ => String::new(),
+        }
+    }
+
+    fn widgets(
+        container: &mut Container,
+        widgets: &[WidgetDesc],
+        values: &mut HashMap<String, UiValue>,
+        headers: &mut HashMap<String, NodeState>,
+        events: &mut Vec<UiEvent>,
+    ) {
+        for w in widgets {
+            match w {
+                WidgetDesc::Row { widths, height } => container.set_row_widths_height(widths, *height),
+                WidgetDesc::Label { text } => container.label(text),
+                WidgetDesc::Text { text } => container.text(text),
+                WidgetDesc::Button { label, action } => {
+                    if container.button_ex(label, None, WidgetOption::ALIGN_CENTER).is_submitted() {
+                        events.push(UiEvent::Action(action.clone()));
+                    }
+                }
+                WidgetDesc::Checkbox { label, key } => {
+                    let mut state = Self::bool_value(values, key);
+                    let id = container.idmngr.get_id_from_str(key);
+                    let r = container.next_cell();
+                    if container.checkbox_raw(label, &mut state, id, r).is_changed() {
+                        values.insert(key.clone(), UiValue::Bool(state));
+                        events.push(UiEvent::Changed(key.clone()));
+                    }
+                }
+                WidgetDesc::Slider { key, low, high, step, precision } => {
+                    let mut value = Self::real_value(values, key);
+                    let id = container.idmngr.get_id_from_str(key);
+                    let r = container.next_cell();
+                    if container.slider_raw(&mut value, id, r, *low, *high, *step, *precision, WidgetOption::ALIGN_CENTER).is_changed() {
+                        values.insert(key.clone(), UiValue::Real(value));
+                        events.push(UiEvent::Changed(key.clone()));
+                    }
+                }
+                WidgetDesc::Number { key, step, precision } => {
+                    let mut value = Self::real_value(values, key);
+                    let id = container.idmngr.get_id_from_str(key);
+                    let r = container.next_cell();
+                    if container.number_raw(&mut value, id, r, *step, *precision, WidgetOption::ALIGN_CENTER).is_changed() {
+                        values.insert(key.clone(), UiValue::Real(value));
+                        events.push(UiEvent::Changed(key.clone()));
+                    }
+                }
+                WidgetDesc::Textbox { key } => {
+                    let mut text = Self::text_value(values, key);
+                    let id = container.idmngr.get_id_from_str(key);
+                    let r = container.next_cell();
+                    let res = container.textbox_raw(&mut text, id, r, WidgetOption::NONE);
+                    if res.is_changed() {
+                        values.insert(key.clone(), UiValue::Text(text));
+                        events.push(UiEvent::Changed(key.clone()));
+                    }
+                    if res.is_submitted() {
+                        events.push(UiEvent::Action(key.clone()));
+                    }
+                }
+                WidgetDesc::Header { label, expanded, children } => {
+                    let default = if *expanded { NodeState::Expanded } else { NodeState::Closed };
+                    let state = *headers.get(label).unwrap_or(&default);
+                    let state = container.header(label, state, |container| Self::widgets(container, children, values, headers, events));
+                    headers.insert(label.clone(), state);
+                }
+                WidgetDesc::Column { children } => container.column(|container| Self::widgets(container, children, values, headers, events)),
+            }
+        }
+    }
+
+    // evaluates all the described windows and returns the events of the frame
+    pub fn eval<R: Renderer>(&mut self, ctx: &mut Context<R>) -> Vec<UiEvent> {
+        let mut events = Vec::new();
+        for (desc, window) in self.desc.windows.iter().zip(self.windows.iter_mut()) {
+            let values = &mut self.values;
+            let headers = &mut self.headers;
+            ctx.window(window, ContainerOption::NONE, |container| {
+                Self::widgets(container, &desc.widgets, values, headers, &mut events);
+                WindowState::Open
+            });
+        }
+        events
+    }
+}
@@ -0,0 +1,160 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+use std::fmt;
+use std::str::FromStr;
+
+// The names from the root window down to a panel, e.g. `Settings/General/Advanced`. Unlike the handles it
+// doesn't depend on where things were allocated, so it can be saved, sent to a test harness or typed in a
+// script. Slashes and backslashes inside a name are escaped with a backslash in the text form.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UiPath(Vec<String>);
+
+impl UiPath {
+    pub fn new(root: &str) -> Self {
+        Self(vec![root.to_string()])
+    }
+
+    pub fn child(&self, name: &str) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(name.to_string());
+        Self(segments)
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        match self.0.len() {
+            0 | 1 => None,
+            n => Some(Self(self.0[..n - 1].to_vec())),
+        }
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+
+    // the root window name
+    pub fn root(&self) -> Option<&str> {
+        self.0.first().map(|s| s.as_str())
+    }
+
+    pub fn is_ancestor_of(&self, other: &UiPath) -> bool {
+        other.0.len() > self.0.len() && other.0.starts_with(&self.0)
+    }
+
+    // FNV-1a over the segments, the same on every run and platform
+    pub fn stable_id(&self) -> u64 {
+        let mut h: u64 = 0xcbf29ce484222325;
+        for s in &self.0 {
+            for b in s.bytes().chain(std::iter::once(0)) {
+                h = (h ^ b as u64).wrapping_mul(0x100000001b3);
+            }
+        }
+        h
+    }
+}
+
+impl fmt::Display for UiPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, s) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(&s.replace('\\', "\\\\").replace('/', "\\/"))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for UiPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = vec![String::new()];
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(e @ ('\\' | '/')) => segments.last_mut().unwrap().push(e),
+                    _ => return Err(format!("bad escape in path: {}", s)),
+                },
+                '/' => segments.push(String::new()),
+                c => segments.last_mut().unwrap().push(c),
+            }
+        }
+        Ok(Self(segments))
+    }
+}
+
+impl Container {
+    // where this container was laid out last, a panel takes the path of its parent when it begins
+    pub fn path(&self) -> UiPath {
+        self.path.clone()
+    }
+
+    fn find_panel(&self, path: &UiPath) -> Option<ContainerHandle> {
+        for p in &self.panels {
+            let panel_path = p.inner().path.clone();
+            if panel_path == *path {
+                return Some(p.clone());
+            }
+            if panel_path.is_ancestor_of(path) {
+                if let Some(found) = p.inner().find_panel(path) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl WindowHandle {
+    pub fn path(&self) -> UiPath {
+        self.inner().main.path.clone()
+    }
+}
+
+impl ContainerHandle {
+    pub fn path(&self) -> UiPath {
+        self.inner().path.clone()
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // the window of the last frame with this path, the top most one if several share the name
+    pub fn find_window(&self, path: &UiPath) -> Option<WindowHandle> {
+        self.root_list.iter().rev().find(|w| w.path() == *path).cloned()
+    }
+
+    // a panel laid out during the last frame
+    pub fn find_panel(&self, path: &UiPath) -> Option<ContainerHandle> {
+        let root = UiPath::new(path.root()?);
+        self.root_list.iter().rev().filter(|w| w.path() == root).find_map(|w| w.inner().main.find_panel(path))
+    }
+}
@@ -0,0 +1,188 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+use std::collections::HashMap;
+
+type Setter<T> = Rc<dyn Fn(&mut T, &EventValue)>;
+
+struct Edit<T> {
+    label: String,
+    // the widget whose drag is merged into this edit, if any
+    id: Option<Id>,
+    apply: Box<dyn Fn(&mut T)>,
+    revert: Box<dyn Fn(&mut T)>,
+}
+
+struct Property<T> {
+    label: String,
+    get: Rc<dyn Fn(&T) -> EventValue>,
+    set: Setter<T>,
+    last: EventValue,
+}
+
+// Undo/redo history over the application state `T`. Commands are either pushed directly with their
+// apply/revert closures or built from the `Changed` events of bound widgets, in which case a drag over
+// several frames is merged into one edit. Nothing is mapped to keys here, the backend calls `undo` and
+// `redo` for Ctrl+Z and Ctrl+Y.
+pub struct UndoStack<T> {
+    done: Vec<Edit<T>>,
+    undone: Vec<Edit<T>>,
+    limit: usize,
+    properties: HashMap<Id, Property<T>>,
+    open: Option<Id>,
+}
+
+impl<T: 'static> UndoStack<T> {
+    pub fn new(limit: usize) -> Self {
+        Self { done: Vec::new(), undone: Vec::new(), limit, properties: HashMap::new(), open: None }
+    }
+
+    // Records a change the application already applied
+    pub fn push<A: Fn(&mut T) + 'static, R: Fn(&mut T) + 'static>(&mut self, label: &str, apply: A, revert: R) {
+        self.open = None;
+        self.record(Edit { label: label.to_string(), id: None, apply: Box::new(apply), revert: Box::new(revert) });
+    }
+
+    // Ties the widget `id` to a property of the state, `get` reads it and `set` writes a value reported
+    // by the widget back
+    pub fn bind<G, S>(&mut self, target: &T, id: Id, label: &str, get: G, set: S)
+    where
+        G: Fn(&T) -> EventValue + 'static,
+        S: Fn(&mut T, &EventValue) + 'static,
+    {
+        let last = get(target);
+        self.properties.insert(id, Property { label: label.to_string(), get: Rc::new(get), set: Rc::new(set), last });
+    }
+
+    pub fn unbind(&mut self, id: Id) {
+        self.properties.remove(&id);
+    }
+
+    // Turns the changes of the bound widgets into edits, call it with the events of each frame
+    pub fn handle_events(&mut self, target: &T, events: &[WidgetEvent]) {
+        for e in events {
+            match e.kind {
+                WidgetEventKind::Changed => (),
+                // a new press starts a new edit, even on the same widget
+                WidgetEventKind::Focused => {
+                    self.open = None;
+                    continue;
+                }
+                _ => continue,
+            }
+            let (before, after, set) = match self.properties.get_mut(&e.id) {
+                Some(p) => {
+                    let after = (p.get)(target);
+                    (std::mem::replace(&mut p.last, after.clone()), after, p.set.clone())
+                }
+                None => continue,
+            };
+            let merge = self.open == Some(e.id) && self.done.last().map(|edit| edit.id == Some(e.id)).unwrap_or(false);
+            if merge {
+                // keep the revert of the first frame of the drag
+                let edit = self.done.last_mut().unwrap();
+                edit.apply = Box::new(move |t| set(t, &after));
+                self.undone.clear();
+            } else {
+                let label = self.properties[&e.id].label.clone();
+                let revert_set = set.clone();
+                self.record(Edit {
+                    label,
+                    id: Some(e.id),
+                    apply: Box::new(move |t| set(t, &after)),
+                    revert: Box::new(move |t| revert_set(t, &before)),
+                });
+                self.open = Some(e.id);
+            }
+        }
+    }
+
+    pub fn undo(&mut self, target: &mut T) -> bool {
+        match self.done.pop() {
+            Some(edit) => {
+                (edit.revert)(target);
+                self.undone.push(edit);
+                self.resync(target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self, target: &mut T) -> bool {
+        match self.undone.pop() {
+            Some(edit) => {
+                (edit.apply)(target);
+                self.done.push(edit);
+                self.resync(target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    pub fn undo_label(&self) -> Option<&str> {
+        self.done.last().map(|e| e.label.as_str())
+    }
+
+    pub fn redo_label(&self) -> Option<&str> {
+        self.undone.last().map(|e| e.label.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+        self.open = None;
+    }
+
+    fn record(&mut self, edit: Edit<T>) {
+        self.undone.clear();
+        self.done.push(edit);
+        if self.done.len() > self.limit {
+            self.done.remove(0);
+        }
+    }
+
+    // the bound values changed behind the widgets' back
+    fn resync(&mut self, target: &T) {
+        self.open = None;
+        for p in self.properties.values_mut() {
+            p.last = (p.get)(target);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// A decoded YUV 4:2:0 frame (BT.601, limited range). The chroma planes have half the resolution of the luma
+// plane, rounded up.
+#[derive(Copy, Clone)]
+pub struct Yuv420Frame<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub y: &'a [u8],
+    pub u: &'a [u8],
+    pub v: &'a [u8],
+    pub y_stride: usize,
+    pub uv_stride: usize,
+}
+
+impl<'a> Yuv420Frame<'a> {
+    // tightly packed planes, the strides are the plane widths. Panics when a plane is too small for the size.
+    pub fn new(width: usize, height: usize, y: &'a [u8], u: &'a [u8], v: &'a [u8]) -> Self {
+        let frame = Self { width, height, y, u, v, y_stride: width, uv_stride: width.div_ceil(2) };
+        assert!(frame.is_valid(), "yuv420 planes too small for a {}x{} frame", width, height);
+        frame
+    }
+
+    // the strides cover the rows and every plane holds all its rows
+    pub fn is_valid(&self) -> bool {
+        let plane_len = |stride: usize, row: usize, rows: usize| if rows == 0 { 0 } else { stride * (rows - 1) + row };
+        let (cw, ch) = (self.width.div_ceil(2), self.height.div_ceil(2));
+        self.y_stride >= self.width
+            && self.uv_stride >= cw
+            && self.y.len() >= plane_len(self.y_stride, self.width, self.height)
+            && self.u.len() >= plane_len(self.uv_stride, cw, ch)
+            && self.v.len() >= plane_len(self.uv_stride, cw, ch)
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Color4b {
+        let l = self.y[x + y * self.y_stride] as Real - 16.0;
+        let ci = x / 2 + (y / 2) * self.uv_stride;
+        let u = self.u[ci] as Real - 128.0;
+        let v = self.v[ci] as Real - 128.0;
+        let c = |f: Real| f.round().clamp(0.0, 255.0) as u8;
+        let l = 1.164 * l;
+        color4b(c(l + 1.596 * v), c(l - 0.392 * u - 0.813 * v), c(l + 2.017 * u), 0xFF)
+    }
+
+    // the frame resampled (nearest) to `width` x `height` RGBA pixels
+    pub fn to_rgba(&self, width: usize, height: usize) -> Vec<Color4b> {
+        let mut pixels = Vec::with_capacity(width * height);
+        if self.width == 0 || self.height == 0 {
+            pixels.resize(width * height, color4b(0, 0, 0, 0xFF));
+            return pixels;
+        }
+        for y in 0..height {
+            let sy = y * self.height / max(height, 1);
+            for x in 0..width {
+                pixels.push(self.pixel(x * self.width / max(width, 1), sy));
+            }
+        }
+        pixels
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Copies a video frame into `slot`, scaled to the slot size. Call it once per decoded frame, the slot is
+    // then shown with `Container::video_frame`. Frames with planes or strides too small for their size are
+    // skipped and false is returned.
+    pub fn upload_video_frame(&mut self, slot: SlotId, frame: &Yuv420Frame) -> bool {
+        if !frame.is_valid() {
+            return false;
+        }
+        self.canvas.upload_yuv420(slot, frame);
+        true
+    }
+}
+
+impl Container {
+    // Shows `slot` scaled to fit the cell with the aspect ratio `aspect` (width / height), `None` keeps the
+    // aspect of the slot. The uncovered parts of the cell are filled with black bars. Returns SUBMIT when
+    // clicked, players usually toggle the playback.
+    #[inline(never)]
+    pub fn video_frame(&mut self, slot: SlotId, aspect: Option<Real>, opt: WidgetOption) -> ResourceState {
+        let mut res = ResourceState::NONE;
+        let id = self.idmngr.get_id_u32(slot.into());
+        let r = self.layout.next_sized(self.atlas.get_slot_size(slot));
+        self.update_control(id, r, opt);
+        if self.is_clicked(id) {
+            res |= ResourceState::SUBMIT;
+        }
+
+        self.draw_rect(r, color(0, 0, 0, 0xFF));
+        let src = self.atlas.get_slot_rect(slot);
+        if src.width <= 0 || src.height <= 0 || r.width <= 0 || r.height <= 0 {
+            return res;
+        }
+        let aspect = aspect.filter(|a| *a > 0.0).unwrap_or(src.width as Real / src.height as Real);
+        let (w, h) = if r.width as Real / r.height as Real > aspect {
+            ((r.height as Real * aspect) as i32, r.height)
+        } else {
+            (r.width, (r.width as Real / aspect) as i32)
+        };
+        let dst = rect(r.x + (r.width - w) / 2, r.y + (r.height - h) / 2, w, h);
+        let prev = self.sampler();
+        self.set_sampler(Sampler::LINEAR);
+        self.draw_atlas_region(src, dst, color(0xFF, 0xFF, 0xFF, 0xFF));
+        self.set_sampler(prev);
+        res
+    }
+}
@@ -0,0 +1,90 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// A widget written outside of the crate. `Container::widget` takes the layout cell, derives the id from the
+// widget address and updates the hover/focus state, `handle` then reacts to the input and draws it.
+pub trait Widget {
+    // the size the widget would like, used by the `SizePolicy::Auto` cells. Zero takes the default cell width.
+    fn preferred_size(&self, _style: &Style, _atlas: &AtlasHandle) -> Dimensioni {
+        Dimensioni::new(0, 0)
+    }
+
+    fn options(&self) -> WidgetOption {
+        WidgetOption::NONE
+    }
+
+    fn handle(&mut self, container: &mut Container, id: Id, rect: Recti) -> ResourceState;
+}
+
+impl Container {
+    // the atlas the container draws from, to measure text and images
+    pub fn atlas(&self) -> &AtlasHandle {
+        &self.atlas
+    }
+
+    #[inline(never)]
+    pub fn widget<W: Widget + ?Sized>(&mut self, widget: &mut W) -> ResourceState {
+        let id = self.idmngr.get_id_from_ptr(widget);
+        let r = self.layout.next_sized(widget.preferred_size(&self.style, &self.atlas));
+        let opt = widget.options();
+        self.update_control(id, r, opt);
+        widget.handle(self, id, r)
+    }
+}
+
+// Declares the extension trait `$ext` giving `Container` a `$method` for the widget type `$ty`, so a custom
+// widget is called like the built in ones:
+//
+//     implement_widget!(Knob, KnobExt, knob);
+//     ...
+//     if container.knob(&mut state.knob).is_changed() { ... }
+#[macro_export]
+macro_rules! implement_widget {
+    ($ty:ty, $ext:ident, $method:ident) => {
+        pub trait $ext {
+            fn $method(&mut self, widget: &mut $ty) -> $crate::ResourceState;
+        }
+
+        impl $ext for $crate::Container {
+            fn $method(&mut self, widget: &mut $ty) -> $crate::ResourceState {
+                self.widget(widget)
+            }
+        }
+    };
+}
+
+pub mod widgets {
+    // what a custom widget usually needs: `use microui_redux::widgets::prelude::*;`
+    pub mod prelude {
+        pub use crate::implement_widget;
+        pub use crate::{color, rect, vec2, AtlasHandle, Color, Container, ControlColor, Dimensioni, EventValue, Id, Margin, Real, Recti, ResourceState, RowAlign, SizePolicy, Style, Vec2f, Vec2i, Widget, WidgetEventKind, WidgetOption};
+    }
+}
@@ -0,0 +1,70 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WidgetEventKind {
+    Clicked,
+    Changed,
+    Submitted,
+    Focused,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventValue {
+    None,
+    Bool(bool),
+    Real(Real),
+    Text(String),
+}
+
+// What a widget reported during the frame, see `Context::take_events`
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetEvent {
+    pub id: Id,
+    pub kind: WidgetEventKind,
+    pub value: EventValue,
+}
+
+impl Container {
+    pub fn emit_event(&self, id: Id, kind: WidgetEventKind, value: EventValue) {
+        self.input.borrow_mut().events.push(WidgetEvent { id, kind, value });
+    }
+
+    // one event per flag raised in `res`
+    pub(crate) fn emit_state(&self, id: Id, res: &ResourceState, value: impl Fn() -> EventValue) {
+        if res.is_changed() {
+            self.emit_event(id, WidgetEventKind::Changed, value());
+        }
+        if res.is_submitted() {
+            self.emit_event(id, WidgetEventKind::Submitted, value());
+        }
+    }
+}
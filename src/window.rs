@@ -59,6 +59,14 @@ pub enum WindowState {
     Closed,
 }
 
+// what a double click on the title bar does
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TitleDoubleClick {
+    None,
+    Maximize, // toggles between the whole viewport and the previous rect
+    Collapse, // toggles between the title bar only and the previous height
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum Type {
     Dialog,
@@ -70,6 +78,15 @@ pub(crate) struct Window {
     pub(crate) ty: Type,
     pub(crate) win_state: WindowState,
     pub(crate) main: Container,
+    pub(crate) opt: ContainerOption, // options of the last begin_window
+    pub(crate) viewport: Recti,
+    pub(crate) keep_in_viewport: bool, // dragging stops at the viewport edges
+    pub(crate) title_double_click: TitleDoubleClick,
+    pub(crate) restore_rect: Option<Recti>, // set while maximized
+    pub(crate) maximize_request: Option<bool>,
+    pub(crate) collapsed_height: Option<i32>,
+    pub(crate) animation: Option<WindowAnimation>,
+    pub(crate) render_scale: Real,
 }
 
 impl Window {
@@ -81,6 +98,15 @@ impl Window {
             ty: Type::Dialog,
             win_state: WindowState::Closed,
             main,
+            opt: ContainerOption::NONE,
+            viewport: Recti::default(),
+            keep_in_viewport: false,
+            title_double_click: TitleDoubleClick::None,
+            restore_rect: None,
+            maximize_request: None,
+            collapsed_height: None,
+            animation: None,
+            render_scale: 1.0,
         }
     }
 
@@ -92,6 +118,15 @@ impl Window {
             ty: Type::Window,
             win_state: WindowState::Open,
             main,
+            opt: ContainerOption::NONE,
+            viewport: Recti::default(),
+            keep_in_viewport: false,
+            title_double_click: TitleDoubleClick::None,
+            restore_rect: None,
+            maximize_request: None,
+            collapsed_height: None,
+            animation: None,
+            render_scale: 1.0,
         }
     }
 
@@ -103,6 +138,15 @@ impl Window {
             ty: Type::Popup,
             win_state: WindowState::Closed,
             main,
+            opt: ContainerOption::NONE,
+            viewport: Recti::default(),
+            keep_in_viewport: false,
+            title_double_click: TitleDoubleClick::None,
+            restore_rect: None,
+            maximize_request: None,
+            collapsed_height: None,
+            animation: None,
+            render_scale: 1.0,
         }
     }
 
@@ -113,8 +157,47 @@ impl Window {
         }
     }
 
+    fn is_resizable(&self, opt: ContainerOption) -> bool {
+        !opt.is_auto_sizing() && !opt.is_fixed() && self.restore_rect.is_none() && self.collapsed_height.is_none()
+    }
+
+    // the resize zones reach outside the frame, the window is hovered there too
+    pub(crate) fn hover_rect(&self, opt: ContainerOption) -> Recti {
+        let r = self.main.rect;
+        let out = self.main.style.resize_outset;
+        if self.is_resizable(opt) && out > 0 {
+            rect(r.x, r.y, r.width + out, r.height + out)
+        } else {
+            r
+        }
+    }
+
+    // the viewport is only known once the window begins, requests are applied there
+    fn apply_maximized(&mut self, maximized: bool) {
+        match (maximized, self.restore_rect) {
+            (true, None) if !self.opt.is_fixed() => self.restore_rect = Some(self.main.rect),
+            (false, Some(r)) => {
+                self.main.rect = r;
+                self.restore_rect = None;
+            }
+            _ => (),
+        }
+    }
+
     #[inline(never)]
     fn begin_window(&mut self, opt: ContainerOption) {
+        self.opt = opt;
+        self.advance_animation();
+        if let Some(maximized) = self.maximize_request.take() {
+            self.apply_maximized(maximized);
+        }
+        if opt.is_fixed() {
+            self.apply_maximized(false);
+        }
+        if self.restore_rect.is_some() {
+            // follows the viewport while maximized
+            self.main.rect = self.viewport;
+        }
         let is_popup = self.is_popup();
         let container = &mut self.main;
         let mut body = container.rect;
@@ -137,9 +220,25 @@ impl Window {
                     ControlColor::TitleText,
                     WidgetOption::NONE,
                 );
-                if Some(id) == container.focus && container.input.borrow().mouse_down.is_left() {
+                if Some(id) == container.focus && container.input.borrow().mouse_double_clicked().is_left() {
+                    match self.title_double_click {
+                        TitleDoubleClick::None => (),
+                        TitleDoubleClick::Maximize => self.maximize_request = Some(self.restore_rect.is_none()),
+                        TitleDoubleClick::Collapse => match self.collapsed_height.take() {
+                            Some(h) => container.rect.height = h,
+                            None => {
+                                self.collapsed_height = Some(container.rect.height);
+                                container.rect.height = tr.height;
+                            }
+                        },
+                    }
+                    body = container.rect;
+                } else if Some(id) == container.focus && container.input.borrow().mouse_down.is_left() && self.restore_rect.is_none() {
                     container.rect.x += container.input.borrow().mouse_delta.x;
                     container.rect.y += container.input.borrow().mouse_delta.y;
+                    if self.keep_in_viewport {
+                        container.rect = clamp_rect_into(container.rect, self.viewport);
+                    }
                 }
                 body.y += tr.height;
                 body.height -= tr.height;
@@ -155,24 +254,55 @@ impl Window {
                     self.win_state = WindowState::Closed;
                 }
             }
+            if opt.has_maximize() && !opt.is_fixed() {
+                let id = container.idmngr.get_id_from_str("!maximize");
+                let r: Recti = rect(tr.x + tr.width - tr.height, tr.y, tr.height, tr.height);
+                tr.width -= r.width;
+                let color = container.style.colors[ControlColor::TitleText as usize];
+                let b = expand_rect(r, -r.height / 3);
+                if self.restore_rect.is_some() {
+                    container.draw_box(rect(b.x + 2, b.y - 2, b.width, b.height), color);
+                    container.draw_box(rect(b.x - 1, b.y + 1, b.width, b.height), color);
+                } else {
+                    container.draw_box(b, color);
+                }
+                container.update_control(id, r, WidgetOption::NONE);
+                if container.input.borrow().mouse_pressed.is_left() && Some(id) == container.focus {
+                    self.maximize_request = Some(self.restore_rect.is_none());
+                }
+            }
         }
         container.push_container_body(body, opt);
-        if !opt.is_auto_sizing() {
-            let sz = container.style.title_height;
-            let id_2 = container.idmngr.get_id_from_str("!resize");
-            let r_0 = rect(r.x + r.width - sz, r.y + r.height - sz, sz, sz);
-            container.update_control(id_2, r_0, WidgetOption::NONE);
-            if Some(id_2) == container.focus && container.input.borrow().mouse_down.is_left() {
-                container.rect.width = if 96 > container.rect.width + container.input.borrow().mouse_delta.x {
-                    96
-                } else {
-                    container.rect.width + container.input.borrow().mouse_delta.x
-                };
-                container.rect.height = if 64 > container.rect.height + container.input.borrow().mouse_delta.y {
-                    64
-                } else {
-                    container.rect.height + container.input.borrow().mouse_delta.y
-                };
+        // same as is_resizable, the title bar may have just collapsed the window
+        if !opt.is_auto_sizing() && !opt.is_fixed() && self.restore_rect.is_none() && self.collapsed_height.is_none() {
+            // the corner resizes both ways, the right and bottom edges one way each
+            let (sz, border, out) = (container.style.resize_grip_size, container.style.resize_border, container.style.resize_outset);
+            let th = if opt.has_no_title() { 0 } else { container.style.title_height };
+            let zones = [
+                ("!resize", rect(r.x + r.width - sz, r.y + r.height - sz, sz + out, sz + out), 1, 1),
+                ("!resize_x", rect(r.x + r.width - border, r.y + th, border + out, r.height - th - sz), 1, 0),
+                ("!resize_y", rect(r.x, r.y + r.height - border, r.width - sz, border + out), 0, 1),
+            ];
+            for (name, zone, dx, dy) in zones {
+                if border <= 0 && dx != dy {
+                    continue;
+                }
+                let id = container.idmngr.get_id_from_str(name);
+                container.update_control(id, zone, WidgetOption::NONE);
+                if Some(id) == container.focus && container.input.borrow().mouse_down.is_left() {
+                    let delta = container.input.borrow().mouse_delta;
+                    container.rect.width = max(96, container.rect.width + delta.x * dx);
+                    container.rect.height = max(64, container.rect.height + delta.y * dy);
+                }
+            }
+            if container.style.show_resize_grip {
+                let color = container.style.colors[ControlColor::ScrollThumb as usize];
+                let (right, bottom) = (r.x + r.width, r.y + r.height);
+                for i in 0..3 {
+                    for j in 0..3 - i {
+                        container.draw_rect(rect(right - 5 - 4 * i, bottom - 5 - 4 * j, 2, 2), color);
+                    }
+                }
             }
         }
         if opt.is_auto_sizing() {
@@ -192,6 +322,53 @@ impl Window {
         let container = &mut self.main;
         container.pop_clip_rect();
     }
+
+    // mirrors the regions laid out by begin_window
+    fn hit_test(&self, pos: Vec2i) -> Option<HitPart> {
+        let r = self.main.rect;
+        if self.opt.is_click_through() || !self.hover_rect(self.opt).contains(&pos) {
+            return None;
+        }
+        let th = self.main.style.title_height;
+        if !self.opt.has_no_title() && pos.y < r.y + th {
+            let mut right = r.x + r.width;
+            if !self.opt.has_no_close() {
+                if pos.x >= right - th {
+                    return Some(HitPart::CloseButton);
+                }
+                right -= th;
+            }
+            if self.opt.has_maximize() && !self.opt.is_fixed() && pos.x >= right - th {
+                return Some(HitPart::MaximizeButton);
+            }
+            return Some(HitPart::TitleBar);
+        }
+        if self.is_resizable(self.opt) {
+            let (sz, border) = (self.main.style.resize_grip_size, self.main.style.resize_border);
+            let (right, bottom) = (r.x + r.width, r.y + r.height);
+            let corner = pos.x >= right - sz && pos.y >= bottom - sz;
+            let edge = border > 0 && (pos.x >= right - border || pos.y >= bottom - border);
+            if corner || edge {
+                return Some(HitPart::ResizeGrip);
+            }
+        }
+        Some(HitPart::Body)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HitPart {
+    TitleBar,
+    CloseButton,
+    MaximizeButton,
+    ResizeGrip,
+    Body,
+}
+
+#[derive(Clone)]
+pub enum HitTarget {
+    None,
+    Window(WindowHandle, HitPart),
 }
 
 #[derive(Clone)]
@@ -230,17 +407,82 @@ impl WindowHandle {
     }
 
     pub(crate) fn render<R: Renderer>(&mut self, canvas: &mut Canvas<R>) {
-        self.0.borrow_mut().main.render(canvas)
+        let mut w = self.0.borrow_mut();
+        // the border is drawn a pixel outside the window rect and the resize zones up to the outset
+        let area = expand_rect(w.main.rect, max(w.main.style.resize_outset, 1));
+        let layered = w.render_scale > 1.0 && canvas.begin_scaled_layer(area, w.render_scale);
+        w.main.render(canvas);
+        if layered {
+            canvas.end_scaled_layer();
+        }
+    }
+
+    // Renders the window supersampled, e.g. 2.0 for smoother edges on the triangles and scaled images in
+    // screenshots and recordings. Text and icons still come from the 1x atlas, they are not sharper. The
+    // backend needs offscreen targets, the window is drawn as usual otherwise.
+    pub fn set_render_scale(&mut self, scale: Real) {
+        self.inner_mut().render_scale = scale.max(1.0);
+    }
+
+    pub fn render_scale(&self) -> Real {
+        self.inner().render_scale
     }
 
     pub(crate) fn finish(&mut self) {
         self.inner_mut().main.finish()
     }
 
-    pub(crate) fn zindex(&self) -> i32 {
+    pub fn zindex(&self) -> i32 {
         self.0.borrow().main.zindex
     }
 
+    // Windows with a negative z-index are never brought to the front when clicked, which keeps HUD layers
+    // behind. Windows with the same z-index are drawn in the order they were evaluated.
+    pub fn set_zindex(&mut self, zindex: i32) {
+        self.inner_mut().main.zindex = zindex;
+    }
+
+    pub fn hit_test(&self, pos: Vec2i) -> Option<HitPart> {
+        if !self.is_open() {
+            return None;
+        }
+        self.0.borrow().hit_test(pos)
+    }
+
+    pub fn name(&self) -> String {
+        self.0.borrow().main.name.clone()
+    }
+
+    pub fn ptr_eq(&self, other: &WindowHandle) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn on_title_double_click(&mut self, action: TitleDoubleClick) {
+        self.inner_mut().title_double_click = action;
+    }
+
+    pub fn title_double_click(&self) -> TitleDoubleClick {
+        self.0.borrow().title_double_click
+    }
+
+    // applied when the window begins, ignored for NO_RESIZE windows
+    pub fn set_maximized(&mut self, maximized: bool) {
+        self.inner_mut().maximize_request = Some(maximized);
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        let w = self.0.borrow();
+        w.maximize_request.unwrap_or(w.restore_rect.is_some())
+    }
+
+    pub fn set_background(&mut self, background: Option<BackgroundImage>) {
+        self.inner_mut().main.set_background(background);
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.0.borrow().collapsed_height.is_some()
+    }
+
     pub(crate) fn begin_window(&mut self, opt: ContainerOption) {
         self.0.borrow_mut().begin_window(opt)
     }
@@ -0,0 +1,103 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use super::*;
+
+// a tween of the window rect, advanced when the window begins
+#[derive(Clone, Copy)]
+pub(crate) struct WindowAnimation {
+    from: Recti,
+    to: Recti,
+    duration: Real,
+    elapsed: Real,
+    last: Recti, // what the animation set, anything else moved the window
+}
+
+fn same_rect(a: &Recti, b: &Recti) -> bool {
+    (a.x, a.y, a.width, a.height) == (b.x, b.y, b.width, b.height)
+}
+
+fn lerp_rect(a: Recti, b: Recti, t: Real) -> Recti {
+    let l = |a: i32, b: i32| a + ((b - a) as Real * t).round() as i32;
+    rect(l(a.x, b.x), l(a.y, b.y), l(a.width, b.width), l(a.height, b.height))
+}
+
+impl Window {
+    pub(crate) fn advance_animation(&mut self) {
+        let Some(mut anim) = self.animation.take() else {
+            return;
+        };
+        // grabbing, moving or maximizing the window cancels the animation where it is
+        let input = self.main.input.borrow();
+        let grabbed = !input.mouse_pressed.is_none() && self.main.in_hover_root && self.hover_rect(self.opt).contains(&input.mouse_pos);
+        let dt = input.frame_dt;
+        drop(input);
+        if grabbed || self.restore_rect.is_some() || !same_rect(&self.main.rect, &anim.last) {
+            return;
+        }
+
+        anim.elapsed += dt;
+        if anim.elapsed >= anim.duration {
+            self.main.rect = anim.to;
+            return;
+        }
+        // ease in and out
+        let t = anim.elapsed / anim.duration;
+        let t = t * t * (3.0 - 2.0 * t);
+        self.main.rect = lerp_rect(anim.from, anim.to, t);
+        anim.last = self.main.rect;
+        self.animation = Some(anim);
+    }
+}
+
+impl WindowHandle {
+    pub fn is_animating(&self) -> bool {
+        self.inner().animation.is_some()
+    }
+
+    // stops the animation, the window keeps its current rect
+    pub fn cancel_animation(&mut self) {
+        self.inner_mut().animation = None;
+    }
+}
+
+impl<R: Renderer> Context<R> {
+    // Tweens the rect of the window to `target` over `duration` seconds, e.g. when switching between workspace
+    // layouts. The window stops where it is if the user grabs it or something else moves it meanwhile.
+    pub fn animate_window_to(&mut self, window: &mut WindowHandle, target: Recti, duration: Real) {
+        let mut w = window.inner_mut();
+        if duration <= 0.0 {
+            w.main.rect = target;
+            w.animation = None;
+            return;
+        }
+        let from = w.main.rect;
+        w.animation = Some(WindowAnimation { from, to: target, duration, elapsed: 0.0, last: from });
+    }
+}
@@ -0,0 +1,147 @@
+//
+// Copyright 2022-Present (c) Raja Lehtihet & Wael El Oraiby
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+// this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+// this list of conditions and the following disclaimer in the documentation
+// and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+// may be used to endorse or promote products derived from this software without
+// specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE
+// ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE
+// LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+// CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF
+// SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS
+// INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN
+// CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE
+// POSSIBILITY OF SUCH DAMAGE.
+//
+use crate::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum WizardResult {
+    None,
+    Finished,
+    Cancelled,
+}
+
+pub struct WizardState {
+    win: WindowHandle,
+    steps: Vec<String>,
+    current: usize,
+    result: WizardResult,
+    pub extras: Extras,
+}
+
+impl WizardState {
+    pub fn new<R: Renderer>(ctx: &mut Context<R>, name: &str, initial_rect: Recti, steps: &[&str]) -> Self {
+        assert!(!steps.is_empty());
+        Self {
+            win: ctx.new_dialog(name, initial_rect),
+            steps: steps.iter().map(|s| s.to_string()).collect(),
+            current: 0,
+            result: WizardResult::None,
+            extras: Extras::default(),
+        }
+    }
+
+    pub fn current_step(&self) -> usize {
+        self.current
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn result(&self) -> WizardResult {
+        self.result
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.win.is_open()
+    }
+
+    pub fn open<R: Renderer>(&mut self, ctx: &mut Context<R>) {
+        self.current = 0;
+        self.result = WizardResult::None;
+        ctx.open_dialog(&mut self.win);
+    }
+
+    fn step_header(&self, cont: &mut Container) {
+        let count = self.steps.len() as i32;
+        cont.set_row_widths_height(&[-1], 0);
+        cont.label(format!("Step {} of {}: {}", self.current + 1, count, self.steps[self.current]).as_str());
+
+        // one bar per step, the completed & current ones are highlighted
+        cont.set_row_widths_height(&[-1], 4);
+        let r = cont.next_cell();
+        let spacing = cont.style.spacing;
+        let w = (r.width - spacing * (count - 1)) / count;
+        for i in 0..count {
+            let colorid = if i as usize <= self.current { ControlColor::ButtonFocus } else { ControlColor::Base };
+            let color = cont.style.colors[colorid as usize];
+            cont.draw_rect(rect(r.x + i * (w + spacing), r.y, w, r.height), color);
+        }
+    }
+
+    // `f` builds the page of the given step and returns whether its content is valid, the wizard won't allow
+    // moving forward (Next/Finish) before that
+    pub fn eval<R: Renderer, F: FnMut(usize, &mut Container) -> bool>(&mut self, ctx: &mut Context<R>, mut f: F) -> WizardResult {
+        let mut result = WizardResult::None;
+        ctx.dialog(&mut self.win.clone(), ContainerOption::NO_CLOSE, |cont| {
+            self.step_header(cont);
+
+            cont.set_row_widths_height(&[-1], -25);
+            let mut valid = false;
+            cont.column(|cont| {
+                cont.set_row_widths_height(&[-1], 0);
+                valid = f(self.current, cont);
+            });
+
+            let is_first = self.current == 0;
+            let is_last = self.current + 1 == self.steps.len();
+            let quarter = cont.body.width / 4;
+            cont.set_row_widths_height(&[quarter, quarter, quarter, -1], 0);
+
+            if cont.disabled(is_first, |c| c.button_ex("Back", None, WidgetOption::ALIGN_CENTER)).is_submitted() {
+                self.current -= 1;
+            }
+            if cont
+                .disabled(!valid, |c| c.button_ex(if is_last { "Finish" } else { "Next" }, None, WidgetOption::ALIGN_CENTER))
+                .is_submitted()
+            {
+                if is_last {
+                    result = WizardResult::Finished;
+                    return WindowState::Closed;
+                }
+                self.current += 1;
+            }
+            cont.next_cell();
+            if cont.button_ex("Cancel", None, WidgetOption::ALIGN_CENTER).is_submitted() {
+                result = WizardResult::Cancelled;
+                return WindowState::Closed;
+            }
+            WindowState::Open
+        });
+        if result != WizardResult::None {
+            self.result = result;
+            let name = self.win.name();
+            ctx.report_telemetry(match result {
+                WizardResult::Finished => TelemetryEvent::DialogConfirmed { name },
+                _ => TelemetryEvent::DialogCancelled { name },
+            });
+        }
+        result
+    }
+}